@@ -0,0 +1,326 @@
+use chrono::{Local, NaiveDateTime};
+use odin_database::{
+    DatabaseService,
+    entity::{
+        account::{self, Entity as AccountEntity},
+        account_ban::{self, Entity as AccountBanEntity},
+    },
+    password::hash_password,
+    sea_orm::{ActiveValue::Set, DbErr, QueryFilter, prelude::*, sea_query::Func},
+};
+use odin_models::{
+    account::{AccessLevel, BanType},
+    account_charlist::CharacterInfo,
+    uuid::Uuid,
+};
+use odin_repositories::account_repository::{AccountRepository, AccountRepositoryError};
+use thiserror::Error;
+
+pub struct AccountManager {
+    database: DatabaseService,
+}
+impl AccountManager {
+    pub fn new(database: DatabaseService) -> Self {
+        Self { database }
+    }
+
+    pub async fn create_account(
+        &self,
+        username: &str,
+        password: &str,
+        access: Option<AccessLevel>,
+    ) -> Result<Uuid, AccountManagerError> {
+        if self.find_account(username).await?.is_some() {
+            return Err(AccountManagerError::UsernameTaken(username.to_string()));
+        }
+
+        let account = account::ActiveModel {
+            username: Set(username.to_string()),
+            password: Set(hash_password(password)?),
+            access: Set(access_value(access.as_ref())),
+            ..Default::default()
+        }
+        .insert(&self.database.get_connection())
+        .await?;
+
+        Ok(account.id)
+    }
+
+    pub async fn reset_password(
+        &self,
+        username: &str,
+        password: &str,
+    ) -> Result<(), AccountManagerError> {
+        let account = self.get_account(username).await?;
+        account::ActiveModel {
+            id: Set(account.id),
+            password: Set(hash_password(password)?),
+            ..Default::default()
+        }
+        .update(&self.database.get_connection())
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn reset_token(&self, username: &str) -> Result<(), AccountManagerError> {
+        let account = self.get_account(username).await?;
+        account::ActiveModel {
+            id: Set(account.id),
+            token: Set(None),
+            token_failures: Set(0),
+            token_locked_until: Set(None),
+            ..Default::default()
+        }
+        .update(&self.database.get_connection())
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn set_access(
+        &self,
+        username: &str,
+        access: Option<AccessLevel>,
+    ) -> Result<(), AccountManagerError> {
+        let account = self.get_account(username).await?;
+        account::ActiveModel {
+            id: Set(account.id),
+            access: Set(access_value(access.as_ref())),
+            ..Default::default()
+        }
+        .update(&self.database.get_connection())
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn ban(
+        &self,
+        username: &str,
+        banned_by: &str,
+        r#type: BanType,
+        expires_at: NaiveDateTime,
+        reason: &str,
+    ) -> Result<(), AccountManagerError> {
+        let account = self.get_account(username).await?;
+        let banned_by = self.get_account(banned_by).await?;
+
+        AccountBanEntity::insert(account_ban::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            account_id: Set(account.id),
+            account_banned_by: Set(banned_by.id),
+            expires_at: Set(expires_at),
+            reason: Set(reason.to_string()),
+            r#type: Set(match r#type {
+                BanType::Analysis => account_ban::BanType::Analysis,
+                BanType::Blocked => account_ban::BanType::Blocked,
+            }),
+            ..Default::default()
+        })
+        .exec_without_returning(&self.database.get_connection())
+        .await?;
+
+        Ok(())
+    }
+
+    // Bans are kept for history, lifting one only makes it expire now
+    pub async fn unban(&self, username: &str) -> Result<u64, AccountManagerError> {
+        let account = self.get_account(username).await?;
+        let now = Local::now().naive_local();
+
+        let result = AccountBanEntity::update_many()
+            .col_expr(account_ban::Column::ExpiresAt, Expr::value(now))
+            .filter(account_ban::Column::AccountId.eq(account.id))
+            .filter(account_ban::Column::ExpiresAt.gt(now))
+            .exec(&self.database.get_connection())
+            .await?;
+
+        Ok(result.rows_affected)
+    }
+
+    pub async fn list_characters(
+        &self,
+        username: &str,
+    ) -> Result<Vec<(usize, CharacterInfo)>, AccountManagerError> {
+        let account = self.get_account(username).await?;
+        Ok(self
+            .database
+            .account_repository()
+            .fetch_charlist(account.id)
+            .await?)
+    }
+
+    async fn find_account(&self, username: &str) -> Result<Option<account::Model>, DbErr> {
+        AccountEntity::find()
+            .filter(
+                Expr::expr(Func::lower(Expr::col(account::Column::Username)))
+                    .eq(Expr::expr(Func::lower(Expr::value(username)))),
+            )
+            .one(&self.database.get_connection())
+            .await
+    }
+
+    async fn get_account(&self, username: &str) -> Result<account::Model, AccountManagerError> {
+        self.find_account(username)
+            .await?
+            .ok_or_else(|| AccountManagerError::AccountNotFound(username.to_string()))
+    }
+}
+
+fn access_value(access: Option<&AccessLevel>) -> i32 {
+    access.map(|access| access.get_level() as i32).unwrap_or(0)
+}
+
+#[derive(Debug, Error)]
+pub enum AccountManagerError {
+    #[error("Account {0} was not found")]
+    AccountNotFound(String),
+
+    #[error("Username {0} is already taken")]
+    UsernameTaken(String),
+
+    #[error(transparent)]
+    Database(#[from] DbErr),
+
+    #[error(transparent)]
+    Repository(#[from] AccountRepositoryError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+    use odin_database::{
+        password::{PasswordMatch, verify_password},
+        sea_orm::Database,
+    };
+    use odin_models::{character::Class, nickname::Nickname};
+
+    async fn manager() -> AccountManager {
+        let database =
+            DatabaseService::from_database(Database::connect("sqlite::memory:").await.unwrap());
+        database.fresh().await.unwrap();
+        AccountManager::new(database)
+    }
+
+    async fn fetch(manager: &AccountManager, username: &str) -> account::Model {
+        manager.get_account(username).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn creates_an_account_with_a_hashed_password() {
+        let manager = manager().await;
+        manager
+            .create_account("admin", "secret", Some(AccessLevel::Administrator))
+            .await
+            .unwrap();
+
+        let account = fetch(&manager, "ADMIN").await;
+        assert_eq!(account.access, 100);
+        assert_eq!(
+            verify_password("secret", &account.password),
+            PasswordMatch::Hashed
+        );
+        assert!(matches!(
+            manager.create_account("Admin", "other", None).await,
+            Err(AccountManagerError::UsernameTaken(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn resets_password_token_and_access() {
+        let manager = manager().await;
+        let account_id = manager.create_account("player", "old", None).await.unwrap();
+        let repository = manager.database.account_repository();
+        repository
+            .update_token(account_id, Some("1234".to_string()))
+            .await
+            .unwrap();
+        repository
+            .lock_token(account_id, Local::now().naive_local() + TimeDelta::hours(1))
+            .await
+            .unwrap();
+
+        manager.reset_password("player", "new").await.unwrap();
+        manager.reset_token("player").await.unwrap();
+        manager
+            .set_access("player", Some(AccessLevel::GameMaster(10)))
+            .await
+            .unwrap();
+
+        let account = fetch(&manager, "player").await;
+        assert_eq!(
+            verify_password("new", &account.password),
+            PasswordMatch::Hashed
+        );
+        assert_eq!(account.token, None);
+        assert_eq!(account.token_locked_until, None);
+        assert_eq!(account.access, 10);
+    }
+
+    #[tokio::test]
+    async fn bans_and_unbans_an_account() {
+        let manager = manager().await;
+        manager
+            .create_account("admin", "admin", None)
+            .await
+            .unwrap();
+        manager
+            .create_account("player", "player", None)
+            .await
+            .unwrap();
+        let repository = manager.database.account_repository();
+
+        let expiration = Local::now().naive_local() + TimeDelta::days(3);
+        manager
+            .ban("player", "admin", BanType::Blocked, expiration, "cheating")
+            .await
+            .unwrap();
+        let ban = repository
+            .fetch_account("player")
+            .await
+            .unwrap()
+            .unwrap()
+            .ban;
+        assert_eq!(ban.map(|ban| ban.r#type), Some(BanType::Blocked));
+
+        assert_eq!(manager.unban("player").await.unwrap(), 1);
+        let ban = repository
+            .fetch_account("player")
+            .await
+            .unwrap()
+            .unwrap()
+            .ban;
+        assert!(ban.is_none());
+    }
+
+    #[tokio::test]
+    async fn lists_characters_of_an_account() {
+        let manager = manager().await;
+        let account_id = manager
+            .create_account("player", "player", None)
+            .await
+            .unwrap();
+        manager
+            .database
+            .account_repository()
+            .create_character(
+                account_id,
+                1,
+                &Nickname::try_from("Hero").unwrap(),
+                Class::Huntress,
+            )
+            .await
+            .unwrap();
+
+        let characters = manager.list_characters("player").await.unwrap();
+        assert_eq!(characters.len(), 1);
+        assert_eq!(characters[0].0, 1);
+        assert_eq!(characters[0].1.name, "Hero");
+        assert!(matches!(
+            manager.list_characters("nobody").await,
+            Err(AccountManagerError::AccountNotFound(_))
+        ));
+    }
+}
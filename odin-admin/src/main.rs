@@ -0,0 +1,144 @@
+pub mod accounts;
+
+use accounts::AccountManager;
+use chrono::{Local, TimeDelta};
+use clap::{Parser, Subcommand, ValueEnum};
+use odin_database::DatabaseService;
+use odin_models::account::{AccessLevel, BanType};
+
+#[derive(Parser)]
+#[command(author, version, about = "Odin account management", long_about = None)]
+struct Cli {
+    /// Database to connect to, read from DATABASE_URL when omitted
+    #[arg(long)]
+    database_url: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a new account
+    CreateAccount {
+        username: String,
+        password: String,
+        /// 1 to 99 for a game master, 100 for an administrator
+        #[arg(long)]
+        access: Option<u32>,
+    },
+    /// Set a new password for an account
+    ResetPassword { username: String, password: String },
+    /// Clear the numeric token, it is set again on the next login
+    ResetToken { username: String },
+    /// Set the access level, 0 removes it
+    GrantAccess { username: String, level: u32 },
+    /// Ban an account for the given amount of days
+    Ban {
+        username: String,
+        #[arg(long, value_enum)]
+        r#type: BanKind,
+        #[arg(long)]
+        days: u32,
+        /// Account responsible for the ban
+        #[arg(long)]
+        by: String,
+        #[arg(long, default_value = "")]
+        reason: String,
+    },
+    /// Lift every active ban of an account
+    Unban { username: String },
+    /// List the characters of an account
+    ListCharacters { username: String },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum BanKind {
+    Analysis,
+    Blocked,
+}
+impl From<BanKind> for BanType {
+    fn from(value: BanKind) -> Self {
+        match value {
+            BanKind::Analysis => BanType::Analysis,
+            BanKind::Blocked => BanType::Blocked,
+        }
+    }
+}
+
+fn access_level(level: u32) -> Option<AccessLevel> {
+    match level {
+        0 => None,
+        100.. => Some(AccessLevel::Administrator),
+        level => Some(AccessLevel::GameMaster(level)),
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    dotenvy::dotenv().ok();
+
+    let database_url = cli
+        .database_url
+        .or_else(|| dotenvy::var("DATABASE_URL").ok())
+        .expect("Database URL is mandatory");
+    let database = DatabaseService::new(&database_url)
+        .await
+        .expect("Failed to connect to the database");
+    let manager = AccountManager::new(database);
+
+    let result = match cli.command {
+        Command::CreateAccount {
+            username,
+            password,
+            access,
+        } => manager
+            .create_account(&username, &password, access.and_then(access_level))
+            .await
+            .map(|id| println!("Created account {username} ({id})")),
+        Command::ResetPassword { username, password } => manager
+            .reset_password(&username, &password)
+            .await
+            .map(|_| println!("Password of {username} changed")),
+        Command::ResetToken { username } => manager
+            .reset_token(&username)
+            .await
+            .map(|_| println!("Numeric token of {username} cleared")),
+        Command::GrantAccess { username, level } => manager
+            .set_access(&username, access_level(level))
+            .await
+            .map(|_| println!("Access level of {username} set to {level}")),
+        Command::Ban {
+            username,
+            r#type,
+            days,
+            by,
+            reason,
+        } => {
+            let expires_at = Local::now().naive_local() + TimeDelta::days(days as i64);
+            manager
+                .ban(&username, &by, r#type.into(), expires_at, &reason)
+                .await
+                .map(|_| println!("{username} banned until {expires_at}"))
+        }
+        Command::Unban { username } => manager
+            .unban(&username)
+            .await
+            .map(|lifted| println!("Lifted {lifted} bans of {username}")),
+        Command::ListCharacters { username } => {
+            manager.list_characters(&username).await.map(|characters| {
+                for (slot, character) in characters {
+                    println!(
+                        "{slot}: {} level {} {:?}",
+                        character.name, character.status.level, character.class
+                    );
+                }
+            })
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("{e}");
+        std::process::exit(1);
+    }
+}
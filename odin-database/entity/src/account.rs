@@ -15,6 +15,8 @@ pub struct Model {
     pub access: i32,
     pub storage_coin: i64,
     pub token: Option<String>,
+    pub token_failures: i32,
+    pub token_locked_until: Option<DateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter)]
@@ -34,6 +34,8 @@ pub struct Model {
     pub learned1: i32,
     pub learned2: i32,
     pub guild_level: Option<i16>,
+    pub skill_bonus: i16,
+    pub skill_bar: Option<Vec<u8>>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter)]
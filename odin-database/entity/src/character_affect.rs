@@ -0,0 +1,55 @@
+use crate::ActiveValueExt;
+use async_trait::async_trait;
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "character_affect")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub character_id: Uuid,
+    pub affect_id: i16,
+    pub value: i32,
+    pub remaining_ticks: i32,
+    pub source_type: i16,
+    pub source_id: i16,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::character::Entity",
+        from = "Column::CharacterId",
+        to = "super::character::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Character,
+}
+impl Related<super::character::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Character.def()
+    }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(mut self, _db: &C, insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        self.id.generate_new_uuid(insert);
+        Ok(self)
+    }
+}
+
+impl From<Model> for odin_models::affect::Affect {
+    fn from(value: Model) -> Self {
+        odin_models::affect::Affect {
+            id: value.affect_id as u8,
+            value: value.value,
+            remaining_ticks: value.remaining_ticks.max(0) as u32,
+            source: odin_models::affect::AffectSource::from_raw(value.source_type, value.source_id),
+        }
+    }
+}
@@ -9,9 +9,13 @@ pub struct Model {
     pub fame: i32,
     pub kingdom: i32,
     pub wins: i32,
+    #[sea_orm(column_name = "sub_guild1")]
     pub sub_guild_1: String,
+    #[sea_orm(column_name = "sub_guild2")]
     pub sub_guild_2: String,
+    #[sea_orm(column_name = "sub_guild3")]
     pub sub_guild_3: String,
+    pub notice: String,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter)]
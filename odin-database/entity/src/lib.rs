@@ -1,9 +1,11 @@
 pub mod account;
 pub mod account_ban;
 pub mod character;
+pub mod character_affect;
 pub mod guilds;
 pub mod item;
 pub mod start_item;
+pub mod storage_item;
 pub mod storage_start_items;
 
 use sea_orm::ActiveValue;
@@ -0,0 +1,60 @@
+use crate::ActiveValueExt;
+use async_trait::async_trait;
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[sea_orm(table_name = "storage_item")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub slot: i16,
+    pub item_id: i16,
+    pub ef1: i16,
+    pub efv1: i16,
+    pub ef2: i16,
+    pub efv2: i16,
+    pub ef3: i16,
+    pub efv3: i16,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::account::Entity",
+        from = "Column::AccountId",
+        to = "super::account::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    Account,
+}
+impl Related<super::account::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Account.def()
+    }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {
+    async fn before_save<C>(mut self, _db: &C, insert: bool) -> Result<Self, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        self.id.generate_new_uuid(insert);
+        Ok(self)
+    }
+}
+
+impl From<Model> for odin_models::item::Item {
+    fn from(value: Model) -> Self {
+        odin_models::item::Item {
+            id: value.item_id as u16,
+            effects: [
+                (value.ef1 as u8, value.efv1 as u8).into(),
+                (value.ef2 as u8, value.efv2 as u8).into(),
+                (value.ef3 as u8, value.efv3 as u8).into(),
+            ],
+        }
+    }
+}
@@ -10,6 +10,11 @@ impl MigratorTrait for Migrator {
             Box::new(m20241029_210508_characters::Migration),
             Box::new(m20241029_222100_items::Migration),
             Box::new(m20241103_141804_start_items::Migration),
+            Box::new(m20261017_120000_account_token_lockout::Migration),
+            Box::new(m20261017_130000_storage_items::Migration),
+            Box::new(m20261017_140000_character_affects::Migration),
+            Box::new(m20261017_150000_character_skills::Migration),
+            Box::new(m20261017_160000_guild_notice::Migration),
         ]
     }
 }
@@ -17,3 +22,8 @@ mod m20241026_013531_create_accounts_table;
 mod m20241029_210508_characters;
 mod m20241029_222100_items;
 mod m20241103_141804_start_items;
+mod m20261017_120000_account_token_lockout;
+mod m20261017_130000_storage_items;
+mod m20261017_140000_character_affects;
+mod m20261017_150000_character_skills;
+mod m20261017_160000_guild_notice;
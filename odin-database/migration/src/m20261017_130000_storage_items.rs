@@ -0,0 +1,77 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20241026_013531_create_accounts_table::Account;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let mut table = Table::create();
+        table
+            .if_not_exists()
+            .table(StorageItem::Table)
+            .col(
+                ColumnDef::new(StorageItem::Id)
+                    .uuid()
+                    .not_null()
+                    .primary_key(),
+            )
+            .col(ColumnDef::new(StorageItem::AccountId).uuid().not_null())
+            .col(ColumnDef::new(StorageItem::Slot).small_integer().not_null());
+        for column in [
+            StorageItem::ItemId,
+            StorageItem::Ef1,
+            StorageItem::Efv1,
+            StorageItem::Ef2,
+            StorageItem::Efv2,
+            StorageItem::Ef3,
+            StorageItem::Efv3,
+        ] {
+            table.col(ColumnDef::new(column).small_integer().default(0).not_null());
+        }
+
+        manager
+            .create_table(
+                table
+                    .index(
+                        Index::create()
+                            .table(StorageItem::Table)
+                            .col(StorageItem::AccountId)
+                            .col(StorageItem::Slot)
+                            .unique(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(StorageItem::Table, StorageItem::AccountId)
+                            .to(Account::Table, Account::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(StorageItem::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum StorageItem {
+    Table,
+    Id,
+    AccountId,
+    Slot,
+    #[allow(clippy::enum_variant_names)]
+    ItemId,
+    Ef1,
+    Efv1,
+    Ef2,
+    Efv2,
+    Ef3,
+    Efv3,
+}
@@ -0,0 +1,91 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20241029_210508_characters::Character;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .if_not_exists()
+                    .table(CharacterAffect::Table)
+                    .col(
+                        ColumnDef::new(CharacterAffect::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(CharacterAffect::CharacterId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(CharacterAffect::AffectId)
+                            .small_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(CharacterAffect::Value)
+                            .integer()
+                            .default(0)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(CharacterAffect::RemainingTicks)
+                            .integer()
+                            .default(0)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(CharacterAffect::SourceType)
+                            .small_integer()
+                            .default(0)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(CharacterAffect::SourceId)
+                            .small_integer()
+                            .default(0)
+                            .not_null(),
+                    )
+                    .index(
+                        Index::create()
+                            .table(CharacterAffect::Table)
+                            .col(CharacterAffect::CharacterId)
+                            .col(CharacterAffect::AffectId)
+                            .unique(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(CharacterAffect::Table, CharacterAffect::CharacterId)
+                            .to(Character::Table, Character::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CharacterAffect::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CharacterAffect {
+    Table,
+    Id,
+    CharacterId,
+    AffectId,
+    Value,
+    RemainingTicks,
+    SourceType,
+    SourceId,
+}
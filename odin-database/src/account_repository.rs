@@ -1,25 +1,31 @@
-use chrono::Local;
+use crate::password::{PasswordMatch, hash_password_blocking, verify_password_blocking};
+use chrono::{Local, NaiveDateTime};
 use entity::{
     account::Entity as AccountEntity,
     account_ban::Entity as AccountBanEntity,
     character::{Entity as CharacterEntity, Evolution, Model as Character},
+    character_affect::Entity as CharacterAffectEntity,
     item::{Entity as ItemEntity, ItemCategory},
     start_item::Entity as StartItemEntity,
+    storage_item::Entity as StorageItemEntity,
 };
 use odin_models::{
     EquipmentSlot, EquipmentSlots, InventorySlots,
     account::{AccessLevel, Ban, BanType},
     account_charlist::{AccountCharlist, CharacterInfo},
+    affect::Affect,
     character::{Character as CharacterModel, Class, GuildLevel},
     item::Item,
     nickname::Nickname,
     position::Position,
+    skill::{LearnedSkills, SkillBar},
     status::Score,
+    storage::Storage,
 };
 use odin_repositories::account_repository::{AccountRepository, AccountRepositoryError};
 use sea_orm::{
-    ActiveValue, DatabaseConnection, FromQueryResult, QueryOrder, QuerySelect, SelectColumns, Set,
-    TransactionTrait, prelude::*,
+    ActiveValue, DatabaseConnection, DatabaseTransaction, FromQueryResult, QueryOrder, QuerySelect,
+    SelectColumns, Set, TransactionTrait, prelude::*,
 };
 use sea_query::Func;
 
@@ -136,7 +142,7 @@ impl AccountRepository for DatabaseAccountRepository {
                 },
             }),
             access,
-            storage: Default::default(),
+            storage: self.fetch_storage(account.id).await?,
             charlist,
         }))
     }
@@ -185,6 +191,16 @@ impl AccountRepository for DatabaseAccountRepository {
             .await
             .map_err(map_to_fail_to_load)?;
 
+        let affects = CharacterAffectEntity::find()
+            .filter(entity::character_affect::Column::CharacterId.eq(character.id))
+            .all(&self.connection)
+            .await
+            .map_err(map_to_fail_to_load)?
+            .into_iter()
+            .map(Affect::from)
+            .collect::<Vec<_>>()
+            .into();
+
         let mut equipments = EquipmentSlots::default();
         let mut inventory = InventorySlots::default();
 
@@ -235,6 +251,17 @@ impl AccountRepository for DatabaseAccountRepository {
             last_pos: Position::try_from(character.last_pos.as_str()).unwrap_or_default(),
             inventory,
             equipments,
+            affects,
+            learned_skills: LearnedSkills::from_raw([
+                character.learned1 as u32,
+                character.learned2 as u32,
+            ]),
+            skill_bar: character
+                .skill_bar
+                .as_deref()
+                .map(SkillBar::from_raw)
+                .unwrap_or_default(),
+            skill_bonus: character.skill_bonus,
         }))
     }
 
@@ -266,6 +293,74 @@ impl AccountRepository for DatabaseAccountRepository {
             .and_then(|x| x.token))
     }
 
+    async fn record_token_failure(&self, id: Uuid) -> Result<u32, AccountRepositoryError> {
+        let result = AccountEntity::update_many()
+            .col_expr(
+                entity::account::Column::TokenFailures,
+                Expr::col(entity::account::Column::TokenFailures).add(1),
+            )
+            .filter(entity::account::Column::Id.eq(id))
+            .exec(&self.connection)
+            .await
+            .map_err(map_to_generic)?;
+        if result.rows_affected == 0 {
+            return Err(AccountRepositoryError::EntityNotFound);
+        }
+
+        let account = AccountEntity::find_by_id(id)
+            .one(&self.connection)
+            .await
+            .map_err(map_to_fail_to_load)?
+            .ok_or(AccountRepositoryError::EntityNotFound)?;
+        Ok(account.token_failures as u32)
+    }
+
+    async fn reset_token_failures(&self, id: Uuid) -> Result<(), AccountRepositoryError> {
+        let account = entity::account::ActiveModel {
+            id: ActiveValue::Set(id),
+            token_failures: Set(0),
+            ..Default::default()
+        };
+
+        account
+            .update(&self.connection)
+            .await
+            .map_err(map_to_generic)?;
+
+        Ok(())
+    }
+
+    async fn lock_token(
+        &self,
+        id: Uuid,
+        until: NaiveDateTime,
+    ) -> Result<(), AccountRepositoryError> {
+        let account = entity::account::ActiveModel {
+            id: ActiveValue::Set(id),
+            token_failures: Set(0),
+            token_locked_until: Set(Some(until)),
+            ..Default::default()
+        };
+
+        account
+            .update(&self.connection)
+            .await
+            .map_err(map_to_generic)?;
+
+        Ok(())
+    }
+
+    async fn get_token_lock(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<NaiveDateTime>, AccountRepositoryError> {
+        Ok(AccountEntity::find_by_id(id)
+            .one(&self.connection)
+            .await
+            .map_err(map_to_fail_to_load)?
+            .and_then(|x| x.token_locked_until))
+    }
+
     async fn create_character(
         &self,
         account_id: Uuid,
@@ -412,7 +507,7 @@ impl AccountRepository for DatabaseAccountRepository {
         account_id: Uuid,
         password: &str,
     ) -> Result<bool, AccountRepositoryError> {
-        let r: String = AccountEntity::find()
+        let stored: String = AccountEntity::find()
             .select_only()
             .column(entity::account::Column::Password)
             .filter(entity::account::Column::Id.eq(account_id))
@@ -422,10 +517,309 @@ impl AccountRepository for DatabaseAccountRepository {
             .map_err(map_to_fail_to_load)?
             .ok_or(AccountRepositoryError::EntityNotFound)?;
 
-        Ok(r == password)
+        match verify_password_blocking(password.to_string(), stored).await? {
+            PasswordMatch::Hashed => Ok(true),
+            PasswordMatch::Legacy => {
+                let account = entity::account::ActiveModel {
+                    id: ActiveValue::Set(account_id),
+                    password: Set(hash_password_blocking(password.to_string()).await?),
+                    ..Default::default()
+                };
+                account
+                    .update(&self.connection)
+                    .await
+                    .map_err(map_to_generic)?;
+                log::info!("Upgraded legacy password of account {}", account_id);
+
+                Ok(true)
+            }
+            PasswordMatch::Mismatch => Ok(false),
+        }
+    }
+
+    async fn save_character(
+        &self,
+        character: &CharacterModel,
+    ) -> Result<(), AccountRepositoryError> {
+        self.save_characters(std::slice::from_ref(character)).await
+    }
+
+    async fn save_characters(
+        &self,
+        characters: &[CharacterModel],
+    ) -> Result<(), AccountRepositoryError> {
+        let rows = characters.iter().map(character_rows).collect::<Vec<_>>();
+
+        self.connection
+            .transaction(|transaction| {
+                Box::pin(async move {
+                    for character_rows in rows {
+                        write_character_rows(transaction, character_rows).await?;
+                    }
+
+                    Result::<(), DbErr>::Ok(())
+                })
+            })
+            .await
+            .map_err(|err| match err {
+                sea_orm::TransactionError::Connection(db_err) => map_to_generic(db_err),
+                sea_orm::TransactionError::Transaction(db_err) => map_to_generic(db_err),
+            })
+    }
+
+    async fn fetch_storage(&self, account_id: Uuid) -> Result<Storage, AccountRepositoryError> {
+        let coin: i64 = AccountEntity::find()
+            .select_only()
+            .column(entity::account::Column::StorageCoin)
+            .filter(entity::account::Column::Id.eq(account_id))
+            .into_tuple()
+            .one(&self.connection)
+            .await
+            .map_err(map_to_fail_to_load)?
+            .ok_or(AccountRepositoryError::EntityNotFound)?;
+
+        let items = StorageItemEntity::find()
+            .filter(entity::storage_item::Column::AccountId.eq(account_id))
+            .all(&self.connection)
+            .await
+            .map_err(map_to_fail_to_load)?
+            .into_iter()
+            .map(|item| (item.slot as usize, Item::from(item)))
+            .collect::<Vec<_>>()
+            .into();
+
+        Ok(Storage {
+            items,
+            coin: coin.max(0) as u64,
+        })
+    }
+
+    async fn save_storage(
+        &self,
+        account_id: Uuid,
+        storage: &Storage,
+    ) -> Result<(), AccountRepositoryError> {
+        let rows = storage_rows(account_id, storage);
+
+        self.connection
+            .transaction(|transaction| {
+                Box::pin(async move { write_storage_rows(transaction, rows).await })
+            })
+            .await
+            .map_err(|err| match err {
+                sea_orm::TransactionError::Connection(db_err) => map_to_generic(db_err),
+                sea_orm::TransactionError::Transaction(db_err) => map_to_generic(db_err),
+            })
+    }
+
+    async fn save_player(
+        &self,
+        character: &CharacterModel,
+        account_id: Uuid,
+        storage: &Storage,
+    ) -> Result<(), AccountRepositoryError> {
+        let character_rows = character_rows(character);
+        let storage_rows = storage_rows(account_id, storage);
+
+        self.connection
+            .transaction(|transaction| {
+                Box::pin(async move {
+                    write_character_rows(transaction, character_rows).await?;
+                    write_storage_rows(transaction, storage_rows).await
+                })
+            })
+            .await
+            .map_err(|err| match err {
+                sea_orm::TransactionError::Connection(db_err) => map_to_generic(db_err),
+                sea_orm::TransactionError::Transaction(db_err) => map_to_generic(db_err),
+            })
     }
 }
 
+type CharacterRows = (
+    Uuid,
+    entity::character::ActiveModel,
+    Vec<entity::item::ActiveModel>,
+    Vec<entity::character_affect::ActiveModel>,
+);
+
+fn character_rows(character: &CharacterModel) -> CharacterRows {
+    let character_id = character.identifier;
+    let character_row = entity::character::ActiveModel {
+        id: Set(character_id),
+        name: Set(character.name.clone()),
+        merchant: Set(character.merchant),
+        guild_id: Set(character.guild),
+        guild_level: Set(character.guild_level.map(|level| level.as_raw() as i16)),
+        class: Set(character.class.into()),
+        evolution: Set(character.evolution.into()),
+        affect_info: Set(character.affect_info),
+        quest_info: Set(character.quest_info),
+        coin: Set(character.coin),
+        experience: Set(character.experience),
+        last_pos: Set(format!("({})", character.last_pos)),
+        level: Set(character.score.level as i32),
+        reserved: Set(character.score.reserved as i32),
+        strength: Set(character.score.strength as i32),
+        intelligence: Set(character.score.intelligence as i32),
+        dexterity: Set(character.score.dexterity as i32),
+        constitution: Set(character.score.constitution as i32),
+        special0: Set(character.score.specials[0] as i32),
+        special1: Set(character.score.specials[1] as i32),
+        special2: Set(character.score.specials[2] as i32),
+        special3: Set(character.score.specials[3] as i32),
+        current_hp: Set(character.score.hp as i32),
+        current_mp: Set(character.score.mp as i32),
+        learned1: Set(character.learned_skills.to_raw()[0] as i32),
+        learned2: Set(character.learned_skills.to_raw()[1] as i32),
+        skill_bonus: Set(character.skill_bonus),
+        skill_bar: Set(Some(character.skill_bar.to_raw().to_vec())),
+        ..Default::default()
+    };
+
+    let mut items = item_active_models(
+        character_id,
+        character
+            .equipments
+            .iter()
+            .map(|(slot, item)| (slot.as_index(), item)),
+        ItemCategory::Equip,
+    );
+    items.extend(item_active_models(
+        character_id,
+        character.inventory.iter(),
+        ItemCategory::Inventory,
+    ));
+    let affects = character
+        .affects
+        .iter()
+        .map(|affect| {
+            let (source_type, source_id) = affect.source.as_raw();
+            entity::character_affect::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                character_id: Set(character_id),
+                affect_id: Set(affect.id as i16),
+                value: Set(affect.value),
+                remaining_ticks: Set(affect.remaining_ticks as i32),
+                source_type: Set(source_type),
+                source_id: Set(source_id),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    (character_id, character_row, items, affects)
+}
+
+async fn write_character_rows(
+    transaction: &DatabaseTransaction,
+    (character_id, character_row, items, affects): CharacterRows,
+) -> Result<(), DbErr> {
+    character_row.update(transaction).await?;
+
+    ItemEntity::delete_many()
+        .filter(entity::item::Column::CharacterId.eq(character_id))
+        .filter(entity::item::Column::Type.is_in([ItemCategory::Equip, ItemCategory::Inventory]))
+        .exec(transaction)
+        .await?;
+
+    if !items.is_empty() {
+        ItemEntity::insert_many(items)
+            .exec_without_returning(transaction)
+            .await?;
+    }
+
+    CharacterAffectEntity::delete_many()
+        .filter(entity::character_affect::Column::CharacterId.eq(character_id))
+        .exec(transaction)
+        .await?;
+
+    if !affects.is_empty() {
+        CharacterAffectEntity::insert_many(affects)
+            .exec_without_returning(transaction)
+            .await?;
+    }
+
+    Ok(())
+}
+
+type StorageRows = (
+    Uuid,
+    entity::account::ActiveModel,
+    Vec<entity::storage_item::ActiveModel>,
+);
+
+fn storage_rows(account_id: Uuid, storage: &Storage) -> StorageRows {
+    let account = entity::account::ActiveModel {
+        id: ActiveValue::Set(account_id),
+        storage_coin: Set(storage.coin as i64),
+        ..Default::default()
+    };
+    let items = storage
+        .items
+        .iter()
+        .map(|(slot, item)| entity::storage_item::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            account_id: Set(account_id),
+            slot: Set(slot as i16),
+            item_id: Set(item.id as i16),
+            ef1: Set(item.effects[0].index as i16),
+            efv1: Set(item.effects[0].value as i16),
+            ef2: Set(item.effects[1].index as i16),
+            efv2: Set(item.effects[1].value as i16),
+            ef3: Set(item.effects[2].index as i16),
+            efv3: Set(item.effects[2].value as i16),
+        })
+        .collect::<Vec<_>>();
+
+    (account_id, account, items)
+}
+
+async fn write_storage_rows(
+    transaction: &DatabaseTransaction,
+    (account_id, account, items): StorageRows,
+) -> Result<(), DbErr> {
+    account.update(transaction).await?;
+
+    StorageItemEntity::delete_many()
+        .filter(entity::storage_item::Column::AccountId.eq(account_id))
+        .exec(transaction)
+        .await?;
+
+    if !items.is_empty() {
+        StorageItemEntity::insert_many(items)
+            .exec_without_returning(transaction)
+            .await?;
+    }
+
+    Ok(())
+}
+
+fn item_active_models<'a>(
+    character_id: Uuid,
+    items: impl Iterator<Item = (usize, &'a Item)>,
+    category: ItemCategory,
+) -> Vec<entity::item::ActiveModel> {
+    items
+        .map(|(slot, item)| entity::item::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            r#type: Set(category),
+            item_id: Set(item.id as i16),
+            ef1: Set(item.effects[0].index as i16),
+            efv1: Set(item.effects[0].value as i16),
+            ef2: Set(item.effects[1].index as i16),
+            efv2: Set(item.effects[1].value as i16),
+            ef3: Set(item.effects[2].index as i16),
+            efv3: Set(item.effects[2].value as i16),
+            ef4: Set(0),
+            efv4: Set(0),
+            ef5: Set(0),
+            efv5: Set(0),
+            slot: Set(slot as i16),
+            character_id: Set(character_id),
+        })
+        .collect()
+}
+
 #[derive(DerivePartialModel, FromQueryResult)]
 #[sea_orm(entity = "CharacterEntity")]
 struct BaseCharacterPartialModel {
@@ -0,0 +1,216 @@
+use entity::{
+    character::Entity as CharacterEntity,
+    guilds::{Entity as GuildEntity, Model as GuildRow},
+};
+use odin_models::{character::GuildLevel, uuid::Uuid};
+use odin_repositories::guild_repository::{Guild, GuildRepository, GuildRepositoryError};
+use sea_orm::{DatabaseConnection, QueryOrder, QuerySelect, Set, TransactionTrait, prelude::*};
+use sea_query::Func;
+
+#[derive(Clone)]
+pub struct DatabaseGuildRepository {
+    connection: DatabaseConnection,
+}
+impl DatabaseGuildRepository {
+    pub fn new(connection: DatabaseConnection) -> Self {
+        Self { connection }
+    }
+}
+impl GuildRepository for DatabaseGuildRepository {
+    async fn create_guild(
+        &self,
+        name: &str,
+        leader: Uuid,
+        leader_coin: i32,
+    ) -> Result<Guild, GuildRepositoryError> {
+        let taken = GuildEntity::find()
+            .filter(
+                Expr::expr(Func::lower(Expr::col(entity::guilds::Column::Name)))
+                    .eq(Expr::expr(Func::lower(Expr::value(name)))),
+            )
+            .count(&self.connection)
+            .await
+            .map_err(map_to_fail_to_load)?;
+        if taken > 0 {
+            return Err(GuildRepositoryError::NameTaken);
+        }
+
+        let name = name.to_string();
+        let row = self
+            .connection
+            .transaction(|transaction| {
+                Box::pin(async move {
+                    // Guild ids are stored as i16 in the character table, so
+                    // they are not left to the database sequence
+                    let id = GuildEntity::find()
+                        .order_by_desc(entity::guilds::Column::Id)
+                        .one(transaction)
+                        .await?
+                        .map_or(1, |guild| guild.id + 1);
+                    if id > i16::MAX as i32 {
+                        return Err(DbErr::Custom("No guild id available".to_string()));
+                    }
+
+                    let row = entity::guilds::ActiveModel {
+                        id: Set(id),
+                        name: Set(name),
+                        fame: Set(0),
+                        kingdom: Set(0),
+                        wins: Set(0),
+                        sub_guild_1: Set(String::new()),
+                        sub_guild_2: Set(String::new()),
+                        sub_guild_3: Set(String::new()),
+                        notice: Set(String::new()),
+                    }
+                    .insert(transaction)
+                    .await?;
+
+                    let updated = CharacterEntity::update_many()
+                        .col_expr(entity::character::Column::GuildId, Expr::value(id as i16))
+                        .col_expr(
+                            entity::character::Column::GuildLevel,
+                            Expr::value(GuildLevel::Leader.as_raw() as i16),
+                        )
+                        .col_expr(entity::character::Column::Coin, Expr::value(leader_coin))
+                        .filter(entity::character::Column::Id.eq(leader))
+                        .exec(transaction)
+                        .await?;
+                    if updated.rows_affected == 0 {
+                        return Err(DbErr::RecordNotFound(leader.to_string()));
+                    }
+
+                    Ok(row)
+                })
+            })
+            .await
+            .map_err(|err| match err {
+                sea_orm::TransactionError::Connection(db_err) => map_to_generic(db_err),
+                sea_orm::TransactionError::Transaction(DbErr::RecordNotFound(_)) => {
+                    GuildRepositoryError::EntityNotFound
+                }
+                sea_orm::TransactionError::Transaction(db_err) => map_to_generic(db_err),
+            })?;
+
+        Ok(to_guild(row))
+    }
+
+    async fn fetch_guild(&self, id: i16) -> Result<Option<Guild>, GuildRepositoryError> {
+        Ok(GuildEntity::find_by_id(id as i32)
+            .one(&self.connection)
+            .await
+            .map_err(map_to_fail_to_load)?
+            .map(to_guild))
+    }
+
+    async fn find_member_by_name(
+        &self,
+        guild: i16,
+        name: &str,
+    ) -> Result<Option<Uuid>, GuildRepositoryError> {
+        CharacterEntity::find()
+            .select_only()
+            .column(entity::character::Column::Id)
+            .filter(entity::character::Column::GuildId.eq(guild))
+            .filter(
+                Expr::expr(Func::lower(Expr::col(entity::character::Column::Name)))
+                    .eq(Expr::expr(Func::lower(Expr::value(name)))),
+            )
+            .into_tuple()
+            .one(&self.connection)
+            .await
+            .map_err(map_to_fail_to_load)
+    }
+
+    async fn set_membership(
+        &self,
+        character_id: Uuid,
+        membership: Option<(i16, GuildLevel)>,
+    ) -> Result<(), GuildRepositoryError> {
+        let result = CharacterEntity::update_many()
+            .col_expr(
+                entity::character::Column::GuildId,
+                Expr::value(membership.map(|(guild, _)| guild)),
+            )
+            .col_expr(
+                entity::character::Column::GuildLevel,
+                Expr::value(membership.map(|(_, level)| level.as_raw() as i16)),
+            )
+            .filter(entity::character::Column::Id.eq(character_id))
+            .exec(&self.connection)
+            .await
+            .map_err(map_to_generic)?;
+        if result.rows_affected == 0 {
+            return Err(GuildRepositoryError::EntityNotFound);
+        }
+
+        Ok(())
+    }
+
+    async fn set_notice(&self, id: i16, notice: &str) -> Result<(), GuildRepositoryError> {
+        let result = GuildEntity::update_many()
+            .col_expr(entity::guilds::Column::Notice, Expr::value(notice))
+            .filter(entity::guilds::Column::Id.eq(id as i32))
+            .exec(&self.connection)
+            .await
+            .map_err(map_to_generic)?;
+        if result.rows_affected == 0 {
+            return Err(GuildRepositoryError::EntityNotFound);
+        }
+
+        Ok(())
+    }
+
+    async fn disband_guild(&self, id: i16) -> Result<(), GuildRepositoryError> {
+        self.connection
+            .transaction(|transaction| {
+                Box::pin(async move {
+                    CharacterEntity::update_many()
+                        .col_expr(entity::character::Column::GuildId, Expr::value(None::<i16>))
+                        .col_expr(
+                            entity::character::Column::GuildLevel,
+                            Expr::value(None::<i16>),
+                        )
+                        .filter(entity::character::Column::GuildId.eq(id))
+                        .exec(transaction)
+                        .await?;
+
+                    let deleted = GuildEntity::delete_by_id(id as i32)
+                        .exec(transaction)
+                        .await?;
+                    if deleted.rows_affected == 0 {
+                        return Err(DbErr::RecordNotFound(id.to_string()));
+                    }
+
+                    Ok(())
+                })
+            })
+            .await
+            .map_err(|err| match err {
+                sea_orm::TransactionError::Connection(db_err) => map_to_generic(db_err),
+                sea_orm::TransactionError::Transaction(DbErr::RecordNotFound(_)) => {
+                    GuildRepositoryError::EntityNotFound
+                }
+                sea_orm::TransactionError::Transaction(db_err) => map_to_generic(db_err),
+            })
+    }
+}
+
+fn to_guild(row: GuildRow) -> Guild {
+    Guild {
+        id: row.id as i16,
+        name: row.name,
+        fame: row.fame,
+        kingdom: row.kingdom,
+        wins: row.wins,
+        sub_guilds: [row.sub_guild_1, row.sub_guild_2, row.sub_guild_3],
+        notice: row.notice,
+    }
+}
+
+fn map_to_generic(err: DbErr) -> GuildRepositoryError {
+    GuildRepositoryError::Generic(err.to_string())
+}
+
+fn map_to_fail_to_load(err: DbErr) -> GuildRepositoryError {
+    GuildRepositoryError::FailToLoad(err.to_string())
+}
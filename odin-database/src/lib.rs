@@ -1,9 +1,12 @@
 pub mod account_repository;
+pub mod guild_repository;
+pub mod password;
 
 pub use entity;
 pub use sea_orm;
 
 use account_repository::DatabaseAccountRepository;
+use guild_repository::DatabaseGuildRepository;
 use migration::MigratorTrait;
 use sea_orm::{Database, DatabaseConnection, DbErr};
 use thiserror::Error;
@@ -27,6 +30,10 @@ impl DatabaseService {
         DatabaseAccountRepository::new(self.connection.clone())
     }
 
+    pub fn guild_repository(&self) -> DatabaseGuildRepository {
+        DatabaseGuildRepository::new(self.connection.clone())
+    }
+
     pub fn get_connection(&self) -> DatabaseConnection {
         self.connection.clone()
     }
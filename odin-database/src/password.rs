@@ -0,0 +1,89 @@
+use odin_repositories::account_repository::AccountRepositoryError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordMatch {
+    Hashed,
+    Legacy,
+    Mismatch,
+}
+
+pub fn hash_password(password: &str) -> Result<String, AccountRepositoryError> {
+    bcrypt::hash(password, bcrypt::DEFAULT_COST)
+        .map_err(|err| AccountRepositoryError::Generic(err.to_string()))
+}
+
+// Rows created before hashing was introduced still hold the plaintext password
+pub fn verify_password(password: &str, stored: &str) -> PasswordMatch {
+    if is_hashed(stored) {
+        match bcrypt::verify(password, stored) {
+            Ok(true) => PasswordMatch::Hashed,
+            _ => PasswordMatch::Mismatch,
+        }
+    } else if stored == password {
+        PasswordMatch::Legacy
+    } else {
+        PasswordMatch::Mismatch
+    }
+}
+
+/// [`hash_password`] on the blocking pool, as bcrypt takes long enough to
+/// stall every other task of the runtime.
+pub async fn hash_password_blocking(password: String) -> Result<String, AccountRepositoryError> {
+    tokio::task::spawn_blocking(move || hash_password(&password))
+        .await
+        .map_err(|err| AccountRepositoryError::Generic(err.to_string()))?
+}
+
+/// [`verify_password`] on the blocking pool, see [`hash_password_blocking`].
+pub async fn verify_password_blocking(
+    password: String,
+    stored: String,
+) -> Result<PasswordMatch, AccountRepositoryError> {
+    tokio::task::spawn_blocking(move || verify_password(&password, &stored))
+        .await
+        .map_err(|err| AccountRepositoryError::Generic(err.to_string()))
+}
+
+fn is_hashed(stored: &str) -> bool {
+    stored.len() == 60 && stored.starts_with("$2")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verifies_hashed_passwords() {
+        let hash = bcrypt::hash("admin", 4).unwrap();
+        assert_eq!(verify_password("admin", &hash), PasswordMatch::Hashed);
+        assert_eq!(verify_password("admin2", &hash), PasswordMatch::Mismatch);
+    }
+
+    #[test]
+    fn verifies_legacy_plaintext_passwords() {
+        assert_eq!(verify_password("admin", "admin"), PasswordMatch::Legacy);
+        assert_eq!(verify_password("admin2", "admin"), PasswordMatch::Mismatch);
+    }
+
+    #[tokio::test]
+    async fn blocking_variants_match_the_sync_ones() {
+        let hash = hash_password_blocking("admin".to_string()).await.unwrap();
+        assert_eq!(
+            verify_password_blocking("admin".to_string(), hash).await,
+            Ok(PasswordMatch::Hashed)
+        );
+        assert_eq!(
+            verify_password_blocking("admin2".to_string(), "admin".to_string()).await,
+            Ok(PasswordMatch::Mismatch)
+        );
+    }
+
+    #[test]
+    fn hashes_are_salted() {
+        let first = hash_password("admin").unwrap();
+        let second = hash_password("admin").unwrap();
+        assert_ne!(first, second);
+        assert_eq!(first.len(), 60);
+        assert_eq!(verify_password("admin", &first), PasswordMatch::Hashed);
+    }
+}
@@ -0,0 +1,234 @@
+use crate::MAX_AFFECT;
+
+/// Affect ids understood by the stat pipeline. Unknown ids are still kept,
+/// ticked and shown to the client, they just don't change any stat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AffectKind {
+    Slow = 1,
+    Haste = 2,
+    Poison = 3,
+    Damage = 4,
+    Defense = 5,
+    MaxHp = 6,
+    MaxMp = 7,
+    RegenHp = 8,
+    RegenMp = 9,
+    Resist = 10,
+    ExpBonus = 11,
+}
+impl TryFrom<u8> for AffectKind {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            1 => AffectKind::Slow,
+            2 => AffectKind::Haste,
+            3 => AffectKind::Poison,
+            4 => AffectKind::Damage,
+            5 => AffectKind::Defense,
+            6 => AffectKind::MaxHp,
+            7 => AffectKind::MaxMp,
+            8 => AffectKind::RegenHp,
+            9 => AffectKind::RegenMp,
+            10 => AffectKind::Resist,
+            11 => AffectKind::ExpBonus,
+            _ => return Err(value),
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AffectSource {
+    #[default]
+    System,
+    Item(u16),
+    Skill(u16),
+}
+impl AffectSource {
+    pub fn as_raw(self) -> (i16, i16) {
+        match self {
+            AffectSource::System => (0, 0),
+            AffectSource::Item(id) => (1, id as i16),
+            AffectSource::Skill(id) => (2, id as i16),
+        }
+    }
+
+    pub fn from_raw(kind: i16, id: i16) -> Self {
+        match kind {
+            1 => AffectSource::Item(id as u16),
+            2 => AffectSource::Skill(id as u16),
+            _ => AffectSource::System,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Affect {
+    pub id: u8,
+    pub value: i32,
+    pub remaining_ticks: u32,
+    pub source: AffectSource,
+}
+impl Affect {
+    pub fn kind(&self) -> Option<AffectKind> {
+        AffectKind::try_from(self.id).ok()
+    }
+}
+
+/// The affects currently on a mob, at most one per id and `MAX_AFFECT` in
+/// total, matching the affect bytes of `CreateMob`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AffectList {
+    affects: Vec<Affect>,
+}
+
+impl AffectList {
+    /// Adds an affect, replacing any affect with the same id. Returns false
+    /// when the list is full.
+    pub fn insert(&mut self, affect: Affect) -> bool {
+        if let Some(existing) = self.affects.iter_mut().find(|a| a.id == affect.id) {
+            *existing = affect;
+            return true;
+        }
+        if self.affects.len() >= MAX_AFFECT {
+            return false;
+        }
+        self.affects.push(affect);
+        true
+    }
+
+    pub fn remove(&mut self, id: u8) -> Option<Affect> {
+        let index = self.affects.iter().position(|a| a.id == id)?;
+        Some(self.affects.remove(index))
+    }
+
+    pub fn get(&self, id: u8) -> Option<&Affect> {
+        self.affects.iter().find(|a| a.id == id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Affect> {
+        self.affects.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.affects.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.affects.len()
+    }
+
+    /// Consumes one tick of every affect and drops the ones that ran out,
+    /// returning them.
+    pub fn tick(&mut self) -> Vec<Affect> {
+        for affect in &mut self.affects {
+            affect.remaining_ticks = affect.remaining_ticks.saturating_sub(1);
+        }
+        let (expired, active) = self
+            .affects
+            .drain(..)
+            .partition(|affect| affect.remaining_ticks == 0);
+        self.affects = active;
+        expired
+    }
+
+    pub fn sum(&self, kind: AffectKind) -> i32 {
+        self.affects
+            .iter()
+            .filter(|affect| affect.kind() == Some(kind))
+            .map(|affect| affect.value)
+            .sum()
+    }
+
+    pub fn to_bytes(&self) -> [u8; MAX_AFFECT] {
+        let mut bytes = [0u8; MAX_AFFECT];
+        for (byte, affect) in bytes.iter_mut().zip(&self.affects) {
+            *byte = affect.id;
+        }
+        bytes
+    }
+}
+
+impl From<Vec<Affect>> for AffectList {
+    fn from(affects: Vec<Affect>) -> Self {
+        let mut list = AffectList::default();
+        for affect in affects {
+            list.insert(affect);
+        }
+        list
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn affect(id: u8, value: i32, remaining_ticks: u32) -> Affect {
+        Affect {
+            id,
+            value,
+            remaining_ticks,
+            source: AffectSource::System,
+        }
+    }
+
+    #[test]
+    fn insert_replaces_same_id() {
+        let mut list = AffectList::default();
+        assert!(list.insert(affect(4, 10, 5)));
+        assert!(list.insert(affect(4, 20, 8)));
+
+        assert_eq!(list.len(), 1);
+        assert_eq!(list.get(4), Some(&affect(4, 20, 8)));
+    }
+
+    #[test]
+    fn insert_rejects_when_full() {
+        let mut list = AffectList::default();
+        for id in 0..MAX_AFFECT as u8 {
+            assert!(list.insert(affect(id + 20, 1, 1)));
+        }
+
+        assert!(!list.insert(affect(4, 1, 1)));
+        assert_eq!(list.len(), MAX_AFFECT);
+    }
+
+    #[test]
+    fn tick_returns_expired_affects() {
+        let mut list = AffectList::from(vec![affect(4, 10, 1), affect(5, 3, 2)]);
+
+        assert_eq!(list.tick(), vec![affect(4, 10, 0)]);
+        assert_eq!(list.get(5).map(|a| a.remaining_ticks), Some(1));
+        assert_eq!(list.tick(), vec![affect(5, 3, 0)]);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn sum_only_counts_matching_kind() {
+        let list = AffectList::from(vec![affect(4, 10, 1), affect(5, 3, 1), affect(99, 7, 1)]);
+
+        assert_eq!(list.sum(AffectKind::Damage), 10);
+        assert_eq!(list.sum(AffectKind::Defense), 3);
+        assert_eq!(list.sum(AffectKind::Haste), 0);
+    }
+
+    #[test]
+    fn to_bytes_lists_affect_ids() {
+        let list = AffectList::from(vec![affect(4, 10, 1), affect(2, 3, 1)]);
+
+        let bytes = list.to_bytes();
+        assert_eq!(&bytes[..3], &[4, 2, 0]);
+    }
+
+    #[test]
+    fn source_round_trips_through_raw() {
+        for source in [
+            AffectSource::System,
+            AffectSource::Item(3314),
+            AffectSource::Skill(42),
+        ] {
+            let (kind, id) = source.as_raw();
+            assert_eq!(AffectSource::from_raw(kind, id), source);
+        }
+    }
+}
@@ -1,4 +1,10 @@
-use crate::{EquipmentSlots, InventorySlots, position::Position, status::Score};
+use crate::{
+    EquipmentSlots, InventorySlots,
+    affect::AffectList,
+    position::Position,
+    skill::{LearnedSkills, SkillBar},
+    status::Score,
+};
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -25,6 +31,10 @@ pub struct Character {
     pub last_pos: Position,
     pub inventory: InventorySlots,
     pub equipments: EquipmentSlots,
+    pub affects: AffectList,
+    pub learned_skills: LearnedSkills,
+    pub skill_bar: SkillBar,
+    pub skill_bonus: i16,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -34,6 +34,18 @@ impl<K: SlotIndex, const N: usize> ItemSlots<K, N> {
         }
     }
 
+    pub fn take(&mut self, key: K) -> Option<Item> {
+        let idx = key.to_index();
+        self.items.get_mut(idx)?.take()
+    }
+
+    pub fn first_empty(&self) -> Option<K> {
+        self.items
+            .iter()
+            .position(Option::is_none)
+            .and_then(K::from_index)
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = (K, &Item)> {
         self.items.iter().enumerate().filter_map(|(i, item)| {
             let key = K::from_index(i)?;
@@ -126,6 +138,16 @@ mod tests {
         assert!(slots.get(3).is_none());
     }
 
+    #[test]
+    fn first_empty_finds_the_first_free_slot() {
+        let mut slots = TestSlots::from([(0, Item::from(100u16)), (2, Item::from(200u16))]);
+        assert_eq!(slots.first_empty(), Some(1));
+
+        slots.set(1, Item::from(300u16));
+        slots.set(3, Item::from(400u16));
+        assert_eq!(slots.first_empty(), None);
+    }
+
     #[test]
     fn iter_skips_empty_slots() {
         let item_a = Item::from(100u16);
@@ -146,6 +168,16 @@ mod tests {
         assert!(slots.get(0).is_none());
     }
 
+    #[test]
+    fn take_empties_the_slot() {
+        let mut slots = TestSlots::from([(1, Item::from(10u16))]);
+
+        assert_eq!(slots.take(1).unwrap().id, 10);
+        assert!(slots.get(1).is_none());
+        assert!(slots.take(1).is_none());
+        assert!(slots.take(10).is_none());
+    }
+
     #[test]
     fn out_of_bounds_set_is_noop() {
         let mut slots = TestSlots::default();
@@ -1,5 +1,6 @@
 pub mod account;
 pub mod account_charlist;
+pub mod affect;
 pub mod character;
 pub mod direction;
 pub mod effect;
@@ -10,6 +11,7 @@ pub mod item_slots;
 pub mod nickname;
 pub mod npc_mob;
 pub mod position;
+pub mod skill;
 pub mod status;
 pub mod storage;
 
@@ -20,6 +22,7 @@ pub const MAX_ITEM_EFFECT: usize = 3;
 pub const MAX_INVENTORY: usize = 64;
 pub const MAX_INVENTORY_VISIBLE: usize = MAX_INVENTORY - 4;
 pub const MAX_AFFECT: usize = 32;
+pub const MAX_COIN: i32 = 2_000_000_000;
 
 pub use uuid;
 
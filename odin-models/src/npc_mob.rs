@@ -14,9 +14,18 @@ pub struct NpcMob {
     pub quest_info: i16,
     pub coin: i32,
     pub experience: i64,
+    pub aggro_radius: u16,
     pub score: Score,
     pub equipments: EquipmentSlots,
     pub inventory: InventorySlots,
+    pub loot: Vec<LootDrop>,
+}
+
+/// Chance, from 0.0 to 1.0, that the inventory item in `slot` drops on death.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LootDrop {
+    pub slot: usize,
+    pub chance: f32,
 }
 
 #[cfg(test)]
@@ -30,5 +39,6 @@ mod tests {
         assert_eq!(mob.clan, 0);
         assert_eq!(mob.coin, 0);
         assert_eq!(mob.guild, None);
+        assert!(mob.loot.is_empty());
     }
 }
@@ -0,0 +1,92 @@
+pub const MAX_SKILLS: usize = 64;
+pub const MAX_SKILL_BAR: usize = 20;
+/// Skill bar slot value the client reads as empty.
+pub const EMPTY_SKILL_SLOT: u8 = 0xFF;
+
+/// Bitset of learned skills, split by the client (and the database) in two
+/// 32 bit words.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LearnedSkills(u64);
+
+impl LearnedSkills {
+    pub fn from_raw(raw: [u32; 2]) -> Self {
+        Self(raw[0] as u64 | (raw[1] as u64) << 32)
+    }
+
+    pub fn to_raw(self) -> [u32; 2] {
+        [self.0 as u32, (self.0 >> 32) as u32]
+    }
+
+    pub fn knows(self, skill: u8) -> bool {
+        (skill as usize) < MAX_SKILLS && self.0 & (1 << skill) != 0
+    }
+
+    /// Marks a skill as learned. Returns false when it was already learned
+    /// or is out of range.
+    pub fn learn(&mut self, skill: u8) -> bool {
+        if skill as usize >= MAX_SKILLS || self.knows(skill) {
+            return false;
+        }
+        self.0 |= 1 << skill;
+        true
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SkillBar([Option<u8>; MAX_SKILL_BAR]);
+
+impl SkillBar {
+    pub fn from_raw(raw: &[u8]) -> Self {
+        let mut bar = SkillBar::default();
+        for (slot, skill) in bar.0.iter_mut().zip(raw) {
+            *slot = (*skill != EMPTY_SKILL_SLOT).then_some(*skill);
+        }
+        bar
+    }
+
+    pub fn to_raw(&self) -> [u8; MAX_SKILL_BAR] {
+        self.0.map(|skill| skill.unwrap_or(EMPTY_SKILL_SLOT))
+    }
+
+    pub fn skills(&self) -> impl Iterator<Item = u8> + '_ {
+        self.0.iter().flatten().copied()
+    }
+}
+
+impl Default for SkillBar {
+    fn default() -> Self {
+        Self([None; MAX_SKILL_BAR])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn learned_skills_round_trip_through_raw_words() {
+        let mut learned = LearnedSkills::default();
+        assert!(learned.learn(3));
+        assert!(learned.learn(40));
+        assert!(!learned.learn(3));
+        assert!(!learned.learn(MAX_SKILLS as u8));
+
+        let raw = learned.to_raw();
+        assert_eq!(raw, [1 << 3, 1 << 8]);
+        assert_eq!(LearnedSkills::from_raw(raw), learned);
+        assert!(learned.knows(40));
+        assert!(!learned.knows(41));
+    }
+
+    #[test]
+    fn skill_bar_maps_empty_slots() {
+        let mut raw = [EMPTY_SKILL_SLOT; MAX_SKILL_BAR];
+        raw[0] = 3;
+        raw[5] = 0;
+
+        let bar = SkillBar::from_raw(&raw);
+        assert_eq!(bar.skills().collect::<Vec<_>>(), vec![3, 0]);
+        assert_eq!(bar.to_raw(), raw);
+        assert_eq!(SkillBar::from_raw(&[]), SkillBar::default());
+    }
+}
@@ -6,7 +6,7 @@ use std::rc::Rc;
 use std::time::Instant;
 use thiserror::Error;
 
-const KEYTABLE_LENGTH: usize = 512;
+pub const KEYTABLE_LENGTH: usize = 512;
 const HALF_KEYTABLE_LENGTH: usize = 255;
 
 #[derive(Debug, Clone)]
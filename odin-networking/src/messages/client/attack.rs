@@ -0,0 +1,45 @@
+use crate::messages::common::PositionRaw;
+use deku::prelude::*;
+
+pub const MAX_ATTACK_TARGETS: usize = 13;
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct DamageRaw {
+    pub target_id: i32,
+    pub damage: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct AttackRaw<const N: usize> {
+    pub attacker_id: i16,
+    pub attack_count: i16,
+    pub position: PositionRaw,
+    pub target_position: PositionRaw,
+    pub skill_index: i16,
+    pub current_mp: i16,
+    pub motion: i8,
+    pub skill_parm: i8,
+    pub flag_local: i8,
+    pub double_critical: i8,
+    pub hold: i32,
+    pub current_exp: i32,
+    pub req_mp: i16,
+    pub rsv: i16,
+    pub damages: [DamageRaw; N],
+}
+
+pub type AttackMultiRaw = AttackRaw<MAX_ATTACK_TARGETS>;
+pub type AttackOneRaw = AttackRaw<1>;
+pub type AttackTwoRaw = AttackRaw<2>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_of() {
+        assert_eq!(std::mem::size_of::<AttackOneRaw>(), 40);
+        assert_eq!(std::mem::size_of::<AttackTwoRaw>(), 48);
+        assert_eq!(std::mem::size_of::<AttackMultiRaw>(), 136);
+    }
+}
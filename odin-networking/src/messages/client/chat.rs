@@ -0,0 +1,39 @@
+use crate::messages::string::FixedSizeString;
+use deku::prelude::*;
+
+pub const CHAT_LENGTH: usize = 96;
+pub const WHISPER_NAME_LENGTH: usize = 16;
+pub const WHISPER_LENGTH: usize = 128;
+
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct ChatRaw {
+    pub message: FixedSizeString<CHAT_LENGTH>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct WhisperRaw {
+    pub name: FixedSizeString<WHISPER_NAME_LENGTH>,
+    pub message: FixedSizeString<WHISPER_LENGTH>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_of() {
+        let chat = ChatRaw {
+            message: "hello".to_string().try_into().unwrap(),
+        };
+        assert_eq!(chat.to_bytes().unwrap().len(), CHAT_LENGTH);
+
+        let whisper = WhisperRaw {
+            name: "Someone".to_string().try_into().unwrap(),
+            message: "hello".to_string().try_into().unwrap(),
+        };
+        assert_eq!(
+            whisper.to_bytes().unwrap().len(),
+            WHISPER_NAME_LENGTH + WHISPER_LENGTH
+        );
+    }
+}
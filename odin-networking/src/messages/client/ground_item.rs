@@ -0,0 +1,19 @@
+use crate::messages::common::PositionRaw;
+use deku::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct DropItemRaw {
+    pub src_type: i32,
+    pub src_slot: i32,
+    pub rotation: i32,
+    pub position: PositionRaw,
+    pub item_id: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct PickupItemRaw {
+    pub dest_type: i32,
+    pub dest_slot: i32,
+    pub item_id: u16,
+    pub position: PositionRaw,
+}
@@ -1,7 +1,20 @@
 pub mod action;
 pub mod apply_bonus;
+pub mod attack;
+pub mod chat;
 pub mod create_character;
 pub mod delete_character;
 pub mod enter_world;
+pub mod ground_item;
 pub mod login;
+pub mod move_item;
 pub mod numeric_token;
+pub mod open_merchant;
+pub mod party;
+pub mod restart;
+pub mod set_short_skill;
+pub mod shop;
+pub mod stall;
+pub mod storage_coin;
+pub mod trade;
+pub mod use_item;
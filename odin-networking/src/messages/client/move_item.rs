@@ -0,0 +1,10 @@
+use deku::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct MoveItemRaw {
+    pub dest_type: i8,
+    pub dest_slot: i8,
+    pub src_type: i8,
+    pub src_slot: i8,
+    pub warp_id: i32,
+}
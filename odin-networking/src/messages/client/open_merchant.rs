@@ -0,0 +1,7 @@
+use deku::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct OpenMerchantRaw {
+    pub target_id: u16,
+    pub rsv: u16,
+}
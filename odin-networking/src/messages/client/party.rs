@@ -0,0 +1,31 @@
+use crate::messages::string::FixedSizeString;
+use deku::prelude::*;
+
+pub const PARTY_NAME_LENGTH: usize = 16;
+
+/// Sent by the client to invite `target_id`, and forwarded to the invited
+/// player with the leader's information filled in.
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct PartyInviteRaw {
+    pub leader_id: u16,
+    pub level: u16,
+    pub max_hp: u32,
+    pub hp: u32,
+    pub name: FixedSizeString<PARTY_NAME_LENGTH>,
+    pub target_id: u16,
+    pub rsv: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct AcceptPartyRaw {
+    pub leader_id: u16,
+    pub name: FixedSizeString<PARTY_NAME_LENGTH>,
+}
+
+/// Leaves the party when `target_id` is the sender itself, kicks
+/// `target_id` otherwise.
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct RemovePartyRaw {
+    pub target_id: u16,
+    pub rsv: u16,
+}
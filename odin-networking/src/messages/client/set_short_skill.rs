@@ -0,0 +1,7 @@
+use deku::prelude::*;
+use odin_models::skill::MAX_SKILL_BAR;
+
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct SetShortSkillRaw {
+    pub skills: [u8; MAX_SKILL_BAR],
+}
@@ -0,0 +1,17 @@
+use deku::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct BuyItemRaw {
+    pub target_id: u16,
+    pub shop_slot: i16,
+    pub inventory_slot: i16,
+    pub rsv: u16,
+    pub coin: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct SellItemRaw {
+    pub target_id: u16,
+    pub slot_type: i16,
+    pub slot: i16,
+}
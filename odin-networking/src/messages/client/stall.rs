@@ -0,0 +1,35 @@
+use crate::messages::{common::ItemRaw, string::FixedSizeString};
+use deku::prelude::*;
+
+pub const MAX_STALL_ITEMS: usize = 12;
+pub const STALL_TITLE_LENGTH: usize = 24;
+
+/// Opens the sender's personal shop with the priced items taken from
+/// `inventory_slots`, and sent back to players browsing the shop of
+/// `owner_id`. Empty shop slots have `inventory_slots` set to -1.
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct StallRaw {
+    pub title: FixedSizeString<STALL_TITLE_LENGTH>,
+    pub items: [ItemRaw; MAX_STALL_ITEMS],
+    pub inventory_slots: [i8; MAX_STALL_ITEMS],
+    pub prices: [i32; MAX_STALL_ITEMS],
+    pub tax: i16,
+    pub owner_id: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct BrowseStallRaw {
+    pub target_id: u16,
+    pub rsv: u16,
+}
+
+/// `price` and `item` are what the buyer saw, so the sale is refused if the
+/// shop changed in the meantime.
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct BuyStallItemRaw {
+    pub target_id: i32,
+    pub stall_slot: i32,
+    pub price: i32,
+    pub tax: i32,
+    pub item: ItemRaw,
+}
@@ -0,0 +1,6 @@
+use deku::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct StorageCoinRaw {
+    pub coin: i32,
+}
@@ -0,0 +1,19 @@
+use crate::messages::common::ItemRaw;
+use deku::prelude::*;
+
+pub const MAX_TRADE_ITEMS: usize = 15;
+
+/// Sent by both sides for every step of a trade: requesting or accepting it
+/// with `opponent_id`, changing the offer, locking it and confirming it,
+/// as told by `check`. Empty offer slots have `inventory_slots` set to -1.
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct TradeRaw {
+    pub items: [ItemRaw; MAX_TRADE_ITEMS],
+    pub inventory_slots: [i8; MAX_TRADE_ITEMS],
+    pub coin: i32,
+    pub check: u8,
+    pub opponent_id: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct QuitTradeRaw;
@@ -0,0 +1,12 @@
+use crate::messages::common::PositionRaw;
+use deku::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct UseItemRaw {
+    pub src_type: i32,
+    pub src_slot: i32,
+    pub dest_type: i32,
+    pub dest_slot: i32,
+    pub position: PositionRaw,
+    pub warp_id: u16,
+}
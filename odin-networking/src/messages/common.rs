@@ -19,6 +19,14 @@ impl From<Item> for ItemRaw {
         }
     }
 }
+impl From<ItemRaw> for Item {
+    fn from(value: ItemRaw) -> Self {
+        Item {
+            id: value.id,
+            effects: array::from_fn(|i| (value.effects[i].index, value.effects[i].value).into()),
+        }
+    }
+}
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, DekuRead, DekuWrite)]
 pub struct ItemBonusEffectRaw {
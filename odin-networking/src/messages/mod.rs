@@ -24,6 +24,30 @@ pub enum ClientMessage {
     Action,
     Action2,
     ActionStop,
+    Chat,
+    Whisper,
+    Attack,
+    AttackOne,
+    AttackTwo,
+    Restart,
+    MoveItem,
+    OpenMerchant,
+    DepositCoin,
+    WithdrawCoin,
+    DropItem,
+    PickupItem,
+    UseItem,
+    SetShortSkill,
+    BuyItem,
+    SellItem,
+    PartyInvite,
+    AcceptParty,
+    RemoveParty,
+    Trade,
+    QuitTrade,
+    OpenStall,
+    BrowseStall,
+    BuyStallItem,
 }
 impl TryFrom<u16> for ClientMessage {
     type Error = InvalidMessageType;
@@ -39,6 +63,30 @@ impl TryFrom<u16> for ClientMessage {
             0x36C => ClientMessage::Action,
             0x368 => ClientMessage::Action2,
             0x366 => ClientMessage::ActionStop,
+            0x333 => ClientMessage::Chat,
+            0x334 => ClientMessage::Whisper,
+            0x367 => ClientMessage::Attack,
+            0x39D => ClientMessage::AttackOne,
+            0x39E => ClientMessage::AttackTwo,
+            0x289 => ClientMessage::Restart,
+            0x376 => ClientMessage::MoveItem,
+            0x27B => ClientMessage::OpenMerchant,
+            0x388 => ClientMessage::DepositCoin,
+            0x387 => ClientMessage::WithdrawCoin,
+            0x272 => ClientMessage::DropItem,
+            0x270 => ClientMessage::PickupItem,
+            0x373 => ClientMessage::UseItem,
+            0x378 => ClientMessage::SetShortSkill,
+            0x379 => ClientMessage::BuyItem,
+            0x37A => ClientMessage::SellItem,
+            0x37F => ClientMessage::PartyInvite,
+            0x3AB => ClientMessage::AcceptParty,
+            0x37E => ClientMessage::RemoveParty,
+            0x383 => ClientMessage::Trade,
+            0x384 => ClientMessage::QuitTrade,
+            0x397 => ClientMessage::OpenStall,
+            0x39A => ClientMessage::BrowseStall,
+            0x398 => ClientMessage::BuyStallItem,
             _ => return Err(InvalidMessageType(value)),
         })
     }
@@ -61,6 +109,23 @@ pub enum ServerMessage {
     ActionStop,
     RemoveMob,
     UpdateScore,
+    Chat,
+    Whisper,
+    CreateItem,
+    Attack,
+    ShopList,
+    UpdateStorageCoin,
+    CreateGroundItem,
+    RemoveGroundItem,
+    Motion,
+    PartyInvite,
+    AddPartyMember,
+    RemovePartyMember,
+    PartyMemberPosition,
+    Trade,
+    QuitTrade,
+    StallList,
+    CreateMobTrade,
 }
 impl TryFrom<ServerMessage> for u16 {
     type Error = InvalidMessageType;
@@ -82,6 +147,23 @@ impl TryFrom<ServerMessage> for u16 {
             ServerMessage::ActionStop => 0x366,
             ServerMessage::RemoveMob => 0x165,
             ServerMessage::UpdateScore => 0x336,
+            ServerMessage::Chat => 0x333,
+            ServerMessage::Whisper => 0x334,
+            ServerMessage::CreateItem => 0x182,
+            ServerMessage::Attack => 0x367,
+            ServerMessage::ShopList => 0x17C,
+            ServerMessage::UpdateStorageCoin => 0x339,
+            ServerMessage::CreateGroundItem => 0x26E,
+            ServerMessage::RemoveGroundItem => 0x16F,
+            ServerMessage::Motion => 0x36A,
+            ServerMessage::PartyInvite => 0x37F,
+            ServerMessage::AddPartyMember => 0x37D,
+            ServerMessage::RemovePartyMember => 0x37E,
+            ServerMessage::PartyMemberPosition => 0x3B0,
+            ServerMessage::Trade => 0x383,
+            ServerMessage::QuitTrade => 0x384,
+            ServerMessage::StallList => 0x397,
+            ServerMessage::CreateMobTrade => 0x363,
         })
     }
 }
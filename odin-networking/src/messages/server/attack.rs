@@ -0,0 +1,79 @@
+use crate::{
+    WritableResource, WritableResourceError,
+    messages::{
+        ServerMessage,
+        client::attack::{AttackMultiRaw, DamageRaw, MAX_ATTACK_TARGETS},
+        common::PositionRaw,
+    },
+};
+use odin_models::position::Position;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Damage {
+    pub target_id: u16,
+    pub damage: i32,
+}
+
+pub struct Attack {
+    pub attacker_id: u16,
+    pub position: Position,
+    pub target_position: Position,
+    pub skill_index: i16,
+    pub current_mp: i16,
+    pub motion: i8,
+    pub skill_parm: i8,
+    pub double_critical: bool,
+    pub current_exp: i32,
+    pub damages: Vec<Damage>,
+}
+
+impl WritableResource for Attack {
+    const IDENTIFIER: ServerMessage = ServerMessage::Attack;
+    type Output = AttackMultiRaw;
+
+    fn write(self) -> Result<Self::Output, WritableResourceError> {
+        if self.damages.len() > MAX_ATTACK_TARGETS {
+            return Err(WritableResourceError::Generic(format!(
+                "Attack has {} targets, the maximum is {}",
+                self.damages.len(),
+                MAX_ATTACK_TARGETS
+            )));
+        }
+
+        let mut damages = [DamageRaw::default(); MAX_ATTACK_TARGETS];
+        for (raw, damage) in damages.iter_mut().zip(&self.damages) {
+            *raw = DamageRaw {
+                target_id: damage.target_id as i32,
+                damage: damage.damage,
+            };
+        }
+
+        Ok(AttackMultiRaw {
+            attacker_id: self.attacker_id as i16,
+            attack_count: 0,
+            position: PositionRaw {
+                x: self.position.x,
+                y: self.position.y,
+            },
+            target_position: PositionRaw {
+                x: self.target_position.x,
+                y: self.target_position.y,
+            },
+            skill_index: self.skill_index,
+            current_mp: self.current_mp,
+            motion: self.motion,
+            skill_parm: self.skill_parm,
+            flag_local: 0,
+            double_critical: self.double_critical as i8,
+            hold: 0,
+            current_exp: self.current_exp,
+            req_mp: 0,
+            rsv: 0,
+            damages,
+        })
+    }
+
+    fn client_id(&self) -> Option<u16> {
+        Some(self.attacker_id)
+    }
+}
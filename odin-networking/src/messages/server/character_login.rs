@@ -11,6 +11,7 @@ use odin_models::{
     EquipmentSlots, InventorySlots, MAX_EQUIPS, MAX_INVENTORY,
     character::{Class, Evolution, GuildLevel},
     position::Position,
+    skill::MAX_SKILL_BAR,
     status::Score,
 };
 use std::array;
@@ -42,6 +43,8 @@ pub struct CharacterLogin {
     pub score_bonus: i16,
     pub special_bonus: i16,
     pub skill_bonus: i16,
+    pub learned_skill: [u32; 2],
+    pub skill_bar: [u8; MAX_SKILL_BAR],
 
     pub critical: u8,
     pub save_mana: i32,
@@ -66,6 +69,7 @@ impl WritableResource for CharacterLogin {
         });
 
         let guild_level = self.guild_level.map(|g| g.as_raw() as i8).unwrap_or(0);
+        let (short_skill, extra_short_skill) = self.skill_bar.split_at(4);
 
         let mob = StructMobRaw {
             mob_name: self.name.as_str().try_into().unwrap_or_default(),
@@ -85,13 +89,13 @@ impl WritableResource for CharacterLogin {
             current_score: self.current_score.into(),
             equip,
             carry,
-            learned_skill: [0; 2],
+            learned_skill: self.learned_skill,
             score_bonus: self.score_bonus,
             special_bonus: self.special_bonus,
             skill_bonus: self.skill_bonus,
             critical: self.critical,
             save_mana: self.save_mana.clamp(0, 255) as u8,
-            short_skill: [0xFF; 4],
+            short_skill: short_skill.try_into().expect("skill bar starts with 4 slots"),
             guild_level,
             magic: self.magic.max(0) as u32,
             regen_hp: self.regen_hp.clamp(0, 255) as u8,
@@ -111,7 +115,9 @@ impl WritableResource for CharacterLogin {
             slot: 0,
             client_id: self.client_id,
             weather: 0,
-            short_skill: [0xFF; 16],
+            short_skill: extra_short_skill
+                .try_into()
+                .expect("skill bar ends with 16 slots"),
             ext1: [0; EXT1_SIZE],
             ext2: [0; EXT2_SIZE],
         })
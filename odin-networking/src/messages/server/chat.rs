@@ -0,0 +1,48 @@
+use crate::{
+    WritableResource, WritableResourceError,
+    messages::{
+        ServerMessage,
+        client::chat::{ChatRaw, WhisperRaw},
+    },
+};
+
+pub struct Chat {
+    pub sender_id: u16,
+    pub message: String,
+}
+
+impl WritableResource for Chat {
+    const IDENTIFIER: ServerMessage = ServerMessage::Chat;
+    type Output = ChatRaw;
+
+    fn write(self) -> Result<Self::Output, WritableResourceError> {
+        Ok(ChatRaw {
+            message: self.message.try_into()?,
+        })
+    }
+
+    fn client_id(&self) -> Option<u16> {
+        Some(self.sender_id)
+    }
+}
+
+pub struct Whisper {
+    pub name: String,
+    pub message: String,
+}
+
+impl WritableResource for Whisper {
+    const IDENTIFIER: ServerMessage = ServerMessage::Whisper;
+    type Output = WhisperRaw;
+
+    fn write(self) -> Result<Self::Output, WritableResourceError> {
+        Ok(WhisperRaw {
+            name: self.name.try_into()?,
+            message: self.message.try_into()?,
+        })
+    }
+
+    fn client_id(&self) -> Option<u16> {
+        Some(0)
+    }
+}
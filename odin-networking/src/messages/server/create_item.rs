@@ -0,0 +1,53 @@
+use crate::{
+    WritableResource, WritableResourceError,
+    messages::{ServerMessage, common::ItemRaw},
+};
+use deku::prelude::*;
+use odin_models::item::Item;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotType {
+    Equipment,
+    Inventory,
+    Storage,
+}
+impl From<SlotType> for i16 {
+    fn from(value: SlotType) -> Self {
+        match value {
+            SlotType::Equipment => 0,
+            SlotType::Inventory => 1,
+            SlotType::Storage => 2,
+        }
+    }
+}
+
+pub struct CreateItem {
+    pub mob_id: u16,
+    pub slot_type: SlotType,
+    pub slot: u16,
+    pub item: Option<Item>,
+}
+
+impl WritableResource for CreateItem {
+    const IDENTIFIER: ServerMessage = ServerMessage::CreateItem;
+    type Output = CreateItemRaw;
+
+    fn write(self) -> Result<Self::Output, WritableResourceError> {
+        Ok(CreateItemRaw {
+            slot_type: self.slot_type.into(),
+            slot: self.slot as i16,
+            item: self.item.map(ItemRaw::from).unwrap_or_default(),
+        })
+    }
+
+    fn client_id(&self) -> Option<u16> {
+        Some(self.mob_id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct CreateItemRaw {
+    pub slot_type: i16,
+    pub slot: i16,
+    pub item: ItemRaw,
+}
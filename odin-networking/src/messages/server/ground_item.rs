@@ -0,0 +1,69 @@
+use crate::{
+    WritableResource, WritableResourceError,
+    messages::{
+        ServerMessage,
+        common::{ItemRaw, PositionRaw},
+    },
+};
+use deku::prelude::*;
+use odin_models::{item::Item, position::Position};
+
+pub struct CreateGroundItem {
+    pub item_id: u16,
+    pub position: Position,
+    pub item: Item,
+    pub rotation: i8,
+}
+
+impl WritableResource for CreateGroundItem {
+    const IDENTIFIER: ServerMessage = ServerMessage::CreateGroundItem;
+    type Output = CreateGroundItemRaw;
+
+    fn write(self) -> Result<Self::Output, WritableResourceError> {
+        Ok(CreateGroundItemRaw {
+            position: PositionRaw {
+                x: self.position.x,
+                y: self.position.y,
+            },
+            item_id: self.item_id,
+            item: self.item.into(),
+            rotation: self.rotation,
+            state: 0,
+            height: 0,
+            create: 0,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct CreateGroundItemRaw {
+    pub position: PositionRaw,
+    pub item_id: u16,
+    pub item: ItemRaw,
+    pub rotation: i8,
+    pub state: i8,
+    pub height: i8,
+    pub create: i8,
+}
+
+pub struct RemoveGroundItem {
+    pub item_id: u16,
+}
+
+impl WritableResource for RemoveGroundItem {
+    const IDENTIFIER: ServerMessage = ServerMessage::RemoveGroundItem;
+    type Output = RemoveGroundItemRaw;
+
+    fn write(self) -> Result<Self::Output, WritableResourceError> {
+        Ok(RemoveGroundItemRaw {
+            item_id: self.item_id,
+            rsv: 0,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct RemoveGroundItemRaw {
+    pub item_id: u16,
+    pub rsv: u16,
+}
@@ -1,12 +1,22 @@
 pub mod action;
+pub mod attack;
 pub mod character_login;
 pub mod charlist;
+pub mod chat;
+pub mod create_item;
 pub mod create_mob;
+pub mod ground_item;
 pub mod message_panel;
+pub mod motion;
 pub mod numeric_token;
+pub mod party;
 pub mod remove_mob;
+pub mod shop_list;
+pub mod stall;
+pub mod trade;
 pub mod update_etc;
 pub mod update_score;
+pub mod update_storage_coin;
 
 use super::ServerMessage;
 use crate::WritableResource;
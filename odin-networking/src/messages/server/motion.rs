@@ -0,0 +1,34 @@
+use crate::{WritableResource, WritableResourceError, messages::ServerMessage};
+use deku::prelude::*;
+
+pub const MOTION_LEVEL_UP: i16 = 14;
+
+pub struct Motion {
+    pub mob_id: u16,
+    pub motion: i16,
+    pub parm: i16,
+}
+
+impl WritableResource for Motion {
+    const IDENTIFIER: ServerMessage = ServerMessage::Motion;
+    type Output = MotionRaw;
+
+    fn write(self) -> Result<Self::Output, WritableResourceError> {
+        Ok(MotionRaw {
+            motion: self.motion,
+            parm: self.parm,
+            not_used: 0,
+        })
+    }
+
+    fn client_id(&self) -> Option<u16> {
+        Some(self.mob_id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct MotionRaw {
+    pub motion: i16,
+    pub parm: i16,
+    pub not_used: i32,
+}
@@ -0,0 +1,120 @@
+use crate::{
+    WritableResource, WritableResourceError,
+    messages::{
+        ServerMessage,
+        client::party::{PARTY_NAME_LENGTH, PartyInviteRaw, RemovePartyRaw},
+        common::PositionRaw,
+        string::FixedSizeString,
+    },
+};
+use deku::prelude::*;
+use odin_models::position::Position;
+
+pub struct PartyInvite {
+    pub leader_id: u16,
+    pub level: u16,
+    pub max_hp: u32,
+    pub hp: u32,
+    pub name: String,
+    pub target_id: u16,
+}
+
+impl WritableResource for PartyInvite {
+    const IDENTIFIER: ServerMessage = ServerMessage::PartyInvite;
+    type Output = PartyInviteRaw;
+
+    fn write(self) -> Result<Self::Output, WritableResourceError> {
+        Ok(PartyInviteRaw {
+            leader_id: self.leader_id,
+            level: self.level,
+            max_hp: self.max_hp,
+            hp: self.hp,
+            name: self.name.try_into()?,
+            target_id: self.target_id,
+            rsv: 0,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AddPartyMember {
+    pub leader_id: u16,
+    pub member_id: u16,
+    pub level: u16,
+    pub max_hp: u32,
+    pub hp: u32,
+    pub name: String,
+}
+
+impl WritableResource for AddPartyMember {
+    const IDENTIFIER: ServerMessage = ServerMessage::AddPartyMember;
+    type Output = AddPartyMemberRaw;
+
+    fn write(self) -> Result<Self::Output, WritableResourceError> {
+        Ok(AddPartyMemberRaw {
+            leader_id: self.leader_id,
+            member_id: self.member_id,
+            level: self.level,
+            rsv: 0,
+            max_hp: self.max_hp,
+            hp: self.hp,
+            name: self.name.try_into()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct AddPartyMemberRaw {
+    pub leader_id: u16,
+    pub member_id: u16,
+    pub level: u16,
+    pub rsv: u16,
+    pub max_hp: u32,
+    pub hp: u32,
+    pub name: FixedSizeString<PARTY_NAME_LENGTH>,
+}
+
+pub struct RemovePartyMember {
+    pub member_id: u16,
+}
+
+impl WritableResource for RemovePartyMember {
+    const IDENTIFIER: ServerMessage = ServerMessage::RemovePartyMember;
+    type Output = RemovePartyRaw;
+
+    fn write(self) -> Result<Self::Output, WritableResourceError> {
+        Ok(RemovePartyRaw {
+            target_id: self.member_id,
+            rsv: 0,
+        })
+    }
+}
+
+/// Position of a party member the receiver can't see on the map.
+pub struct PartyMemberPosition {
+    pub member_id: u16,
+    pub position: Position,
+}
+
+impl WritableResource for PartyMemberPosition {
+    const IDENTIFIER: ServerMessage = ServerMessage::PartyMemberPosition;
+    type Output = PartyMemberPositionRaw;
+
+    fn write(self) -> Result<Self::Output, WritableResourceError> {
+        Ok(PartyMemberPositionRaw {
+            position: PositionRaw {
+                x: self.position.x,
+                y: self.position.y,
+            },
+        })
+    }
+
+    fn client_id(&self) -> Option<u16> {
+        Some(self.member_id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct PartyMemberPositionRaw {
+    pub position: PositionRaw,
+}
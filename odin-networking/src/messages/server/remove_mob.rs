@@ -1,6 +1,8 @@
 use crate::{WritableResource, WritableResourceError, messages::ServerMessage};
 use deku::prelude::*;
 
+pub const REMOVE_TYPE_DEATH: i32 = 1;
+
 pub struct RemoveMob {
     pub mob_id: u16,
     // TODO: figure out what the remove type is, and if it can be an enum instead of an i32
@@ -0,0 +1,64 @@
+use crate::{
+    WritableResource, WritableResourceError,
+    messages::{ServerMessage, common::ItemRaw},
+};
+use deku::prelude::*;
+use odin_models::item::Item;
+
+pub const MAX_SHOP_ITEMS: usize = 27;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShopType {
+    Items,
+    Storage,
+}
+impl From<ShopType> for i32 {
+    fn from(value: ShopType) -> Self {
+        match value {
+            ShopType::Items => 1,
+            ShopType::Storage => 2,
+        }
+    }
+}
+
+pub struct ShopList {
+    pub merchant_id: u16,
+    pub shop_type: ShopType,
+    pub items: Vec<Item>,
+    pub tax: i32,
+}
+
+impl WritableResource for ShopList {
+    const IDENTIFIER: ServerMessage = ServerMessage::ShopList;
+    type Output = ShopListRaw;
+
+    fn write(self) -> Result<Self::Output, WritableResourceError> {
+        if self.items.len() > MAX_SHOP_ITEMS {
+            return Err(WritableResourceError::Generic(format!(
+                "Shop list has {} items, the maximum is {MAX_SHOP_ITEMS}",
+                self.items.len()
+            )));
+        }
+
+        let mut items = [ItemRaw::default(); MAX_SHOP_ITEMS];
+        for (raw, item) in items.iter_mut().zip(self.items) {
+            *raw = item.into();
+        }
+        Ok(ShopListRaw {
+            shop_type: self.shop_type.into(),
+            items,
+            tax: self.tax,
+        })
+    }
+
+    fn client_id(&self) -> Option<u16> {
+        Some(self.merchant_id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct ShopListRaw {
+    pub shop_type: i32,
+    pub items: [ItemRaw; MAX_SHOP_ITEMS],
+    pub tax: i32,
+}
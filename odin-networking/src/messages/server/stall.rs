@@ -0,0 +1,76 @@
+use crate::{
+    WritableResource, WritableResourceError,
+    messages::{
+        ServerMessage,
+        client::stall::{MAX_STALL_ITEMS, STALL_TITLE_LENGTH, StallRaw},
+        common::ItemRaw,
+        server::create_mob::{CreateMob, CreateMobRaw},
+        string::FixedSizeString,
+    },
+};
+use deku::prelude::*;
+use odin_models::item::Item;
+
+/// The personal shop of `owner_id`, as shown to a player browsing it.
+pub struct StallList {
+    pub owner_id: u16,
+    pub title: String,
+    /// Items by shop slot, with the inventory slot they come from and
+    /// their price.
+    pub items: Vec<(usize, usize, Item, u32)>,
+}
+
+impl WritableResource for StallList {
+    const IDENTIFIER: ServerMessage = ServerMessage::StallList;
+    type Output = StallRaw;
+
+    fn write(self) -> Result<Self::Output, WritableResourceError> {
+        let mut items = [ItemRaw::default(); MAX_STALL_ITEMS];
+        let mut inventory_slots = [-1; MAX_STALL_ITEMS];
+        let mut prices = [0; MAX_STALL_ITEMS];
+        for (stall_slot, inventory_slot, item, price) in self.items {
+            if stall_slot >= MAX_STALL_ITEMS {
+                return Err(WritableResourceError::Generic(format!(
+                    "Stall slot {stall_slot} is out of range"
+                )));
+            }
+            items[stall_slot] = item.into();
+            inventory_slots[stall_slot] = inventory_slot as i8;
+            prices[stall_slot] = price as i32;
+        }
+        Ok(StallRaw {
+            title: self.title.as_str().try_into()?,
+            items,
+            inventory_slots,
+            prices,
+            tax: 0,
+            owner_id: self.owner_id,
+        })
+    }
+}
+
+/// [`CreateMob`] for a player with an open personal shop, whose title is
+/// shown above it.
+#[derive(Clone)]
+pub struct CreateMobTrade {
+    pub create_mob: CreateMob,
+    pub title: String,
+}
+
+impl WritableResource for CreateMobTrade {
+    const IDENTIFIER: ServerMessage = ServerMessage::CreateMobTrade;
+    type Output = CreateMobTradeRaw;
+
+    fn write(self) -> Result<Self::Output, WritableResourceError> {
+        Ok(CreateMobTradeRaw {
+            create_mob: self.create_mob.write()?,
+            title: self.title.as_str().try_into()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, DekuRead, DekuWrite)]
+pub struct CreateMobTradeRaw {
+    pub create_mob: CreateMobRaw,
+    pub title: FixedSizeString<STALL_TITLE_LENGTH>,
+}
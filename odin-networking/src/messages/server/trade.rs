@@ -0,0 +1,57 @@
+use crate::{
+    WritableResource, WritableResourceError,
+    messages::{
+        ServerMessage,
+        client::trade::{MAX_TRADE_ITEMS, QuitTradeRaw, TradeRaw},
+        common::ItemRaw,
+    },
+};
+use odin_models::item::Item;
+
+/// The opponent's side of the trade window.
+pub struct Trade {
+    pub opponent_id: u16,
+    /// Offered items with the inventory slot they come from.
+    pub items: Vec<(usize, Item)>,
+    pub coin: u32,
+    pub check: u8,
+}
+
+impl WritableResource for Trade {
+    const IDENTIFIER: ServerMessage = ServerMessage::Trade;
+    type Output = TradeRaw;
+
+    fn write(self) -> Result<Self::Output, WritableResourceError> {
+        if self.items.len() > MAX_TRADE_ITEMS {
+            return Err(WritableResourceError::Generic(format!(
+                "Trade has {} items, the maximum is {MAX_TRADE_ITEMS}",
+                self.items.len()
+            )));
+        }
+
+        let mut items = [ItemRaw::default(); MAX_TRADE_ITEMS];
+        let mut inventory_slots = [-1; MAX_TRADE_ITEMS];
+        for (i, (slot, item)) in self.items.into_iter().enumerate() {
+            items[i] = item.into();
+            inventory_slots[i] = slot as i8;
+        }
+        Ok(TradeRaw {
+            items,
+            inventory_slots,
+            coin: self.coin as i32,
+            check: self.check,
+            opponent_id: self.opponent_id,
+        })
+    }
+}
+
+pub struct QuitTrade;
+
+impl WritableResource for QuitTrade {
+    const IDENTIFIER: ServerMessage = ServerMessage::QuitTrade;
+    type Output = QuitTradeRaw;
+
+    fn write(self) -> Result<Self::Output, WritableResourceError> {
+        Ok(QuitTradeRaw)
+    }
+}
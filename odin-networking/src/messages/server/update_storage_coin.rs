@@ -0,0 +1,24 @@
+use crate::{WritableResource, WritableResourceError, messages::ServerMessage};
+use deku::prelude::*;
+
+pub struct UpdateStorageCoin {
+    pub coin: u64,
+}
+
+impl WritableResource for UpdateStorageCoin {
+    const IDENTIFIER: ServerMessage = ServerMessage::UpdateStorageCoin;
+    type Output = UpdateStorageCoinRaw;
+
+    fn write(self) -> Result<Self::Output, WritableResourceError> {
+        Ok(UpdateStorageCoinRaw {
+            coin: i32::try_from(self.coin).map_err(|_| {
+                WritableResourceError::Generic(format!("Storage coin {} overflows", self.coin))
+            })?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, DekuRead, DekuWrite)]
+pub struct UpdateStorageCoinRaw {
+    pub coin: i32,
+}
@@ -1,7 +1,9 @@
+use chrono::NaiveDateTime;
 use odin_models::{
     account_charlist::{AccountCharlist, CharacterInfo},
     character::{Character, Class},
     nickname::Nickname,
+    storage::Storage,
     uuid::Uuid,
 };
 use std::future::Future;
@@ -35,6 +37,27 @@ pub trait AccountRepository: Clone + 'static {
         id: Uuid,
     ) -> impl Future<Output = Result<Option<String>, AccountRepositoryError>> + Send;
 
+    fn record_token_failure(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = Result<u32, AccountRepositoryError>> + Send;
+
+    fn reset_token_failures(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = Result<(), AccountRepositoryError>> + Send;
+
+    fn lock_token(
+        &self,
+        id: Uuid,
+        until: NaiveDateTime,
+    ) -> impl Future<Output = Result<(), AccountRepositoryError>> + Send;
+
+    fn get_token_lock(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = Result<Option<NaiveDateTime>, AccountRepositoryError>> + Send;
+
     fn create_character(
         &self,
         account_id: Uuid,
@@ -59,6 +82,38 @@ pub trait AccountRepository: Clone + 'static {
         account_id: Uuid,
         password: &str,
     ) -> impl Future<Output = Result<bool, AccountRepositoryError>> + Send;
+
+    fn save_character(
+        &self,
+        character: &Character,
+    ) -> impl Future<Output = Result<(), AccountRepositoryError>> + Send;
+
+    /// Saves every character in a single transaction, so either all of them
+    /// or none are written.
+    fn save_characters(
+        &self,
+        characters: &[Character],
+    ) -> impl Future<Output = Result<(), AccountRepositoryError>> + Send;
+
+    fn fetch_storage(
+        &self,
+        account_id: Uuid,
+    ) -> impl Future<Output = Result<Storage, AccountRepositoryError>> + Send;
+
+    fn save_storage(
+        &self,
+        account_id: Uuid,
+        storage: &Storage,
+    ) -> impl Future<Output = Result<(), AccountRepositoryError>> + Send;
+
+    /// Saves a character along with the storage of its account in a single
+    /// transaction, so a crash can't leave items duplicated between them.
+    fn save_player(
+        &self,
+        character: &Character,
+        account_id: Uuid,
+        storage: &Storage,
+    ) -> impl Future<Output = Result<(), AccountRepositoryError>> + Send;
 }
 
 #[derive(Debug, Error, PartialEq, Eq)]
@@ -0,0 +1,74 @@
+use odin_models::{character::GuildLevel, uuid::Uuid};
+use std::future::Future;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Guild {
+    pub id: i16,
+    pub name: String,
+    pub fame: i32,
+    pub kingdom: i32,
+    pub wins: i32,
+    pub sub_guilds: [String; 3],
+    pub notice: String,
+}
+
+pub trait GuildRepository: Clone + 'static {
+    /// Creates the guild and makes `leader` its [`GuildLevel::Leader`],
+    /// setting its coin to `leader_coin`, what is left after paying for the
+    /// guild, in the same transaction.
+    fn create_guild(
+        &self,
+        name: &str,
+        leader: Uuid,
+        leader_coin: i32,
+    ) -> impl Future<Output = Result<Guild, GuildRepositoryError>> + Send;
+
+    fn fetch_guild(
+        &self,
+        id: i16,
+    ) -> impl Future<Output = Result<Option<Guild>, GuildRepositoryError>> + Send;
+
+    /// The character of `guild` named `name`, ignoring case, whether it is
+    /// online or not.
+    fn find_member_by_name(
+        &self,
+        guild: i16,
+        name: &str,
+    ) -> impl Future<Output = Result<Option<Uuid>, GuildRepositoryError>> + Send;
+
+    /// Sets the character's guild and rank, or removes it from its guild
+    /// when `membership` is `None`.
+    fn set_membership(
+        &self,
+        character_id: Uuid,
+        membership: Option<(i16, GuildLevel)>,
+    ) -> impl Future<Output = Result<(), GuildRepositoryError>> + Send;
+
+    fn set_notice(
+        &self,
+        id: i16,
+        notice: &str,
+    ) -> impl Future<Output = Result<(), GuildRepositoryError>> + Send;
+
+    /// Deletes the guild, removing every member from it.
+    fn disband_guild(
+        &self,
+        id: i16,
+    ) -> impl Future<Output = Result<(), GuildRepositoryError>> + Send;
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum GuildRepositoryError {
+    #[error("Fail to query: {0}")]
+    FailToLoad(String),
+
+    #[error("{0}")]
+    Generic(String),
+
+    #[error("The entity has not been found")]
+    EntityNotFound,
+
+    #[error("Guild name is already taken")]
+    NameTaken,
+}
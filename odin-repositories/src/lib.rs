@@ -1 +1,2 @@
 pub mod account_repository;
+pub mod guild_repository;
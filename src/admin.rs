@@ -0,0 +1,342 @@
+use crate::{
+    configuration::{CliVer, Configuration, ServerState},
+    game_server_context::GameServerContext,
+    map::EntityId,
+    world::{Mob, World},
+};
+use odin_repositories::{account_repository::AccountRepository, guild_repository::GuildRepository};
+use std::{net::SocketAddr, str::FromStr};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    sync::{mpsc, oneshot},
+};
+
+const HELP: &str =
+    "Commands: state [open|maintenance], cliver [version], clients, kick <client_id>";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdminCommand {
+    Help,
+    GetState,
+    SetState(ServerState),
+    GetCliver,
+    SetCliver(CliVer),
+    Clients,
+    Kick(usize),
+}
+
+impl FromStr for AdminCommand {
+    type Err = AdminCommandError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut parts = line.split_whitespace();
+        let command = parts.next().ok_or(AdminCommandError::Empty)?;
+        let argument = parts.next();
+        if parts.next().is_some() {
+            return Err(AdminCommandError::TooManyArguments(command.to_string()));
+        }
+
+        match (command.to_lowercase().as_str(), argument) {
+            ("help", None) => Ok(AdminCommand::Help),
+            ("state", None) => Ok(AdminCommand::GetState),
+            ("state", Some(state)) => match state.to_lowercase().as_str() {
+                "open" => Ok(AdminCommand::SetState(ServerState::Open)),
+                "maintenance" => Ok(AdminCommand::SetState(ServerState::Maintenance)),
+                _ => Err(AdminCommandError::InvalidArgument(state.to_string())),
+            },
+            ("cliver", None) => Ok(AdminCommand::GetCliver),
+            ("cliver", Some(version)) => version
+                .parse()
+                .map(|version| AdminCommand::SetCliver(CliVer::new(version)))
+                .map_err(|_| AdminCommandError::InvalidArgument(version.to_string())),
+            ("clients", None) => Ok(AdminCommand::Clients),
+            ("kick", Some(client_id)) => client_id
+                .parse()
+                .map(AdminCommand::Kick)
+                .map_err(|_| AdminCommandError::InvalidArgument(client_id.to_string())),
+            ("kick", None) => Err(AdminCommandError::MissingArgument(command.to_string())),
+            ("help" | "clients", Some(_)) => {
+                Err(AdminCommandError::TooManyArguments(command.to_string()))
+            }
+            _ => Err(AdminCommandError::Unknown(command.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum AdminCommandError {
+    #[error("Empty command")]
+    Empty,
+
+    #[error("Unknown command: {0}")]
+    Unknown(String),
+
+    #[error("Missing argument for {0}")]
+    MissingArgument(String),
+
+    #[error("Too many arguments for {0}")]
+    TooManyArguments(String),
+
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+}
+
+pub struct AdminRequest {
+    pub command: AdminCommand,
+    pub reply: oneshot::Sender<String>,
+}
+
+pub async fn execute<A: AccountRepository, G: GuildRepository>(
+    command: AdminCommand,
+    context: &mut GameServerContext<A, G>,
+    world: &mut World,
+) -> String {
+    match command {
+        AdminCommand::Help => format!("OK {HELP}"),
+        AdminCommand::GetState => format!("OK {:?}", context.get_server_state()),
+        AdminCommand::SetState(state) => {
+            context.set_server_state(state);
+            log::info!("Server state changed to {:?} by admin", state);
+            format!("OK {:?}", state)
+        }
+        AdminCommand::GetCliver => format!("OK {}", context.get_current_cliver().get_version()),
+        AdminCommand::SetCliver(cliver) => {
+            context.set_current_cliver(cliver);
+            log::info!("Cliver changed to {} by admin", cliver.get_version());
+            format!("OK {}", cliver.get_version())
+        }
+        AdminCommand::Clients => {
+            let client_ids = context.client_ids();
+            let mut reply = String::new();
+            for client_id in &client_ids {
+                let name = match world.get_mob(EntityId::Player(*client_id)) {
+                    Some(Mob::Player(player)) => player.name.as_str(),
+                    _ => "-",
+                };
+                reply.push_str(&format!("{client_id} {name}\n"));
+            }
+            reply.push_str(&format!("OK {} clients", client_ids.len()));
+            reply
+        }
+        AdminCommand::Kick(client_id) => {
+            if !context.client_ids().contains(&client_id) {
+                return format!("ERR Unknown client {client_id}");
+            }
+            match context.disconnect_player(client_id, world).await {
+                Ok(()) => {
+                    log::info!("ClientId {} kicked by admin", client_id);
+                    format!("OK Kicked {client_id}")
+                }
+                Err(e) => format!("ERR {e:?}"),
+            }
+        }
+    }
+}
+
+pub async fn listen(
+    addr: SocketAddr,
+    requests: mpsc::UnboundedSender<AdminRequest>,
+) -> std::io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    log::info!("Admin channel listening on {}", addr);
+
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let requests = requests.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, requests).await {
+                log::warn!("Admin connection {} closed: {e}", peer);
+            }
+        });
+    }
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    requests: mpsc::UnboundedSender<AdminRequest>,
+) -> std::io::Result<()> {
+    let (read_half, mut write_half) = stream.into_split();
+    let mut lines = BufReader::new(read_half).lines();
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let reply = match line.parse::<AdminCommand>() {
+            Ok(command) => {
+                let (reply, response) = oneshot::channel();
+                if requests.send(AdminRequest { command, reply }).is_err() {
+                    break;
+                }
+                response
+                    .await
+                    .unwrap_or_else(|_| "ERR Server is shutting down".to_string())
+            }
+            Err(e) => format!("ERR {e}"),
+        };
+        write_half.write_all(reply.as_bytes()).await?;
+        write_half.write_all(b"\n").await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        configuration::{DEFAULT_KEYTABLE, ServerConfig},
+        handlers::tests::TestAccountRepository,
+        user_session::SenderSession,
+        world::Player,
+    };
+    use odin_database::{
+        account_repository::DatabaseAccountRepository, guild_repository::DatabaseGuildRepository,
+    };
+    use odin_models::{character::Character, position::Position};
+    use odin_networking::enc_session::EncDecSession;
+    use std::{rc::Rc, time::Instant};
+
+    async fn context() -> GameServerContext<DatabaseAccountRepository, DatabaseGuildRepository> {
+        let repository = TestAccountRepository::new().await;
+        GameServerContext::new(
+            repository.account_repository(),
+            repository.guild_repository(),
+            &ServerConfig {
+                cliver: 759,
+                max_clients: 10,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn connect(
+        context: &mut GameServerContext<DatabaseAccountRepository, DatabaseGuildRepository>,
+    ) -> usize {
+        let client_id = context.allocate_client_id().unwrap();
+        let (writer, _) = mpsc::unbounded_channel();
+        let encdec =
+            EncDecSession::new(client_id as u16, Rc::new(DEFAULT_KEYTABLE), Instant::now());
+        context.add_sender(client_id, SenderSession::new(encdec, writer));
+        client_id
+    }
+
+    #[test]
+    fn parse_commands() {
+        assert_eq!("help".parse(), Ok(AdminCommand::Help));
+        assert_eq!("state".parse(), Ok(AdminCommand::GetState));
+        assert_eq!(
+            "state open".parse(),
+            Ok(AdminCommand::SetState(ServerState::Open))
+        );
+        assert_eq!(
+            "STATE Maintenance".parse(),
+            Ok(AdminCommand::SetState(ServerState::Maintenance))
+        );
+        assert_eq!("cliver".parse(), Ok(AdminCommand::GetCliver));
+        assert_eq!(
+            "cliver 11022".parse(),
+            Ok(AdminCommand::SetCliver(CliVer::new(11022)))
+        );
+        assert_eq!("clients".parse(), Ok(AdminCommand::Clients));
+        assert_eq!("  kick   7 ".parse(), Ok(AdminCommand::Kick(7)));
+    }
+
+    #[test]
+    fn parse_invalid_commands() {
+        assert_eq!("".parse::<AdminCommand>(), Err(AdminCommandError::Empty));
+        assert_eq!(
+            "shutdown".parse::<AdminCommand>(),
+            Err(AdminCommandError::Unknown("shutdown".to_string()))
+        );
+        assert_eq!(
+            "state closed".parse::<AdminCommand>(),
+            Err(AdminCommandError::InvalidArgument("closed".to_string()))
+        );
+        assert_eq!(
+            "cliver abc".parse::<AdminCommand>(),
+            Err(AdminCommandError::InvalidArgument("abc".to_string()))
+        );
+        assert_eq!(
+            "kick".parse::<AdminCommand>(),
+            Err(AdminCommandError::MissingArgument("kick".to_string()))
+        );
+        assert_eq!(
+            "kick 1 2".parse::<AdminCommand>(),
+            Err(AdminCommandError::TooManyArguments("kick".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn set_state_and_cliver_apply_to_context() {
+        let mut context = context().await;
+        let mut world = World::default();
+
+        let reply = execute(
+            AdminCommand::SetState(ServerState::Open),
+            &mut context,
+            &mut world,
+        )
+        .await;
+        assert_eq!(reply, "OK Open");
+        assert_eq!(context.get_server_state(), ServerState::Open);
+
+        let reply = execute(
+            AdminCommand::SetCliver(CliVer::new(11022)),
+            &mut context,
+            &mut world,
+        )
+        .await;
+        assert_eq!(reply, "OK 11022");
+        assert_eq!(context.get_current_cliver(), CliVer::new(11022));
+    }
+
+    #[tokio::test]
+    async fn clients_lists_connected_clients() {
+        let mut context = context().await;
+        let mut world = World::default();
+        let first = connect(&mut context);
+        let second = connect(&mut context);
+        let entity_id = EntityId::Player(second);
+        let player = Player::from_character(
+            entity_id,
+            Character {
+                name: "Admin".to_string(),
+                ..Default::default()
+            },
+        );
+        world
+            .add_player(entity_id, player, Position { x: 2100, y: 2100 })
+            .unwrap();
+
+        let reply = execute(AdminCommand::Clients, &mut context, &mut world).await;
+        assert_eq!(reply, format!("{first} -\n{second} Admin\nOK 2 clients"));
+    }
+
+    #[tokio::test]
+    async fn kick_disconnects_client() {
+        let mut context = context().await;
+        let mut world = World::default();
+        let client_id = connect(&mut context);
+        let entity_id = EntityId::Player(client_id);
+        let player = Player::from_character(
+            entity_id,
+            Character {
+                name: "Kicked".to_string(),
+                ..Default::default()
+            },
+        );
+        world
+            .add_player(entity_id, player, Position { x: 2100, y: 2100 })
+            .unwrap();
+
+        let reply = execute(AdminCommand::Kick(client_id), &mut context, &mut world).await;
+        assert_eq!(reply, format!("OK Kicked {client_id}"));
+        assert!(context.client_ids().is_empty());
+        assert!(!world.entity_exists(entity_id));
+
+        let reply = execute(AdminCommand::Kick(client_id), &mut context, &mut world).await;
+        assert_eq!(reply, format!("ERR Unknown client {client_id}"));
+    }
+}
@@ -0,0 +1,207 @@
+use crate::map::EntityId;
+use crate::packets::BroadcastUpdateScore;
+use crate::session::{PacketSender, SessionError};
+use crate::world::{Mob, World};
+use odin_models::affect::AffectKind;
+use std::time::Duration;
+
+/// Length of one affect tick; `Affect::remaining_ticks` counts these.
+pub const AFFECT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Counts down the affects of every mob on a fixed interval driven by the
+/// main tick, like `RegenTicker`.
+pub struct AffectTicker {
+    ticks_per_affect: u64,
+    tick_counter: u64,
+}
+
+impl AffectTicker {
+    pub fn new(tick_interval: Duration) -> Self {
+        let ticks = AFFECT_INTERVAL.as_millis() / tick_interval.as_millis().max(1);
+        Self {
+            ticks_per_affect: (ticks as u64).max(1),
+            tick_counter: 0,
+        }
+    }
+
+    pub fn tick<P: PacketSender>(
+        &mut self,
+        world: &mut World,
+        sender: &P,
+    ) -> Result<(), SessionError> {
+        self.tick_counter += 1;
+        if !self.tick_counter.is_multiple_of(self.ticks_per_affect) {
+            return Ok(());
+        }
+        process_affects(world, sender)
+    }
+}
+
+/// Applies poison, consumes one tick of every affect and refreshes the
+/// score of mobs whose affects expired. Poison never kills on its own.
+pub fn process_affects<P: PacketSender>(world: &mut World, sender: &P) -> Result<(), SessionError> {
+    let ids: Vec<EntityId> = world
+        .player_ids()
+        .into_iter()
+        .chain(world.npc_ids())
+        .collect();
+    for entity_id in ids {
+        let (affects, computed) = match world.get_mob_mut(entity_id) {
+            Some(Mob::Player(player)) => (&mut player.affects, &mut player.computed),
+            Some(Mob::Npc(npc)) => (&mut npc.affects, &mut npc.computed),
+            None => continue,
+        };
+        if affects.is_empty() {
+            continue;
+        }
+
+        let score = &mut computed.score;
+        let poison = affects.sum(AffectKind::Poison).max(0) as u32;
+        let poisoned = poison > 0 && score.hp > 1;
+        if poisoned {
+            score.hp = score.hp.saturating_sub(poison).max(1);
+        }
+
+        let expired = affects.tick();
+        if !expired.is_empty() {
+            log::debug!("{entity_id:?} lost affects {expired:?}");
+            world.recalculate_score(entity_id);
+        }
+        if poisoned || !expired.is_empty() {
+            world.broadcast_update_score(entity_id, sender)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::tests::MockPacketSender;
+    use crate::world::Player;
+    use odin_models::{
+        affect::{Affect, AffectSource},
+        character::Character,
+        position::Position,
+    };
+    use odin_networking::messages::ServerMessage;
+
+    fn affect(kind: AffectKind, value: i32, remaining_ticks: u32) -> Affect {
+        Affect {
+            id: kind as u8,
+            value,
+            remaining_ticks,
+            source: AffectSource::System,
+        }
+    }
+
+    fn add_player(world: &mut World, client_id: usize) -> EntityId {
+        let entity_id = EntityId::Player(client_id);
+        let player = Player::from_character(
+            entity_id,
+            Character {
+                score: odin_models::status::Score {
+                    hp: 50,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+        let position = Position {
+            x: 2100 + client_id as u16 * 2,
+            y: 2100,
+        };
+        world.add_player(entity_id, player, position).unwrap();
+        world.recalculate_score(entity_id);
+        entity_id
+    }
+
+    fn player(world: &World, entity_id: EntityId) -> &Player {
+        let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+            panic!("expected Player");
+        };
+        player
+    }
+
+    #[test]
+    fn add_affect_folds_into_score_and_broadcasts_affect_bytes() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let entity_id = add_player(&mut world, 1);
+        let watcher = add_player(&mut world, 2);
+        let damage = player(&world, entity_id).computed.score.damage;
+
+        let added = world
+            .add_affect(entity_id, affect(AffectKind::Damage, 30, 5), &sender)
+            .unwrap();
+
+        assert!(added);
+        assert_eq!(player(&world, entity_id).computed.score.damage, damage + 30);
+        let packets = sender.messages_for(watcher);
+        assert_eq!(packets.len(), 1);
+        assert_eq!(packets[0].identifier, ServerMessage::UpdateScore);
+    }
+
+    #[test]
+    fn expired_affects_are_removed_from_score() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let entity_id = add_player(&mut world, 1);
+        let damage = player(&world, entity_id).computed.score.damage;
+        world
+            .add_affect(entity_id, affect(AffectKind::Damage, 30, 2), &sender)
+            .unwrap();
+
+        process_affects(&mut world, &sender).unwrap();
+        assert_eq!(player(&world, entity_id).computed.score.damage, damage + 30);
+
+        process_affects(&mut world, &sender).unwrap();
+        assert!(player(&world, entity_id).affects.is_empty());
+        assert_eq!(player(&world, entity_id).computed.score.damage, damage);
+    }
+
+    #[test]
+    fn poison_drains_hp_but_never_kills() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let entity_id = add_player(&mut world, 1);
+        world
+            .add_affect(entity_id, affect(AffectKind::Poison, 30, 10), &sender)
+            .unwrap();
+
+        process_affects(&mut world, &sender).unwrap();
+        assert_eq!(player(&world, entity_id).computed.score.hp, 20);
+
+        process_affects(&mut world, &sender).unwrap();
+        assert_eq!(player(&world, entity_id).computed.score.hp, 1);
+    }
+
+    #[test]
+    fn ticker_runs_once_per_affect_interval() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let entity_id = add_player(&mut world, 1);
+        world
+            .add_affect(entity_id, affect(AffectKind::Haste, 10, 5), &sender)
+            .unwrap();
+        let mut ticker = AffectTicker::new(AFFECT_INTERVAL / 2);
+
+        ticker.tick(&mut world, &sender).unwrap();
+        assert_eq!(
+            player(&world, entity_id)
+                .affects
+                .get(AffectKind::Haste as u8)
+                .map(|a| a.remaining_ticks),
+            Some(5)
+        );
+
+        ticker.tick(&mut world, &sender).unwrap();
+        assert_eq!(
+            player(&world, entity_id)
+                .affects
+                .get(AffectKind::Haste as u8)
+                .map(|a| a.remaining_ticks),
+            Some(4)
+        );
+    }
+}
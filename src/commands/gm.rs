@@ -0,0 +1,713 @@
+use super::{CommandContext, CommandError, CommandRegistry};
+use crate::handlers::gameplay::{
+    action::{self, ActionError},
+    attack,
+};
+use crate::locale::MessageKey;
+use crate::map::EntityId;
+use crate::npc::{loading, spawn_manager::SpawnManager};
+use crate::packets::{BroadcastUpdateScore, ToCreateMob, ToUpdateEtc};
+use crate::session::PacketSender;
+use crate::world::{Mob, World};
+use odin_models::{item::Item, position::Position};
+use odin_networking::messages::server::{
+    create_item::{CreateItem, SlotType},
+    remove_mob::RemoveMob,
+};
+use std::time::Instant;
+
+pub fn register<P: PacketSender>(registry: &mut CommandRegistry<P>) {
+    registry.register("teleport", "/teleport <x> <y>", 1, teleport);
+    registry.register("summon", "/summon <name>", 1, summon);
+    registry.register("invisible", "/invisible", 1, invisible);
+    registry.register("spawn", "/spawn <group>", 10, spawn);
+    registry.register("kill", "/kill [name|mob id]", 50, kill);
+    registry.register("item", "/item <id> [effect value]...", 50, item);
+    registry.register("level", "/level <level> [name]", 50, level);
+    registry.register("coin", "/coin <amount> [name]", 50, coin);
+    registry.register("reload", "/reload", 100, reload);
+}
+
+fn teleport<P: PacketSender>(
+    context: &mut CommandContext<P>,
+    args: &[&str],
+) -> Result<String, CommandError> {
+    let [x, y] = args else {
+        return Err(CommandError::Usage(""));
+    };
+    let destiny = Position {
+        x: parse(x)?,
+        y: parse(y)?,
+    };
+    if destiny.x == 0 || destiny.x >= 4096 || destiny.y == 0 || destiny.y >= 4096 {
+        return Err(CommandError::Failed(context.messages.format(
+            MessageKey::CommandOutOfBounds,
+            &[("x", &destiny.x), ("y", &destiny.y)],
+        )));
+    }
+
+    let position = move_entity(context.world, context.sender, context.issuer, destiny)?;
+    Ok(context.messages.format(
+        MessageKey::CommandTeleported,
+        &[("x", &position.x), ("y", &position.y)],
+    ))
+}
+
+fn summon<P: PacketSender>(
+    context: &mut CommandContext<P>,
+    args: &[&str],
+) -> Result<String, CommandError> {
+    let [name] = args else {
+        return Err(CommandError::Usage(""));
+    };
+    let target = find_player(context.world, name)?;
+    let destiny = context
+        .world
+        .map()
+        .get_position(context.issuer)
+        .ok_or(CommandError::IssuerNotFound)?;
+
+    move_entity(context.world, context.sender, target, destiny)?;
+    Ok(context
+        .messages
+        .format(MessageKey::CommandSummoned, &[("name", name)]))
+}
+
+fn invisible<P: PacketSender>(
+    context: &mut CommandContext<P>,
+    args: &[&str],
+) -> Result<String, CommandError> {
+    if !args.is_empty() {
+        return Err(CommandError::Usage(""));
+    }
+    let Some(Mob::Player(player)) = context.world.get_mob_mut(context.issuer) else {
+        return Err(CommandError::IssuerNotFound);
+    };
+    player.invisible = !player.invisible;
+    let invisible = player.invisible;
+
+    let position = context
+        .world
+        .map()
+        .get_position(context.issuer)
+        .ok_or(CommandError::IssuerNotFound)?;
+    let mob = context
+        .world
+        .get_mob(context.issuer)
+        .ok_or(CommandError::IssuerNotFound)?;
+    for spectator in context.world.map().get_spectators(position, context.issuer) {
+        if invisible {
+            context.sender.send_to(
+                spectator,
+                RemoveMob {
+                    mob_id: context.issuer.id() as u16,
+                    remove_type: 0,
+                },
+            )?;
+        } else {
+            mob.send_create_mob(spectator, position, context.sender)?;
+        }
+    }
+
+    let reply = if invisible {
+        MessageKey::CommandInvisibilityEnabled
+    } else {
+        MessageKey::CommandInvisibilityDisabled
+    };
+    Ok(context.messages.get(reply).to_string())
+}
+
+fn spawn<P: PacketSender>(
+    context: &mut CommandContext<P>,
+    args: &[&str],
+) -> Result<String, CommandError> {
+    let [group] = args else {
+        return Err(CommandError::Usage(""));
+    };
+    if !context.spawn_manager.contains(group) {
+        return Err(CommandError::Failed(
+            context
+                .messages
+                .format(MessageKey::CommandUnknownSpawnGroup, &[("group", group)]),
+        ));
+    }
+
+    context
+        .spawn_manager
+        .spawn_by_id(group, context.world, context.sender);
+    Ok(context
+        .messages
+        .format(MessageKey::CommandSpawned, &[("group", group)]))
+}
+
+fn kill<P: PacketSender>(
+    context: &mut CommandContext<P>,
+    args: &[&str],
+) -> Result<String, CommandError> {
+    let target = match args {
+        [] => context.issuer,
+        [target] => match target.parse().map(EntityId::from_id) {
+            Ok(npc @ EntityId::Mob(id)) => {
+                if !context.world.entity_exists(npc) {
+                    return Err(CommandError::MobNotFound(id));
+                }
+                npc
+            }
+            _ => find_player(context.world, target)?,
+        },
+        _ => return Err(CommandError::Usage("")),
+    };
+    let name = match context.world.get_mob_mut(target) {
+        Some(Mob::Player(player)) => {
+            player.computed.score.hp = 0;
+            player.name.clone()
+        }
+        Some(Mob::Npc(npc)) => {
+            npc.computed.score.hp = 0;
+            npc.template.name.clone()
+        }
+        None => return Err(CommandError::IssuerNotFound),
+    };
+
+    context
+        .world
+        .broadcast_update_score(target, context.sender)?;
+    attack::on_mob_killed(
+        context.world,
+        context.issuer,
+        target,
+        context.sender,
+        &mut rand::thread_rng(),
+        Instant::now(),
+    )?;
+    Ok(context
+        .messages
+        .format(MessageKey::CommandKilled, &[("name", &name)]))
+}
+
+fn item<P: PacketSender>(
+    context: &mut CommandContext<P>,
+    args: &[&str],
+) -> Result<String, CommandError> {
+    let Some((id, effects)) = args.split_first() else {
+        return Err(CommandError::Usage(""));
+    };
+    if effects.len() % 2 != 0 || effects.len() > 6 {
+        return Err(CommandError::Usage(""));
+    }
+    let id: u16 = parse(id)?;
+    let Some(item_data) = context.world.item_db().get(id) else {
+        return Err(CommandError::Failed(
+            context
+                .messages
+                .format(MessageKey::CommandUnknownItem, &[("id", &id)]),
+        ));
+    };
+    let item_name = item_data.name.clone();
+
+    let mut item = Item::from(id);
+    for (effect, pair) in item.effects.iter_mut().zip(effects.chunks(2)) {
+        effect.index = parse(pair[0])?;
+        effect.value = parse(pair[1])?;
+    }
+
+    let Some(Mob::Player(player)) = context.world.get_mob_mut(context.issuer) else {
+        return Err(CommandError::IssuerNotFound);
+    };
+    let Some(slot) = player.inventory.first_empty() else {
+        return Err(CommandError::Failed(
+            context
+                .messages
+                .get(MessageKey::CommandInventoryFull)
+                .to_string(),
+        ));
+    };
+    player.inventory.set(slot, item);
+
+    context.sender.send_to(
+        context.issuer,
+        CreateItem {
+            mob_id: context.issuer.id() as u16,
+            slot_type: SlotType::Inventory,
+            slot: slot as u16,
+            item: Some(item),
+        },
+    )?;
+    Ok(context.messages.format(
+        MessageKey::CommandItemCreated,
+        &[("item", &item_name), ("slot", &slot)],
+    ))
+}
+
+fn level<P: PacketSender>(
+    context: &mut CommandContext<P>,
+    args: &[&str],
+) -> Result<String, CommandError> {
+    let (level, target) = match args {
+        [level] => (parse::<u16>(level)?, context.issuer),
+        [level, name] => (parse::<u16>(level)?, find_player(context.world, name)?),
+        _ => return Err(CommandError::Usage("")),
+    };
+    let Some(Mob::Player(player)) = context.world.get_mob(target) else {
+        return Err(CommandError::IssuerNotFound);
+    };
+    let name = player.name.clone();
+    let max_level = context
+        .world
+        .experience_table()
+        .max_level(player.evolution)
+        .unwrap_or(player.score.level);
+    if level > max_level {
+        return Err(CommandError::Failed(
+            context
+                .messages
+                .format(MessageKey::CommandMaxLevel, &[("level", &max_level)]),
+        ));
+    }
+
+    let level = context.world.set_level(target, level, context.sender)?;
+    Ok(context.messages.format(
+        MessageKey::CommandLevelSet,
+        &[("name", &name), ("level", &level)],
+    ))
+}
+
+fn coin<P: PacketSender>(
+    context: &mut CommandContext<P>,
+    args: &[&str],
+) -> Result<String, CommandError> {
+    let (coin, target) = match args {
+        [coin] => (parse::<i32>(coin)?, context.issuer),
+        [coin, name] => (parse::<i32>(coin)?, find_player(context.world, name)?),
+        _ => return Err(CommandError::Usage("")),
+    };
+    if coin < 0 {
+        return Err(CommandError::Failed(
+            context
+                .messages
+                .get(MessageKey::CommandNegativeCoin)
+                .to_string(),
+        ));
+    }
+
+    let Some(Mob::Player(player)) = context.world.get_mob_mut(target) else {
+        return Err(CommandError::IssuerNotFound);
+    };
+    player.coin = coin;
+    let name = player.name.clone();
+
+    update_etc(context.world, context.sender, target)?;
+    Ok(context.messages.format(
+        MessageKey::CommandCoinSet,
+        &[("name", &name), ("coin", &coin)],
+    ))
+}
+
+fn reload<P: PacketSender>(
+    context: &mut CommandContext<P>,
+    args: &[&str],
+) -> Result<String, CommandError> {
+    if !args.is_empty() {
+        return Err(CommandError::Usage(""));
+    }
+    let messages = context.messages;
+    let templates = loading::load_mob_templates(&context.data.mobs).map_err(|e| {
+        CommandError::Failed(messages.format(MessageKey::CommandMobsLoadFailed, &[("error", &e)]))
+    })?;
+    let configs = loading::load_spawn_groups(&context.data.spawns, &templates).map_err(|e| {
+        CommandError::Failed(messages.format(MessageKey::CommandSpawnsLoadFailed, &[("error", &e)]))
+    })?;
+    let groups = configs.len();
+
+    despawn_npcs(context.world, context.spawn_manager, context.sender)?;
+    context.spawn_manager.reload(configs);
+    Ok(context
+        .messages
+        .format(MessageKey::CommandReloaded, &[("groups", &groups)]))
+}
+
+fn despawn_npcs<P: PacketSender>(
+    world: &mut World,
+    spawn_manager: &mut SpawnManager,
+    sender: &P,
+) -> Result<(), CommandError> {
+    for npc_id in world.npc_ids() {
+        let Ok(removed) = world.remove_entity(npc_id) else {
+            continue;
+        };
+        for spectator in removed.spectators {
+            sender.send_to(
+                spectator,
+                RemoveMob {
+                    mob_id: npc_id.id() as u16,
+                    remove_type: 0,
+                },
+            )?;
+        }
+        spawn_manager.release_mob_id(npc_id.id());
+    }
+    Ok(())
+}
+
+fn move_entity<P: PacketSender>(
+    world: &mut World,
+    sender: &P,
+    entity_id: EntityId,
+    destiny: Position,
+) -> Result<Position, CommandError> {
+    match action::teleport(world, sender, entity_id, destiny) {
+        Ok(position) => Ok(position),
+        Err(ActionError::EntityNotFound) => Err(CommandError::IssuerNotFound),
+        Err(e) => Err(CommandError::Failed(e.to_string())),
+    }
+}
+
+fn update_etc<P: PacketSender>(
+    world: &World,
+    sender: &P,
+    entity_id: EntityId,
+) -> Result<(), CommandError> {
+    let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+        return Err(CommandError::IssuerNotFound);
+    };
+    sender.send_to(entity_id, player.to_update_etc())?;
+    Ok(())
+}
+
+fn find_player(world: &World, name: &str) -> Result<EntityId, CommandError> {
+    world
+        .find_player_by_name(name)
+        .ok_or_else(|| CommandError::PlayerNotFound(name.to_string()))
+}
+
+fn parse<T: std::str::FromStr>(value: &str) -> Result<T, CommandError> {
+    value.parse().map_err(|_| CommandError::Usage(""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configuration::DataConfig;
+    use crate::handlers::tests::MockPacketSender;
+    use crate::locale::MessageCatalog;
+    use crate::npc::{
+        Npc,
+        movement::{MovementBehavior, MovementState},
+    };
+    use crate::score::experience::ExperienceTable;
+    use crate::stall::Stall;
+    use crate::world::Player;
+    use odin_models::account::AccessLevel;
+    use odin_models::character::Character;
+    use odin_models::item_data::{ItemData, ItemDataEffect, ItemDatabase, MAX_ITEM_DATA_EFFECTS};
+    use odin_models::{npc_mob::NpcMob, status::Score};
+    use odin_networking::messages::ServerMessage;
+    use std::path::Path;
+
+    fn make_item_data(id: u16, name: &str) -> ItemData {
+        ItemData {
+            id,
+            name: name.to_string(),
+            mesh: (0, 0),
+            level: 0,
+            str_req: 0,
+            int_req: 0,
+            dex_req: 0,
+            con_req: 0,
+            effects: [ItemDataEffect::default(); MAX_ITEM_DATA_EFFECTS],
+            price: 0,
+            unique: 0,
+            pos: 0,
+            extreme: 0,
+            grade: 0,
+        }
+    }
+
+    fn add_player(world: &mut World, client_id: usize, pos: Position) -> EntityId {
+        let entity_id = EntityId::Player(client_id);
+        let mut player = Player::from_character(
+            entity_id,
+            Character {
+                name: format!("Player{}", client_id),
+                last_pos: pos,
+                ..Default::default()
+            },
+        );
+        player.access = Some(AccessLevel::Administrator);
+        world.add_player(entity_id, player, pos).unwrap();
+        entity_id
+    }
+
+    fn run(
+        world: &mut World,
+        sender: &MockPacketSender,
+        issuer: EntityId,
+        line: &str,
+    ) -> Result<String, CommandError> {
+        let mut spawn_manager = SpawnManager::new(vec![]);
+        let messages = MessageCatalog::load(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("locales"),
+            "en-US",
+        )
+        .unwrap();
+        let mut context = CommandContext {
+            issuer,
+            world,
+            spawn_manager: &mut spawn_manager,
+            sender,
+            data: &DataConfig::default(),
+            messages: &messages,
+        };
+        CommandRegistry::default().execute(&mut context, line)
+    }
+
+    fn identifiers(sender: &MockPacketSender, entity_id: EntityId) -> Vec<ServerMessage> {
+        sender
+            .messages_for(entity_id)
+            .iter()
+            .map(|packet| packet.identifier)
+            .collect()
+    }
+
+    #[test]
+    fn teleport_moves_the_issuer() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let gm = add_player(&mut world, 1, Position { x: 2100, y: 2100 });
+
+        run(&mut world, &sender, gm, "/teleport 2500 2600").unwrap();
+
+        assert_eq!(
+            world.map().get_position(gm),
+            Some(Position { x: 2500, y: 2600 })
+        );
+        assert_eq!(identifiers(&sender, gm), vec![ServerMessage::Action]);
+        assert!(matches!(
+            run(&mut world, &sender, gm, "/teleport 0 10"),
+            Err(CommandError::Failed(_))
+        ));
+    }
+
+    #[test]
+    fn summon_brings_the_target_to_the_issuer() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let gm = add_player(&mut world, 1, Position { x: 2100, y: 2100 });
+        let target = add_player(&mut world, 2, Position { x: 3000, y: 3000 });
+
+        run(&mut world, &sender, gm, "/summon player2").unwrap();
+
+        let position = world.map().get_position(target).unwrap();
+        assert!(position.x.abs_diff(2100) <= 1 && position.y.abs_diff(2100) <= 1);
+        assert_eq!(
+            run(&mut world, &sender, gm, "/summon Nobody"),
+            Err(CommandError::PlayerNotFound("Nobody".to_string()))
+        );
+    }
+
+    #[test]
+    fn invisible_toggles_and_notifies_spectators() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let gm = add_player(&mut world, 1, Position { x: 2100, y: 2100 });
+        let watcher = add_player(&mut world, 2, Position { x: 2105, y: 2105 });
+
+        run(&mut world, &sender, gm, "/invisible").unwrap();
+        assert!(matches!(world.get_mob(gm), Some(Mob::Player(p)) if p.invisible));
+        run(&mut world, &sender, gm, "/invisible").unwrap();
+        assert!(matches!(world.get_mob(gm), Some(Mob::Player(p)) if !p.invisible));
+
+        assert_eq!(
+            identifiers(&sender, watcher),
+            vec![ServerMessage::RemoveMob, ServerMessage::CreateMob]
+        );
+    }
+
+    #[test]
+    fn item_creates_into_first_free_inventory_slot() {
+        let mut world = World::new(ItemDatabase::from_items([make_item_data(1000, "Espada")]));
+        let sender = MockPacketSender::default();
+        let gm = add_player(&mut world, 1, Position { x: 2100, y: 2100 });
+        if let Some(Mob::Player(player)) = world.get_mob_mut(gm) {
+            player.inventory.set(0, Item::from(1));
+        }
+
+        let reply = run(&mut world, &sender, gm, "/item 1000 2 10").unwrap();
+
+        assert_eq!(reply, "Created Espada in slot 1");
+        let Some(Mob::Player(player)) = world.get_mob(gm) else {
+            panic!("player should exist");
+        };
+        assert_eq!(player.inventory.get(1), Some(&Item::from((1000, 2, 10))));
+        assert_eq!(identifiers(&sender, gm), vec![ServerMessage::CreateItem]);
+        assert_eq!(
+            run(&mut world, &sender, gm, "/item 999"),
+            Err(CommandError::Failed("Unknown item 999".to_string()))
+        );
+    }
+
+    /// Levels 0 to 60, each 100 experience apart.
+    fn experience_table() -> ExperienceTable {
+        let levels: Vec<String> = (0..=60).map(|level| (level * 100).to_string()).collect();
+        ExperienceTable::from_toml(&format!("mortal = [{}]", levels.join(", "))).unwrap()
+    }
+
+    #[test]
+    fn level_and_coin_update_the_target() {
+        let mut world = World::default();
+        world.set_experience_table(experience_table());
+        let sender = MockPacketSender::default();
+        let gm = add_player(&mut world, 1, Position { x: 2100, y: 2100 });
+        let target = add_player(&mut world, 2, Position { x: 3000, y: 3000 });
+
+        run(&mut world, &sender, gm, "/level 50 Player2").unwrap();
+        run(&mut world, &sender, gm, "/coin 1000 Player2").unwrap();
+
+        let Some(Mob::Player(player)) = world.get_mob(target) else {
+            panic!("player should exist");
+        };
+        assert_eq!(player.score.level, 50);
+        assert!(player.score_bonus > 0);
+        assert_eq!(player.coin, 1000);
+        assert_eq!(
+            identifiers(&sender, target),
+            vec![
+                ServerMessage::UpdateEtc,
+                ServerMessage::UpdateScore,
+                ServerMessage::Motion,
+                ServerMessage::UpdateEtc
+            ]
+        );
+    }
+
+    #[test]
+    fn level_grants_skill_points_up_to_the_table_cap() {
+        let mut world = World::default();
+        world.set_experience_table(experience_table());
+        let sender = MockPacketSender::default();
+        let gm = add_player(&mut world, 1, Position { x: 2100, y: 2100 });
+        let skill_bonus_and_experience = |world: &World| {
+            let Some(Mob::Player(player)) = world.get_mob(gm) else {
+                panic!("player should exist");
+            };
+            (player.skill_bonus, player.experience)
+        };
+
+        run(&mut world, &sender, gm, "/level 10").unwrap();
+        assert_eq!(skill_bonus_and_experience(&world), (30, 1000));
+
+        assert_eq!(
+            run(&mut world, &sender, gm, "/level 61"),
+            Err(CommandError::Failed("Level must be at most 60".to_string()))
+        );
+
+        run(&mut world, &sender, gm, "/level 5").unwrap();
+        assert_eq!(skill_bonus_and_experience(&world), (15, 500));
+    }
+
+    #[test]
+    fn kill_goes_through_the_death_path() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let gm = add_player(&mut world, 1, Position { x: 2100, y: 2100 });
+        let target = add_player(&mut world, 2, Position { x: 2105, y: 2105 });
+        let partner = add_player(&mut world, 3, Position { x: 2104, y: 2105 });
+        let watcher = add_player(&mut world, 4, Position { x: 2106, y: 2106 });
+        world.trades_mut().request(partner, target).unwrap();
+        world.trades_mut().accept(target, partner).unwrap();
+        if let Some(Mob::Player(player)) = world.get_mob_mut(target) {
+            player.stall = Some(Box::new(Stall {
+                title: "Shop".to_string(),
+                items: Default::default(),
+            }));
+        }
+
+        run(&mut world, &sender, gm, "/kill Player2").unwrap();
+
+        let Some(Mob::Player(player)) = world.get_mob(target) else {
+            panic!("player should exist");
+        };
+        assert_eq!(player.computed.score.hp, 0);
+        assert!(player.stall.is_none());
+        assert_eq!(world.trades().partner_of(partner), None);
+        assert!(identifiers(&sender, partner).contains(&ServerMessage::QuitTrade));
+        assert_eq!(
+            identifiers(&sender, watcher),
+            vec![ServerMessage::UpdateScore, ServerMessage::CreateMob]
+        );
+    }
+
+    #[test]
+    fn kill_removes_npcs_with_the_death_animation() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let gm = add_player(&mut world, 1, Position { x: 2100, y: 2100 });
+        let watcher = add_player(&mut world, 2, Position { x: 2106, y: 2106 });
+        let npc = EntityId::Mob(1000);
+        let template = NpcMob {
+            name: "Wolf".to_string(),
+            score: Score {
+                max_hp: 100,
+                hp: 100,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let movement = MovementState::new(MovementBehavior::Stationary, 1);
+        world
+            .add_npc(
+                npc,
+                Npc::new(npc, template, movement),
+                Position { x: 2105, y: 2105 },
+            )
+            .unwrap();
+
+        assert_eq!(
+            run(&mut world, &sender, gm, "/kill 1000"),
+            Ok("Killed Wolf".to_string())
+        );
+
+        assert!(!world.entity_exists(npc));
+        assert_eq!(
+            identifiers(&sender, watcher),
+            vec![ServerMessage::UpdateScore, ServerMessage::RemoveMob]
+        );
+        assert_eq!(
+            run(&mut world, &sender, gm, "/kill 1000"),
+            Err(CommandError::MobNotFound(1000))
+        );
+    }
+
+    #[test]
+    fn spawn_rejects_unknown_groups() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let gm = add_player(&mut world, 1, Position { x: 2100, y: 2100 });
+
+        assert_eq!(
+            run(&mut world, &sender, gm, "/spawn Nobody"),
+            Err(CommandError::Failed(
+                "Unknown spawn group Nobody".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn commands_are_gated_by_access_level() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let gm = add_player(&mut world, 1, Position { x: 2100, y: 2100 });
+        if let Some(Mob::Player(player)) = world.get_mob_mut(gm) {
+            player.access = Some(AccessLevel::GameMaster(1));
+        }
+
+        assert!(run(&mut world, &sender, gm, "/teleport 2200 2200").is_ok());
+        assert_eq!(
+            run(&mut world, &sender, gm, "/coin 10"),
+            Err(CommandError::PermissionDenied {
+                command: "coin",
+                required: 50
+            })
+        );
+    }
+}
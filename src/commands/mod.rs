@@ -0,0 +1,349 @@
+pub mod gm;
+
+use crate::configuration::DataConfig;
+use crate::locale::{MessageCatalog, MessageKey};
+use crate::map::EntityId;
+use crate::npc::spawn_manager::SpawnManager;
+use crate::session::{PacketSender, SessionError};
+use crate::world::{Mob, World};
+use odin_models::account::AccessLevel;
+use odin_networking::messages::server::message_panel::MessagePanel;
+use thiserror::Error;
+
+pub const COMMAND_PREFIX: char = '/';
+
+pub struct CommandContext<'a, P: PacketSender> {
+    pub issuer: EntityId,
+    pub world: &'a mut World,
+    pub spawn_manager: &'a mut SpawnManager,
+    pub sender: &'a P,
+    pub data: &'a DataConfig,
+    pub messages: &'a MessageCatalog,
+}
+
+pub type CommandFn<P> = fn(&mut CommandContext<P>, &[&str]) -> Result<String, CommandError>;
+
+pub struct Command<P: PacketSender> {
+    pub name: &'static str,
+    pub usage: &'static str,
+    pub min_level: u32,
+    pub run: CommandFn<P>,
+}
+
+pub struct CommandRegistry<P: PacketSender> {
+    commands: Vec<Command<P>>,
+}
+
+impl<P: PacketSender> CommandRegistry<P> {
+    pub fn new() -> Self {
+        Self {
+            commands: Vec::new(),
+        }
+    }
+
+    pub fn register(
+        &mut self,
+        name: &'static str,
+        usage: &'static str,
+        min_level: u32,
+        run: CommandFn<P>,
+    ) {
+        self.commands.push(Command {
+            name,
+            usage,
+            min_level,
+            run,
+        });
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Command<P>> {
+        self.commands
+            .iter()
+            .find(|command| command.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn execute(
+        &self,
+        context: &mut CommandContext<P>,
+        line: &str,
+    ) -> Result<String, CommandError> {
+        let (issuer_name, level) = match context.world.get_mob(context.issuer) {
+            Some(Mob::Player(player)) => (
+                player.name.clone(),
+                player
+                    .access
+                    .as_ref()
+                    .map(AccessLevel::get_level)
+                    .unwrap_or(0),
+            ),
+            _ => return Err(CommandError::IssuerNotFound),
+        };
+
+        let result = self.execute_as(context, level, line);
+        match &result {
+            Ok(reply) => log::info!(
+                target: "audit",
+                "{} (level {}) executed \"{}\": {}",
+                issuer_name,
+                level,
+                line,
+                reply
+            ),
+            Err(err) => log::warn!(
+                target: "audit",
+                "{} (level {}) failed \"{}\": {}",
+                issuer_name,
+                level,
+                line,
+                err
+            ),
+        }
+        result
+    }
+
+    fn execute_as(
+        &self,
+        context: &mut CommandContext<P>,
+        level: u32,
+        line: &str,
+    ) -> Result<String, CommandError> {
+        let mut parts = line
+            .trim()
+            .trim_start_matches(COMMAND_PREFIX)
+            .split_whitespace();
+        let name = parts.next().ok_or(CommandError::Empty)?;
+        let args: Vec<&str> = parts.collect();
+
+        let command = self
+            .get(name)
+            .ok_or_else(|| CommandError::UnknownCommand(name.to_string()))?;
+        if level < command.min_level {
+            return Err(CommandError::PermissionDenied {
+                command: command.name,
+                required: command.min_level,
+            });
+        }
+
+        (command.run)(context, &args).map_err(|err| match err {
+            CommandError::Usage(_) => CommandError::Usage(command.usage),
+            err => err,
+        })
+    }
+
+    pub fn handle(&self, context: &mut CommandContext<P>, line: &str) -> Result<(), SessionError> {
+        let reply = match self.execute(context, line) {
+            Ok(reply) => reply,
+            Err(err) => err.localize(context.messages),
+        };
+        context
+            .sender
+            .send_to(context.issuer, MessagePanel::from(reply))
+    }
+}
+
+impl<P: PacketSender> Default for CommandRegistry<P> {
+    fn default() -> Self {
+        let mut registry = Self::new();
+        gm::register(&mut registry);
+        registry
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CommandError {
+    #[error("Empty command")]
+    Empty,
+
+    #[error("Unknown command: {0}")]
+    UnknownCommand(String),
+
+    #[error("/{command} requires access level {required}")]
+    PermissionDenied {
+        command: &'static str,
+        required: u32,
+    },
+
+    #[error("Usage: {0}")]
+    Usage(&'static str),
+
+    #[error("Player {0} is not connected")]
+    PlayerNotFound(String),
+
+    #[error("Mob {0} is not in the world")]
+    MobNotFound(usize),
+
+    #[error("Command issuer is not in the world")]
+    IssuerNotFound,
+
+    #[error("{0}")]
+    Failed(String),
+
+    #[error(transparent)]
+    Session(#[from] SessionError),
+}
+
+impl CommandError {
+    /// The text shown to the issuer. [`Display`](std::fmt::Display) stays in
+    /// English for the audit log.
+    pub fn localize(&self, messages: &MessageCatalog) -> String {
+        match self {
+            CommandError::Empty => messages.get(MessageKey::CommandEmpty).to_string(),
+            CommandError::UnknownCommand(name) => {
+                messages.format(MessageKey::CommandUnknown, &[("name", name)])
+            }
+            CommandError::PermissionDenied { command, required } => messages.format(
+                MessageKey::CommandPermissionDenied,
+                &[("command", command), ("level", required)],
+            ),
+            CommandError::Usage(usage) => {
+                messages.format(MessageKey::CommandUsage, &[("usage", usage)])
+            }
+            CommandError::PlayerNotFound(name) => {
+                messages.format(MessageKey::CommandPlayerNotFound, &[("name", name)])
+            }
+            CommandError::MobNotFound(id) => {
+                messages.format(MessageKey::CommandMobNotFound, &[("id", id)])
+            }
+            CommandError::IssuerNotFound => {
+                messages.get(MessageKey::CommandIssuerNotFound).to_string()
+            }
+            CommandError::Failed(reply) => reply.clone(),
+            CommandError::Session(err) => err.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::tests::MockPacketSender;
+    use crate::world::Player;
+    use odin_models::{character::Character, position::Position};
+    use odin_networking::messages::ServerMessage;
+
+    fn echo(
+        _context: &mut CommandContext<MockPacketSender>,
+        args: &[&str],
+    ) -> Result<String, CommandError> {
+        match args {
+            [] => Err(CommandError::Usage("")),
+            args => Ok(args.join(" ")),
+        }
+    }
+
+    fn setup(access: Option<AccessLevel>) -> (World, SpawnManager, EntityId) {
+        let mut world = World::default();
+        let entity_id = EntityId::Player(1);
+        let mut player = Player::from_character(
+            entity_id,
+            Character {
+                name: "Gm".to_string(),
+                ..Default::default()
+            },
+        );
+        player.access = access;
+        world
+            .add_player(entity_id, player, Position { x: 2100, y: 2100 })
+            .unwrap();
+        (world, SpawnManager::new(vec![]), entity_id)
+    }
+
+    fn registry() -> CommandRegistry<MockPacketSender> {
+        let mut registry = CommandRegistry::new();
+        registry.register("echo", "/echo <text>", 10, echo);
+        registry
+    }
+
+    #[test]
+    fn executes_a_registered_command() {
+        let (mut world, mut spawn_manager, issuer) = setup(Some(AccessLevel::GameMaster(10)));
+        let sender = MockPacketSender::default();
+        let mut context = CommandContext {
+            issuer,
+            world: &mut world,
+            spawn_manager: &mut spawn_manager,
+            sender: &sender,
+            data: &DataConfig::default(),
+            messages: MessageCatalog::builtin(),
+        };
+
+        assert_eq!(
+            registry().execute(&mut context, "/ECHO hello world"),
+            Ok("hello world".to_string())
+        );
+        assert_eq!(
+            registry().execute(&mut context, "/echo"),
+            Err(CommandError::Usage("/echo <text>"))
+        );
+        assert_eq!(
+            registry().execute(&mut context, "/unknown"),
+            Err(CommandError::UnknownCommand("unknown".to_string()))
+        );
+    }
+
+    #[test]
+    fn rejects_commands_above_the_access_level() {
+        for access in [None, Some(AccessLevel::GameMaster(9))] {
+            let (mut world, mut spawn_manager, issuer) = setup(access);
+            let sender = MockPacketSender::default();
+            let mut context = CommandContext {
+                issuer,
+                world: &mut world,
+                spawn_manager: &mut spawn_manager,
+                sender: &sender,
+                data: &DataConfig::default(),
+                messages: MessageCatalog::builtin(),
+            };
+
+            assert_eq!(
+                registry().execute(&mut context, "/echo hello"),
+                Err(CommandError::PermissionDenied {
+                    command: "echo",
+                    required: 10
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn errors_are_localized_for_the_issuer() {
+        let messages = MessageCatalog::builtin();
+
+        assert_eq!(
+            CommandError::UnknownCommand("fly".to_string()).localize(messages),
+            "Comando desconhecido: fly"
+        );
+        assert_eq!(
+            CommandError::PermissionDenied {
+                command: "coin",
+                required: 50
+            }
+            .localize(messages),
+            "/coin exige nível de acesso 50"
+        );
+        assert_eq!(
+            CommandError::Failed("Inventário cheio".to_string()).localize(messages),
+            "Inventário cheio"
+        );
+    }
+
+    #[test]
+    fn handle_replies_to_the_issuer() {
+        let (mut world, mut spawn_manager, issuer) = setup(Some(AccessLevel::Administrator));
+        let sender = MockPacketSender::default();
+        let mut context = CommandContext {
+            issuer,
+            world: &mut world,
+            spawn_manager: &mut spawn_manager,
+            sender: &sender,
+            data: &DataConfig::default(),
+            messages: MessageCatalog::builtin(),
+        };
+
+        registry().handle(&mut context, "/echo hi").unwrap();
+
+        let packets = sender.messages_for(issuer);
+        assert_eq!(packets.len(), 1);
+        assert_eq!(packets[0].identifier, ServerMessage::MessagePanel);
+    }
+}
@@ -1,6 +1,34 @@
+use crate::locale::{DEFAULT_LOCALE, MessageCatalog};
+use crate::npc::tick::TICK_INTERVAL_MS;
+use odin_networking::enc_session::KEYTABLE_LENGTH;
+use serde::Deserialize;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
 pub trait Configuration {
     fn get_current_cliver(&self) -> CliVer;
     fn get_server_state(&self) -> ServerState;
+
+    fn get_numeric_token_config(&self) -> NumericTokenConfig {
+        NumericTokenConfig::default()
+    }
+
+    fn get_ground_item_config(&self) -> GroundItemConfig {
+        GroundItemConfig::default()
+    }
+
+    fn get_merchant_config(&self) -> MerchantConfig {
+        MerchantConfig::default()
+    }
+
+    fn get_guild_config(&self) -> GuildConfig {
+        GuildConfig::default()
+    }
+
+    fn get_message_catalog(&self) -> &MessageCatalog {
+        MessageCatalog::builtin()
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -29,9 +57,377 @@ impl PartialEq<u32> for CliVer {
     }
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ServerState {
     Open,
     #[default]
     Maintenance,
 }
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ServerConfig {
+    pub cliver: u32,
+    pub state: ServerState,
+    pub max_clients: usize,
+    pub keytable: Option<PathBuf>,
+    pub tick_interval_ms: u64,
+    pub autosave_interval_secs: u64,
+    pub admin_addr: Option<SocketAddr>,
+    /// Locale of the texts sent to players, loaded from `data.locales`
+    pub locale: String,
+    pub numeric_token: NumericTokenConfig,
+    pub ground_items: GroundItemConfig,
+    pub merchant: MerchantConfig,
+    pub guild: GuildConfig,
+    pub data: DataConfig,
+}
+impl ServerConfig {
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let contents = std::fs::read_to_string(path)?;
+        let config = Self::from_toml(&contents).map_err(|source| ConfigError::TomlParse {
+            file: path.display().to_string(),
+            source,
+        })?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Rejects values the server can't start with, e.g. zero intervals
+    /// make the tick and autosave timers panic.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.tick_interval_ms == 0 {
+            return Err(ConfigError::ZeroInterval("tick_interval_ms"));
+        }
+        if self.autosave_interval_secs == 0 {
+            return Err(ConfigError::ZeroInterval("autosave_interval_secs"));
+        }
+        Ok(())
+    }
+
+    pub fn from_toml(contents: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(contents)
+    }
+
+    pub fn cliver(&self) -> CliVer {
+        CliVer::new(self.cliver)
+    }
+
+    pub fn tick_interval(&self) -> Duration {
+        Duration::from_millis(self.tick_interval_ms)
+    }
+
+    pub fn autosave_interval(&self) -> Duration {
+        Duration::from_secs(self.autosave_interval_secs)
+    }
+
+    pub fn load_keytable(&self) -> Result<[u8; KEYTABLE_LENGTH], ConfigError> {
+        let Some(path) = &self.keytable else {
+            return Ok(DEFAULT_KEYTABLE);
+        };
+
+        let bytes = std::fs::read(path)?;
+        bytes
+            .try_into()
+            .map_err(|bytes: Vec<u8>| ConfigError::InvalidKeytableSize(bytes.len()))
+    }
+}
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            cliver: 11022,
+            state: ServerState::Maintenance,
+            max_clients: 750,
+            keytable: None,
+            tick_interval_ms: TICK_INTERVAL_MS,
+            autosave_interval_secs: 300,
+            admin_addr: None,
+            locale: DEFAULT_LOCALE.to_string(),
+            numeric_token: NumericTokenConfig::default(),
+            ground_items: GroundItemConfig::default(),
+            merchant: MerchantConfig::default(),
+            guild: GuildConfig::default(),
+            data: DataConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct NumericTokenConfig {
+    pub max_attempts: u32,
+    pub lockout_secs: u64,
+}
+impl NumericTokenConfig {
+    pub fn lockout(&self) -> Duration {
+        Duration::from_secs(self.lockout_secs)
+    }
+}
+impl Default for NumericTokenConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            lockout_secs: 600,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct GroundItemConfig {
+    pub decay_secs: u64,
+    pub owner_grace_secs: u64,
+}
+impl GroundItemConfig {
+    pub fn decay(&self) -> Duration {
+        Duration::from_secs(self.decay_secs)
+    }
+
+    pub fn owner_grace(&self) -> Duration {
+        Duration::from_secs(self.owner_grace_secs)
+    }
+}
+impl Default for GroundItemConfig {
+    fn default() -> Self {
+        Self {
+            decay_secs: 120,
+            owner_grace_secs: 30,
+        }
+    }
+}
+
+/// Percentages applied to `ItemData::price`: `tax` is added when buying from
+/// an NPC and `sell_ratio` is what the NPC pays back for an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct MerchantConfig {
+    pub tax: u32,
+    pub sell_ratio: u32,
+}
+impl Default for MerchantConfig {
+    fn default() -> Self {
+        Self {
+            tax: 0,
+            sell_ratio: 25,
+        }
+    }
+}
+
+/// `creation_cost` is the coin taken from the leader when a guild is created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct GuildConfig {
+    pub creation_cost: u32,
+}
+impl Default for GuildConfig {
+    fn default() -> Self {
+        Self {
+            creation_cost: 100_000_000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct DataConfig {
+    pub item_list: PathBuf,
+    pub mobs: PathBuf,
+    pub spawns: PathBuf,
+    pub experience: PathBuf,
+    pub skills: PathBuf,
+    pub locales: PathBuf,
+}
+impl Default for DataConfig {
+    fn default() -> Self {
+        Self {
+            item_list: PathBuf::from("ItemList.csv"),
+            mobs: PathBuf::from("data/mobs"),
+            spawns: PathBuf::from("data/spawns"),
+            experience: PathBuf::from("data/experience.toml"),
+            skills: PathBuf::from("data/skills.toml"),
+            locales: PathBuf::from("locales"),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("TOML parse error in {file}: {source}")]
+    TomlParse {
+        file: String,
+        source: toml::de::Error,
+    },
+    #[error("Keytable must have {KEYTABLE_LENGTH} bytes, got {0}")]
+    InvalidKeytableSize(usize),
+    #[error("{0} must be greater than zero")]
+    ZeroInterval(&'static str),
+}
+
+pub const DEFAULT_KEYTABLE: [u8; KEYTABLE_LENGTH] = [
+    0x14, 0x17, 0x47, 0x67, 0x7A, 0x09, 0x21, 0x0D, 0x5B, 0x5B, 0x15, 0x0D, 0x17, 0x11, 0x21, 0x0C,
+    0x1F, 0x03, 0x21, 0x21, 0x17, 0x0D, 0x1D, 0x0D, 0x16, 0x1F, 0x03, 0x1F, 0x71, 0x6D, 0x15, 0x0D,
+    0x15, 0x0D, 0x15, 0x13, 0x17, 0x2C, 0x15, 0x43, 0x1D, 0x72, 0x17, 0x29, 0x1F, 0x09, 0x15, 0x16,
+    0x47, 0x0D, 0x67, 0x6D, 0x79, 0x0D, 0x67, 0x0D, 0x15, 0x09, 0x15, 0x0D, 0x1F, 0x71, 0x17, 0x0E,
+    0x33, 0x17, 0x05, 0x09, 0x6F, 0x73, 0x5B, 0x13, 0x33, 0x32, 0x3E, 0x1E, 0x24, 0x0D, 0x6E, 0x0E,
+    0x15, 0x0A, 0x15, 0x3F, 0x5D, 0x0D, 0x17, 0x35, 0x17, 0x0D, 0x71, 0x0D, 0x18, 0x0D, 0x25, 0x21,
+    0x33, 0x0D, 0x17, 0x0C, 0x1D, 0x0A, 0x15, 0x17, 0x27, 0x0C, 0x15, 0x0D, 0x3C, 0x10, 0x4B, 0x09,
+    0x14, 0x2B, 0x6B, 0x35, 0x67, 0x1F, 0x15, 0x1F, 0x15, 0x0E, 0x15, 0x10, 0x15, 0x28, 0x05, 0x2D,
+    0x33, 0x2A, 0x1D, 0x29, 0x17, 0x0C, 0x15, 0x0D, 0x14, 0x0D, 0x15, 0x0E, 0x77, 0x27, 0x1D, 0x1F,
+    0x15, 0x0B, 0x7A, 0x0D, 0x3D, 0x10, 0x3D, 0x0D, 0x47, 0x3F, 0x1D, 0x0D, 0x79, 0x4D, 0x15, 0x0D,
+    0x17, 0x47, 0x33, 0x0D, 0x77, 0x47, 0x33, 0x1C, 0x17, 0x0E, 0x15, 0x35, 0x0D, 0x06, 0x45, 0x49,
+    0x1D, 0x7F, 0x33, 0x0D, 0x17, 0x2B, 0x15, 0x1C, 0x71, 0x31, 0x1D, 0x0F, 0x17, 0x0D, 0x14, 0x0A,
+    0x14, 0x0B, 0x71, 0x16, 0x78, 0x7F, 0x61, 0x09, 0x15, 0x29, 0x63, 0x25, 0x53, 0x57, 0x29, 0x0D,
+    0x77, 0x1C, 0x47, 0x0C, 0x33, 0x0D, 0x15, 0x0D, 0x5B, 0x09, 0x31, 0x35, 0x17, 0x0D, 0x29, 0x0D,
+    0x1D, 0x0D, 0x25, 0x21, 0x33, 0x0D, 0x17, 0x0C, 0x15, 0x0A, 0x15, 0x3F, 0x5D, 0x0D, 0x17, 0x0D,
+    0x79, 0x4D, 0x15, 0x0D, 0x25, 0x09, 0x15, 0x0D, 0x51, 0x0B, 0x7A, 0x0D, 0x47, 0x0D, 0x15, 0x0D,
+    0x15, 0x0D, 0x1D, 0x0D, 0x79, 0x03, 0x15, 0x09, 0x15, 0x0D, 0x67, 0x0D, 0x15, 0x71, 0x49, 0x71,
+    0x1F, 0x75, 0x15, 0x16, 0x3D, 0x0D, 0x67, 0x6D, 0x33, 0x1E, 0x76, 0x0D, 0x6E, 0x0E, 0x3E, 0x1E,
+    0x1F, 0x71, 0x19, 0x0E, 0x33, 0x0D, 0x05, 0x09, 0x33, 0x71, 0x5B, 0x13, 0x1C, 0x1F, 0x15, 0x0B,
+    0x15, 0x0E, 0x1F, 0x10, 0x15, 0x28, 0x05, 0x0A, 0x15, 0x2A, 0x1D, 0x71, 0x1F, 0x0C, 0x19, 0x1C,
+    0x15, 0x1B, 0x33, 0x79, 0x17, 0x0B, 0x33, 0x1C, 0x2F, 0x47, 0x31, 0x0A, 0x18, 0x0E, 0x1F, 0x35,
+    0x0D, 0x10, 0x47, 0x49, 0x28, 0x4F, 0x5B, 0x29, 0x15, 0x35, 0x21, 0x10, 0x17, 0x11, 0x17, 0x0C,
+    0x1F, 0x03, 0x21, 0x21, 0x14, 0x17, 0x47, 0x67, 0x16, 0x09, 0x71, 0x6D, 0x15, 0x0A, 0x03, 0x2B,
+    0x15, 0x0D, 0x1D, 0x13, 0x17, 0x2C, 0x15, 0x43, 0x17, 0x0D, 0x15, 0x1F, 0x17, 0x0D, 0x1D, 0x0D,
+    0x06, 0x0E, 0x17, 0x0D, 0x18, 0x29, 0x19, 0x05, 0x61, 0x6D, 0x15, 0x0D, 0x1B, 0x53, 0x7A, 0x0A,
+    0x67, 0x40, 0x1D, 0x0D, 0x17, 0x35, 0x17, 0x0C, 0x03, 0x0E, 0x0D, 0x16, 0x17, 0x33, 0x15, 0x20,
+    0x67, 0x6F, 0x7D, 0x35, 0x71, 0x0A, 0x15, 0x33, 0x7A, 0x0E, 0x15, 0x28, 0x3D, 0x09, 0x16, 0x0D,
+    0x15, 0x0D, 0x67, 0x0D, 0x71, 0x0A, 0x05, 0x0D, 0x15, 0x40, 0x3B, 0x47, 0x71, 0x0A, 0x17, 0x09,
+    0x14, 0x0D, 0x03, 0x03, 0x17, 0x0D, 0x33, 0x0D, 0x79, 0x0D, 0x15, 0x0E, 0x12, 0x0D, 0x6D, 0x3D,
+    0x17, 0x09, 0x77, 0x09, 0x3D, 0x0C, 0x33, 0x6A, 0x17, 0x1D, 0x1D, 0x0B, 0x77, 0x09, 0x2B, 0x0D,
+    0x67, 0x1F, 0x15, 0x0D, 0x1D, 0x44, 0x1F, 0x0D, 0x3D, 0x17, 0x79, 0x0C, 0x15, 0x10, 0x15, 0x09,
+    0x1A, 0x53, 0x77, 0x35, 0x78, 0x7B, 0x1D, 0x04, 0x20, 0x03, 0x43, 0x27, 0x1D, 0x47, 0x31, 0x29,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_config_uses_defaults() {
+        let config = ServerConfig::from_toml("").unwrap();
+        assert_eq!(config.cliver(), CliVer::new(11022));
+        assert_eq!(config.state, ServerState::Maintenance);
+        assert_eq!(config.max_clients, 750);
+        assert_eq!(config.tick_interval(), Duration::from_millis(500));
+        assert_eq!(config.autosave_interval(), Duration::from_secs(300));
+        assert_eq!(config.admin_addr, None);
+        assert_eq!(config.locale, "pt-BR");
+        assert_eq!(config.numeric_token, NumericTokenConfig::default());
+        assert_eq!(config.ground_items, GroundItemConfig::default());
+        assert_eq!(config.merchant, MerchantConfig::default());
+        assert_eq!(config.guild, GuildConfig::default());
+        assert_eq!(config.data.item_list, PathBuf::from("ItemList.csv"));
+        assert_eq!(config.data.mobs, PathBuf::from("data/mobs"));
+        assert_eq!(config.data.spawns, PathBuf::from("data/spawns"));
+        assert_eq!(
+            config.data.experience,
+            PathBuf::from("data/experience.toml")
+        );
+    }
+
+    #[test]
+    fn parse_full_config() {
+        let toml_str = r#"
+            cliver = 759
+            state = "open"
+            max_clients = 100
+            keytable = "keys.bin"
+            tick_interval_ms = 250
+            autosave_interval_secs = 60
+            admin_addr = "127.0.0.1:8282"
+            locale = "en-US"
+
+            [numeric_token]
+            max_attempts = 5
+            lockout_secs = 60
+
+            [ground_items]
+            decay_secs = 60
+            owner_grace_secs = 10
+
+            [merchant]
+            tax = 10
+            sell_ratio = 50
+
+            [guild]
+            creation_cost = 5000
+
+            [data]
+            item_list = "res/ItemList.csv"
+            mobs = "res/mobs"
+            spawns = "res/spawns"
+            experience = "res/experience.toml"
+        "#;
+        let config = ServerConfig::from_toml(toml_str).unwrap();
+        assert_eq!(config.cliver(), CliVer::new(759));
+        assert_eq!(config.state, ServerState::Open);
+        assert_eq!(config.max_clients, 100);
+        assert_eq!(config.keytable, Some(PathBuf::from("keys.bin")));
+        assert_eq!(config.tick_interval(), Duration::from_millis(250));
+        assert_eq!(config.autosave_interval(), Duration::from_secs(60));
+        assert_eq!(config.admin_addr, Some("127.0.0.1:8282".parse().unwrap()));
+        assert_eq!(config.locale, "en-US");
+        assert_eq!(config.numeric_token.max_attempts, 5);
+        assert_eq!(config.numeric_token.lockout(), Duration::from_secs(60));
+        assert_eq!(config.ground_items.decay(), Duration::from_secs(60));
+        assert_eq!(config.ground_items.owner_grace(), Duration::from_secs(10));
+        assert_eq!(
+            config.merchant,
+            MerchantConfig {
+                tax: 10,
+                sell_ratio: 50
+            }
+        );
+        assert_eq!(config.guild.creation_cost, 5000);
+        assert_eq!(config.data.item_list, PathBuf::from("res/ItemList.csv"));
+        assert_eq!(config.data.mobs, PathBuf::from("res/mobs"));
+        assert_eq!(config.data.spawns, PathBuf::from("res/spawns"));
+        assert_eq!(config.data.experience, PathBuf::from("res/experience.toml"));
+    }
+
+    #[test]
+    fn parse_invalid_state_errors() {
+        assert!(ServerConfig::from_toml(r#"state = "closed""#).is_err());
+    }
+
+    #[test]
+    fn zero_intervals_are_rejected() {
+        for (contents, field) in [
+            ("tick_interval_ms = 0", "tick_interval_ms"),
+            ("autosave_interval_secs = 0", "autosave_interval_secs"),
+        ] {
+            let path = std::env::temp_dir()
+                .join(format!("odin-config-{}-{field}.toml", std::process::id()));
+            std::fs::write(&path, contents).unwrap();
+
+            let result = ServerConfig::load(&path);
+            std::fs::remove_file(&path).unwrap();
+
+            assert!(matches!(result, Err(ConfigError::ZeroInterval(name)) if name == field));
+        }
+    }
+
+    #[test]
+    fn missing_keytable_uses_default() {
+        let config = ServerConfig::default();
+        assert_eq!(config.load_keytable().unwrap(), DEFAULT_KEYTABLE);
+    }
+
+    #[test]
+    fn keytable_with_wrong_size_errors() {
+        let path = std::env::temp_dir().join(format!("odin-keytable-{}.bin", std::process::id()));
+        std::fs::write(&path, [0u8; 16]).unwrap();
+        let config = ServerConfig {
+            keytable: Some(path.clone()),
+            ..Default::default()
+        };
+
+        let result = config.load_keytable();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(result, Err(ConfigError::InvalidKeytableSize(16))));
+    }
+}
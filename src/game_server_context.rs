@@ -1,35 +1,71 @@
 use crate::{
     client_id_manager::{ClientIdManager, ClientIdManagerError},
-    configuration::{CliVer, Configuration, ServerState},
+    configuration::{
+        CliVer, Configuration, DataConfig, GroundItemConfig, GuildConfig, MerchantConfig,
+        NumericTokenConfig, ServerConfig, ServerState,
+    },
+    handlers::gameplay::{party, trade},
+    locale::MessageCatalog,
     map::EntityId,
+    party::PartyError,
+    persistence,
     session::{PacketSender, SessionError, SessionTrait},
     user_session::{SenderSession, UserSession},
+    world::World,
 };
-use odin_networking::WritableResource;
-use odin_repositories::account_repository::AccountRepository;
+use odin_networking::{WritableResource, messages::server::remove_mob::RemoveMob};
+use odin_repositories::{account_repository::AccountRepository, guild_repository::GuildRepository};
 use std::collections::HashMap;
+use tokio::task::AbortHandle;
 
-pub struct GameServerContext<A: AccountRepository> {
+pub struct GameServerContext<A: AccountRepository, G: GuildRepository> {
     sessions: HashMap<usize, UserSession>,
     senders: HashMap<usize, SenderSession>,
+    connections: HashMap<usize, AbortHandle>,
     client_id_manager: ClientIdManager,
     current_cliver: CliVer,
+    server_state: ServerState,
+    numeric_token_config: NumericTokenConfig,
+    ground_item_config: GroundItemConfig,
+    merchant_config: MerchantConfig,
+    guild_config: GuildConfig,
+    message_catalog: MessageCatalog,
+    data_config: DataConfig,
     pub account_repository: A,
+    pub guild_repository: G,
 }
-impl<A> GameServerContext<A>
+impl<A, G> GameServerContext<A, G>
 where
     A: AccountRepository,
+    G: GuildRepository,
 {
-    pub fn new(client_id_manager: ClientIdManager, account_repository: A) -> Self {
+    pub fn new(account_repository: A, guild_repository: G, config: &ServerConfig) -> Self {
         Self {
             sessions: Default::default(),
             senders: Default::default(),
-            client_id_manager,
-            current_cliver: CliVer::new(11022),
+            connections: Default::default(),
+            client_id_manager: ClientIdManager::with_maximum(config.max_clients),
+            current_cliver: config.cliver(),
+            server_state: config.state,
+            numeric_token_config: config.numeric_token,
+            ground_item_config: config.ground_items,
+            merchant_config: config.merchant,
+            guild_config: config.guild,
+            message_catalog: MessageCatalog::default(),
+            data_config: config.data.clone(),
             account_repository,
+            guild_repository,
         }
     }
 
+    pub fn data_config(&self) -> &DataConfig {
+        &self.data_config
+    }
+
+    pub fn set_message_catalog(&mut self, message_catalog: MessageCatalog) {
+        self.message_catalog = message_catalog;
+    }
+
     pub fn allocate_client_id(&mut self) -> Option<usize> {
         self.client_id_manager.add()
     }
@@ -50,28 +86,106 @@ where
         self.senders.insert(client_id, sender);
     }
 
+    pub fn add_connection(&mut self, client_id: usize, reader: AbortHandle) {
+        self.connections.insert(client_id, reader);
+    }
+
+    pub fn client_ids(&self) -> Vec<usize> {
+        let mut ids: Vec<usize> = self.senders.keys().copied().collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    pub fn set_current_cliver(&mut self, cliver: CliVer) {
+        self.current_cliver = cliver;
+    }
+
+    pub fn set_server_state(&mut self, state: ServerState) {
+        self.server_state = state;
+    }
+
     pub fn disconnect(&mut self, client_id: usize) -> Result<(), ClientIdManagerError> {
         self.sessions.remove(&client_id);
         self.senders.remove(&client_id);
+        if let Some(reader) = self.connections.remove(&client_id) {
+            reader.abort();
+        }
         self.client_id_manager.remove(client_id)
     }
+
+    pub async fn disconnect_player(
+        &mut self,
+        client_id: usize,
+        world: &mut World,
+    ) -> Result<(), ClientIdManagerError> {
+        let entity_id = EntityId::Player(client_id);
+        if world.entity_exists(entity_id)
+            && let Err(e) =
+                persistence::save_player(world, entity_id, &self.account_repository).await
+        {
+            log::error!("Failed to save ClientId {} on logout: {e}", client_id);
+        }
+
+        match party::leave_party(world, entity_id, self) {
+            Ok(()) | Err(PartyError::NotInParty) => {}
+            Err(e) => log::warn!("Failed to leave party for ClientId {}: {e}", client_id),
+        }
+        if let Err(e) = trade::cancel_trade(world, entity_id, self) {
+            log::warn!("Failed to cancel trade for ClientId {}: {e}", client_id);
+        }
+
+        if let Ok(result) = world.remove_entity(entity_id) {
+            for spectator in &result.spectators {
+                let _ = self.send_to(
+                    *spectator,
+                    RemoveMob {
+                        mob_id: client_id as u16,
+                        remove_type: 1,
+                    },
+                );
+            }
+        }
+        self.disconnect(client_id)
+    }
 }
-impl<A> Configuration for GameServerContext<A>
+impl<A, G> Configuration for GameServerContext<A, G>
 where
     A: AccountRepository,
+    G: GuildRepository,
 {
     fn get_current_cliver(&self) -> CliVer {
         self.current_cliver
     }
 
     fn get_server_state(&self) -> ServerState {
-        ServerState::Maintenance
+        self.server_state
+    }
+
+    fn get_numeric_token_config(&self) -> NumericTokenConfig {
+        self.numeric_token_config
+    }
+
+    fn get_ground_item_config(&self) -> GroundItemConfig {
+        self.ground_item_config
+    }
+
+    fn get_merchant_config(&self) -> MerchantConfig {
+        self.merchant_config
+    }
+
+    fn get_guild_config(&self) -> GuildConfig {
+        self.guild_config
+    }
+
+    fn get_message_catalog(&self) -> &MessageCatalog {
+        &self.message_catalog
     }
 }
 
-impl<A> PacketSender for GameServerContext<A>
+impl<A, G> PacketSender for GameServerContext<A, G>
 where
     A: AccountRepository,
+    G: GuildRepository,
 {
     fn send_to<W: WritableResource>(
         &self,
@@ -1,5 +1,6 @@
+use crate::handlers::gameplay::{stall::close_stall, trade::cancel_trade_out_of_range};
 use crate::map::EntityId;
-use crate::packets::ToCreateMob;
+use crate::packets::{ToCreateMob, ToGroundItemPackets};
 use crate::session::{PacketSender, SessionError};
 use crate::world::{Mob, World};
 use odin_models::position::Position;
@@ -12,6 +13,7 @@ use odin_networking::{
                 ActionBroadcastData, ActionIllusionBroadcast, ActionStopBroadcast,
                 ActionWalkBroadcast,
             },
+            party::PartyMemberPosition,
             remove_mob::RemoveMob,
         },
     },
@@ -43,8 +45,13 @@ impl Action {
         sender: &P,
         action_type: ActionType,
     ) -> Result<(), ActionError> {
-        if world.get_mob(entity_id).is_none() {
+        let Some(mob) = world.get_mob(entity_id) else {
             return Err(ActionError::EntityNotFound);
+        };
+        let invisible = mob.is_invisible();
+        // Personal shops stay open only while their owner stays in place
+        if world.map().get_position(entity_id) != Some(self.destiny) {
+            close_stall(world, entity_id, sender)?;
         }
 
         let move_result = world.force_move_entity(entity_id, self.destiny)?;
@@ -57,7 +64,8 @@ impl Action {
             destiny: move_result.to,
         };
 
-        for spectator in move_result.stayed.iter().chain(move_result.entered.iter()) {
+        let spectators = move_result.stayed.iter().chain(move_result.entered.iter());
+        for spectator in spectators.filter(|_| !invisible) {
             match action_type {
                 ActionType::Walk => sender.send_to(*spectator, ActionWalkBroadcast(data))?,
                 ActionType::Illusion => {
@@ -79,24 +87,50 @@ impl Action {
                 .get_position(*entered)
                 .expect("spectator from map must have a position");
 
-            sender.send_to(*entered, my_create_mob.clone())?;
-            sender.send_to(entity_id, spectator.to_create_mob(spectator_pos))?;
+            if !invisible {
+                sender.send_to(*entered, my_create_mob.clone())?;
+            }
+            if !spectator.is_invisible() {
+                spectator.send_create_mob(entity_id, spectator_pos, sender)?;
+            }
         }
 
         for exited in &move_result.exited {
+            if !invisible {
+                sender.send_to(
+                    *exited,
+                    RemoveMob {
+                        mob_id: entity_id.id() as u16,
+                        remove_type: 0,
+                    },
+                )?;
+            }
+
             sender.send_to(
-                *exited,
+                entity_id,
                 RemoveMob {
-                    mob_id: entity_id.id() as u16,
+                    mob_id: exited.id() as u16,
                     remove_type: 0,
                 },
             )?;
+        }
+
+        if let EntityId::Player(_) = entity_id {
+            let map = world.map();
+            for ground_item in map.ground_items_entering_view(move_result.from, move_result.to) {
+                sender.send_to(entity_id, ground_item.to_create_ground_item())?;
+            }
+            for ground_item in map.ground_items_entering_view(move_result.to, move_result.from) {
+                sender.send_to(entity_id, ground_item.to_remove_ground_item())?;
+            }
+        }
 
+        for member in world.party_members_out_of_view(entity_id) {
             sender.send_to(
-                entity_id,
-                RemoveMob {
-                    mob_id: exited.id() as u16,
-                    remove_type: 0,
+                member,
+                PartyMemberPosition {
+                    member_id: entity_id.id() as u16,
+                    position: move_result.to,
                 },
             )?;
         }
@@ -104,11 +138,51 @@ impl Action {
         if let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) {
             player.last_pos = move_result.to;
         }
+        cancel_trade_out_of_range(world, entity_id, sender)?;
 
         Ok(())
     }
 }
 
+/// Instantly moves an entity, running the same create/remove visibility flow
+/// as walking and echoing the move back so the client snaps to the destiny.
+pub fn teleport<P: PacketSender>(
+    world: &mut World,
+    sender: &P,
+    entity_id: EntityId,
+    destiny: Position,
+) -> Result<Position, ActionError> {
+    let last_pos = world
+        .map()
+        .get_position(entity_id)
+        .ok_or(ActionError::EntityNotFound)?;
+    let action = Action {
+        last_pos,
+        move_type: 1,
+        move_speed: 0,
+        command: [0; 24],
+        destiny,
+    };
+    action.handle(entity_id, world, sender, ActionType::Walk)?;
+
+    let position = world
+        .map()
+        .get_position(entity_id)
+        .ok_or(ActionError::EntityNotFound)?;
+    sender.send_to(
+        entity_id,
+        ActionWalkBroadcast(ActionBroadcastData {
+            mover_id: entity_id.id() as u16,
+            last_pos,
+            move_type: action.move_type,
+            move_speed: action.move_speed,
+            route: ActionBroadcastData::route_from_bytes(action.command),
+            destiny: position,
+        }),
+    )?;
+    Ok(position)
+}
+
 impl TryFrom<ActionRaw> for Action {
     type Error = WritableResourceError;
 
@@ -335,6 +409,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn handle_shows_ground_items_entering_view() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let mover = add_player(&mut world, 1, Position { x: 2100, y: 2100 });
+        world
+            .map_mut()
+            .drop_item(
+                odin_models::item::Item::from(1u16),
+                Position { x: 2500, y: 2500 },
+                None,
+                std::time::Instant::now(),
+            )
+            .unwrap();
+
+        let action = make_action(Position { x: 2495, y: 2495 });
+        action
+            .handle(mover, &mut world, &sender, ActionType::Walk)
+            .unwrap();
+
+        assert!(
+            sender
+                .messages_for(mover)
+                .iter()
+                .any(|m| m.identifier == ServerMessage::CreateGroundItem),
+            "mover should see ground items entering view"
+        );
+    }
+
     #[test]
     fn handle_updates_last_pos() {
         let mut world = World::default();
@@ -0,0 +1,722 @@
+use crate::handlers::gameplay::{stall::close_stall, trade::cancel_trade};
+use crate::map::EntityId;
+use crate::npc::ai;
+use crate::packets::BroadcastUpdateScore;
+use crate::score::damage::{AttackKind, DamageCalculator, DamageOutcome};
+use crate::session::{PacketSender, SessionError};
+use crate::skill::{SkillEffect, SkillTarget};
+use crate::world::{Mob, World};
+use odin_models::affect::{Affect, AffectSource};
+use odin_models::position::Position;
+use odin_networking::{
+    WritableResourceError,
+    messages::{
+        client::attack::{AttackRaw, MAX_ATTACK_TARGETS},
+        server::{
+            attack::{Attack as AttackBroadcast, Damage},
+            remove_mob::{REMOVE_TYPE_DEATH, RemoveMob},
+        },
+    },
+};
+use rand::Rng;
+use std::time::Instant;
+
+pub const MELEE_RANGE: u16 = 4;
+
+#[derive(Debug)]
+pub struct Attack {
+    pub skill_index: i16,
+    pub motion: i8,
+    pub skill_parm: i8,
+    pub target_position: Position,
+    pub targets: Vec<EntityId>,
+}
+
+impl Attack {
+    /// Resolves the attack, or the skill cast when `skill_index` is set, and
+    /// returns the NPCs that died, which the caller must release back to the
+    /// spawn manager.
+    pub fn handle<P: PacketSender>(
+        &self,
+        entity_id: EntityId,
+        world: &mut World,
+        sender: &P,
+        rng: &mut impl Rng,
+        now: Instant,
+    ) -> Result<Vec<EntityId>, AttackError> {
+        let Some(attacker) = world.get_mob(entity_id) else {
+            return Err(AttackError::AttackerNotFound);
+        };
+        let attacker_computed = match attacker {
+            Mob::Player(player) => player.computed.clone(),
+            Mob::Npc(npc) => npc.computed.clone(),
+        };
+        if attacker_computed.score.hp == 0 {
+            return Err(AttackError::AttackerDead);
+        }
+        let attacker_is_player = matches!(attacker, Mob::Player(_));
+        let position = world
+            .map()
+            .get_position(entity_id)
+            .ok_or(AttackError::AttackerNotFound)?;
+
+        let skill = if self.skill_index < 0 {
+            None
+        } else {
+            let skill = u8::try_from(self.skill_index)
+                .ok()
+                .and_then(|id| world.skills().get(id))
+                .cloned()
+                .ok_or(AttackError::UnknownSkill(self.skill_index))?;
+            if let Mob::Player(player) = attacker {
+                if !player.learned_skills.knows(skill.id) {
+                    return Err(AttackError::SkillNotLearned);
+                }
+                if player
+                    .skill_cooldowns
+                    .get(&skill.id)
+                    .is_some_and(|ready_at| now < *ready_at)
+                {
+                    return Err(AttackError::OnCooldown);
+                }
+            }
+            if attacker_computed.score.mp < skill.mana_cost(attacker_computed.save_mana) {
+                return Err(AttackError::NotEnoughMana);
+            }
+            Some(skill)
+        };
+
+        let (range, target) = skill
+            .as_ref()
+            .map_or((MELEE_RANGE, SkillTarget::Enemy), |skill| {
+                (skill.range, skill.target)
+            });
+        let targets = match target {
+            SkillTarget::Caster => vec![entity_id],
+            SkillTarget::Enemy | SkillTarget::Ally => {
+                self.targets_in_range(world, entity_id, position, range, target)
+            }
+        };
+        if targets.is_empty() {
+            return Err(AttackError::NoValidTarget);
+        }
+
+        let mut current_mp = attacker_computed.score.mp;
+        if let Some(skill) = &skill {
+            let cost = skill.mana_cost(attacker_computed.save_mana);
+            let computed = match world.get_mob_mut(entity_id) {
+                Some(Mob::Player(player)) => {
+                    player
+                        .skill_cooldowns
+                        .insert(skill.id, now + skill.cooldown());
+                    &mut player.computed
+                }
+                Some(Mob::Npc(npc)) => &mut npc.computed,
+                None => return Err(AttackError::AttackerNotFound),
+            };
+            computed.score.mp = computed.score.mp.saturating_sub(cost);
+            current_mp = computed.score.mp;
+        }
+
+        let level = attacker_computed.score.level;
+        let effect = skill.as_ref().map(|skill| skill.effect);
+        let mut damages = Vec::new();
+        let mut double_critical = false;
+        for &target in &targets {
+            let damage = match effect {
+                None | Some(SkillEffect::Damage { .. }) => {
+                    let kind = match effect {
+                        Some(effect @ SkillEffect::Damage { element, .. }) => AttackKind::Skill {
+                            power: effect.power(level),
+                            element,
+                        },
+                        _ => AttackKind::Melee,
+                    };
+                    let (computed, pvp) = match world.get_mob_mut(target) {
+                        Some(Mob::Player(player)) => (&mut player.computed, attacker_is_player),
+                        Some(Mob::Npc(npc)) => (&mut npc.computed, false),
+                        None => continue,
+                    };
+                    let outcome = DamageCalculator {
+                        attacker: &attacker_computed,
+                        defender: computed,
+                        pvp,
+                    }
+                    .calculate(kind, rng);
+                    computed.score.hp = computed.score.hp.saturating_sub(outcome.damage());
+                    double_critical |= outcome.is_critical();
+                    match outcome {
+                        DamageOutcome::Miss => 0,
+                        DamageOutcome::Hit { damage, .. } => damage as i32,
+                    }
+                }
+                Some(effect @ SkillEffect::Heal { .. }) => {
+                    let amount = effect.power(level);
+                    let Some(Mob::Player(player)) = world.get_mob_mut(target) else {
+                        continue;
+                    };
+                    let score = &mut player.computed.score;
+                    score.hp = score.hp.saturating_add(amount).min(score.max_hp);
+                    -(amount as i32)
+                }
+                Some(SkillEffect::Affect {
+                    affect,
+                    value,
+                    ticks,
+                }) => {
+                    let affect = Affect {
+                        id: affect,
+                        value,
+                        remaining_ticks: ticks,
+                        source: AffectSource::Skill(self.skill_index as u16),
+                    };
+                    world.add_affect(target, affect, sender)?;
+                    0
+                }
+            };
+            damages.push((target, damage));
+        }
+
+        let broadcast = || AttackBroadcast {
+            attacker_id: entity_id.id() as u16,
+            position,
+            target_position: self.target_position,
+            skill_index: self.skill_index,
+            current_mp: current_mp as i16,
+            motion: self.motion,
+            skill_parm: self.skill_parm,
+            double_critical,
+            current_exp: 0,
+            damages: damages
+                .iter()
+                .map(|(target, damage)| Damage {
+                    target_id: target.id() as u16,
+                    damage: *damage,
+                })
+                .collect(),
+        };
+        sender.send_to(entity_id, broadcast())?;
+        for spectator in world.map().get_spectators(position, entity_id) {
+            sender.send_to(spectator, broadcast())?;
+        }
+
+        if skill.is_some() && !targets.contains(&entity_id) {
+            world.broadcast_update_score(entity_id, sender)?;
+        }
+        if matches!(effect, Some(SkillEffect::Affect { .. })) {
+            return Ok(Vec::new());
+        }
+
+        let mut killed = Vec::new();
+        for (target, _) in &damages {
+            world.broadcast_update_score(*target, sender)?;
+            if matches!(effect, Some(SkillEffect::Heal { .. })) {
+                continue;
+            }
+            ai::engage(world, *target, entity_id);
+
+            let dead = match world.get_mob(*target) {
+                Some(Mob::Player(player)) => player.computed.score.hp == 0,
+                Some(Mob::Npc(npc)) => npc.computed.score.hp == 0,
+                None => false,
+            };
+            if dead && on_mob_killed(world, entity_id, *target, sender, rng, now)? {
+                killed.push(*target);
+            }
+        }
+
+        Ok(killed)
+    }
+
+    /// Living requested targets within `range`. Enemies exclude the attacker
+    /// itself, allies are restricted to players.
+    fn targets_in_range(
+        &self,
+        world: &World,
+        entity_id: EntityId,
+        position: Position,
+        range: u16,
+        target: SkillTarget,
+    ) -> Vec<EntityId> {
+        let mut targets = Vec::new();
+        for &id in self.targets.iter().take(MAX_ATTACK_TARGETS) {
+            if targets.contains(&id) || (target == SkillTarget::Enemy && id == entity_id) {
+                continue;
+            }
+            let Some(target_position) = world.map().get_position(id) else {
+                continue;
+            };
+            if position.chebyshev_distance(target_position) > range {
+                continue;
+            }
+            let alive = match world.get_mob(id) {
+                Some(Mob::Player(player)) => player.computed.score.hp > 0,
+                Some(Mob::Npc(npc)) => target == SkillTarget::Enemy && npc.computed.score.hp > 0,
+                None => false,
+            };
+            if alive {
+                targets.push(id);
+            }
+        }
+        targets
+    }
+}
+
+/// Follows up a mob whose HP dropped to zero. An NPC rewards `killer` and
+/// is removed with the death animation, a player stays dead in place with
+/// its trade and personal shop closed. Returns whether the mob was removed
+/// from the world.
+pub fn on_mob_killed<P: PacketSender>(
+    world: &mut World,
+    killer: EntityId,
+    target: EntityId,
+    sender: &P,
+    rng: &mut impl Rng,
+    now: Instant,
+) -> Result<bool, SessionError> {
+    match world.get_mob(target) {
+        Some(Mob::Npc(_)) => {
+            world.on_npc_killed(killer, target, sender, rng, now)?;
+            let Ok(removed) = world.remove_entity(target) else {
+                return Ok(false);
+            };
+            for spectator in removed.spectators {
+                sender.send_to(
+                    spectator,
+                    RemoveMob {
+                        mob_id: target.id() as u16,
+                        remove_type: REMOVE_TYPE_DEATH,
+                    },
+                )?;
+            }
+            Ok(true)
+        }
+        Some(Mob::Player(_)) => {
+            cancel_trade(world, target, sender)?;
+            close_stall(world, target, sender)?;
+            Ok(false)
+        }
+        None => Ok(false),
+    }
+}
+
+impl<const N: usize> TryFrom<AttackRaw<N>> for Attack {
+    type Error = WritableResourceError;
+
+    fn try_from(value: AttackRaw<N>) -> Result<Self, Self::Error> {
+        Ok(Attack {
+            skill_index: value.skill_index,
+            motion: value.motion,
+            skill_parm: value.skill_parm,
+            target_position: Position {
+                x: value.target_position.x,
+                y: value.target_position.y,
+            },
+            targets: value
+                .damages
+                .iter()
+                .filter(|damage| damage.target_id > 0)
+                .map(|damage| EntityId::from_id(damage.target_id as usize))
+                .collect(),
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum AttackError {
+    #[error("Attacker not found in world")]
+    AttackerNotFound,
+
+    #[error("Attacker is dead")]
+    AttackerDead,
+
+    #[error("No valid target in range")]
+    NoValidTarget,
+
+    #[error("Skill {0} has no skill data")]
+    UnknownSkill(i16),
+
+    #[error("Skill is not learned")]
+    SkillNotLearned,
+
+    #[error("Skill is on cooldown")]
+    OnCooldown,
+
+    #[error("Not enough mana")]
+    NotEnoughMana,
+
+    #[error(transparent)]
+    Session(#[from] SessionError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::tests::MockPacketSender;
+    use crate::npc::{
+        Npc,
+        movement::{MovementBehavior, MovementState},
+    };
+    use crate::skill::SkillTable;
+    use crate::world::Player;
+    use odin_models::{
+        affect::AffectKind,
+        character::Character,
+        item::Item,
+        npc_mob::{LootDrop, NpcMob},
+        status::Score,
+    };
+    use odin_networking::messages::ServerMessage;
+    use rand::{SeedableRng, rngs::SmallRng};
+    use std::time::Duration;
+
+    fn add_player(world: &mut World, client_id: usize, pos: Position, damage: u32) -> EntityId {
+        let entity_id = EntityId::Player(client_id);
+        let mut player = Player::from_character(
+            entity_id,
+            Character {
+                name: format!("Player{}", client_id),
+                ..Default::default()
+            },
+        );
+        player.computed.score = Score {
+            damage,
+            max_hp: 100,
+            hp: 100,
+            ..Default::default()
+        };
+        world.add_player(entity_id, player, pos).unwrap();
+        entity_id
+    }
+
+    fn add_npc(world: &mut World, id: usize, pos: Position, hp: u32) -> EntityId {
+        let entity_id = EntityId::Mob(id);
+        let template = NpcMob {
+            name: format!("Npc{}", id),
+            score: Score {
+                max_hp: hp,
+                hp,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let movement = MovementState::new(MovementBehavior::Stationary, 1);
+        world
+            .add_npc(entity_id, Npc::new(entity_id, template, movement), pos)
+            .unwrap();
+        entity_id
+    }
+
+    fn melee(targets: Vec<EntityId>) -> Attack {
+        Attack {
+            skill_index: -1,
+            motion: 0,
+            skill_parm: 0,
+            target_position: Position { x: 2101, y: 2101 },
+            targets,
+        }
+    }
+
+    const SKILLS: &str = r#"
+        [[skills]]
+        id = 1
+        name = "Fire Ball"
+        mana = 40
+        range = 8
+        cooldown_ms = 1000
+        target = "enemy"
+        effect = { type = "damage", base = 100 }
+
+        [[skills]]
+        id = 2
+        name = "Heal"
+        mana = 10
+        range = 6
+        target = "ally"
+        effect = { type = "heal", base = 30 }
+
+        [[skills]]
+        id = 3
+        name = "Haste"
+        range = 0
+        target = "self"
+        effect = { type = "affect", affect = 2, value = 20, ticks = 60 }
+    "#;
+
+    fn add_caster(world: &mut World, mp: u32) -> EntityId {
+        world.set_skill_table(SkillTable::from_toml(SKILLS).unwrap());
+        let caster = add_player(world, 1, Position { x: 2100, y: 2100 }, 0);
+        if let Some(Mob::Player(player)) = world.get_mob_mut(caster) {
+            player.computed.score.mp = mp;
+            player.computed.score.max_mp = mp;
+            for skill in 1..=3 {
+                player.learned_skills.learn(skill);
+            }
+        }
+        caster
+    }
+
+    fn cast(skill_index: i16, targets: Vec<EntityId>) -> Attack {
+        Attack {
+            skill_index,
+            ..melee(targets)
+        }
+    }
+
+    fn mp(world: &World, entity_id: EntityId) -> u32 {
+        match world.get_mob(entity_id).unwrap() {
+            Mob::Player(player) => player.computed.score.mp,
+            Mob::Npc(npc) => npc.computed.score.mp,
+        }
+    }
+
+    fn hp(world: &World, entity_id: EntityId) -> u32 {
+        match world.get_mob(entity_id).unwrap() {
+            Mob::Player(player) => player.computed.score.hp,
+            Mob::Npc(npc) => npc.computed.score.hp,
+        }
+    }
+
+    #[test]
+    fn melee_damages_target_and_broadcasts() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let mut rng = SmallRng::seed_from_u64(1);
+        let attacker = add_player(&mut world, 1, Position { x: 2100, y: 2100 }, 50);
+        let watcher = add_player(&mut world, 2, Position { x: 2105, y: 2105 }, 0);
+        let npc = add_npc(&mut world, 1000, Position { x: 2101, y: 2101 }, 1000);
+
+        let killed = melee(vec![npc])
+            .handle(attacker, &mut world, &sender, &mut rng, Instant::now())
+            .unwrap();
+
+        assert!(killed.is_empty());
+        assert!(hp(&world, npc) < 1000);
+        let identifiers: Vec<_> = sender
+            .messages_for(watcher)
+            .iter()
+            .map(|packet| packet.identifier)
+            .collect();
+        assert_eq!(
+            identifiers,
+            vec![ServerMessage::Attack, ServerMessage::UpdateScore]
+        );
+    }
+
+    #[test]
+    fn killing_an_npc_removes_it() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let mut rng = SmallRng::seed_from_u64(1);
+        let attacker = add_player(&mut world, 1, Position { x: 2100, y: 2100 }, 500);
+        let npc = add_npc(&mut world, 1000, Position { x: 2101, y: 2101 }, 10);
+
+        let killed = melee(vec![npc])
+            .handle(attacker, &mut world, &sender, &mut rng, Instant::now())
+            .unwrap();
+
+        assert_eq!(killed, vec![npc]);
+        assert!(!world.entity_exists(npc));
+        let packets = sender.messages_for(attacker);
+        assert_eq!(packets.last().unwrap().identifier, ServerMessage::RemoveMob);
+    }
+
+    #[test]
+    fn loot_is_dropped_at_the_attack_time() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let mut rng = SmallRng::seed_from_u64(1);
+        let attacker = add_player(&mut world, 1, Position { x: 2100, y: 2100 }, 500);
+        let npc = EntityId::Mob(1000);
+        let mut template = NpcMob {
+            score: Score {
+                max_hp: 10,
+                hp: 10,
+                ..Default::default()
+            },
+            loot: vec![LootDrop {
+                slot: 0,
+                chance: 1.0,
+            }],
+            ..Default::default()
+        };
+        template.inventory.set(0, Item::from(1101u16));
+        let movement = MovementState::new(MovementBehavior::Stationary, 1);
+        world
+            .add_npc(
+                npc,
+                Npc::new(npc, template, movement),
+                Position { x: 2101, y: 2101 },
+            )
+            .unwrap();
+        let now = Instant::now() + Duration::from_secs(60);
+
+        melee(vec![npc])
+            .handle(attacker, &mut world, &sender, &mut rng, now)
+            .unwrap();
+
+        let drops = world
+            .map()
+            .ground_items_in_view(Position { x: 2101, y: 2101 });
+        assert_eq!(drops.len(), 1);
+        assert_eq!(drops[0].dropped_at, now);
+    }
+
+    #[test]
+    fn killing_a_player_leaves_it_dead() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let mut rng = SmallRng::seed_from_u64(1);
+        let attacker = add_player(&mut world, 1, Position { x: 2100, y: 2100 }, 500);
+        let target = add_player(&mut world, 2, Position { x: 2101, y: 2101 }, 0);
+
+        melee(vec![target])
+            .handle(attacker, &mut world, &sender, &mut rng, Instant::now())
+            .unwrap();
+
+        assert!(world.entity_exists(target));
+        assert_eq!(hp(&world, target), 0);
+        assert_eq!(
+            melee(vec![target]).handle(attacker, &mut world, &sender, &mut rng, Instant::now()),
+            Err(AttackError::NoValidTarget)
+        );
+    }
+
+    #[test]
+    fn targets_out_of_range_are_ignored() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let mut rng = SmallRng::seed_from_u64(1);
+        let attacker = add_player(&mut world, 1, Position { x: 2100, y: 2100 }, 50);
+        let npc = add_npc(&mut world, 1000, Position { x: 2110, y: 2110 }, 1000);
+
+        assert_eq!(
+            melee(vec![npc]).handle(attacker, &mut world, &sender, &mut rng, Instant::now()),
+            Err(AttackError::NoValidTarget)
+        );
+        assert_eq!(hp(&world, npc), 1000);
+    }
+
+    #[test]
+    fn dead_attacker_cannot_attack() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let mut rng = SmallRng::seed_from_u64(1);
+        let attacker = add_player(&mut world, 1, Position { x: 2100, y: 2100 }, 50);
+        let npc = add_npc(&mut world, 1000, Position { x: 2101, y: 2101 }, 1000);
+        if let Some(Mob::Player(player)) = world.get_mob_mut(attacker) {
+            player.computed.score.hp = 0;
+        }
+
+        assert_eq!(
+            melee(vec![npc]).handle(attacker, &mut world, &sender, &mut rng, Instant::now()),
+            Err(AttackError::AttackerDead)
+        );
+    }
+
+    #[test]
+    fn damage_skill_spends_mana_and_respects_cooldown() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let mut rng = SmallRng::seed_from_u64(1);
+        let caster = add_caster(&mut world, 100);
+        let npc = add_npc(&mut world, 1000, Position { x: 2106, y: 2106 }, 1000);
+        let now = Instant::now();
+
+        cast(1, vec![npc])
+            .handle(caster, &mut world, &sender, &mut rng, now)
+            .unwrap();
+
+        assert!(hp(&world, npc) < 1000);
+        assert_eq!(mp(&world, caster), 60);
+        assert_eq!(
+            cast(1, vec![npc]).handle(caster, &mut world, &sender, &mut rng, now),
+            Err(AttackError::OnCooldown)
+        );
+
+        let later = now + Duration::from_secs(1);
+        cast(1, vec![npc])
+            .handle(caster, &mut world, &sender, &mut rng, later)
+            .unwrap();
+        assert_eq!(mp(&world, caster), 20);
+        assert_eq!(
+            cast(1, vec![npc]).handle(
+                caster,
+                &mut world,
+                &sender,
+                &mut rng,
+                later + Duration::from_secs(1)
+            ),
+            Err(AttackError::NotEnoughMana)
+        );
+    }
+
+    #[test]
+    fn skill_requires_data_and_learning() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let mut rng = SmallRng::seed_from_u64(1);
+        let caster = add_caster(&mut world, 100);
+        let npc = add_npc(&mut world, 1000, Position { x: 2101, y: 2101 }, 1000);
+
+        assert_eq!(
+            cast(9, vec![npc]).handle(caster, &mut world, &sender, &mut rng, Instant::now()),
+            Err(AttackError::UnknownSkill(9))
+        );
+        if let Some(Mob::Player(player)) = world.get_mob_mut(caster) {
+            player.learned_skills = Default::default();
+        }
+        assert_eq!(
+            cast(1, vec![npc]).handle(caster, &mut world, &sender, &mut rng, Instant::now()),
+            Err(AttackError::SkillNotLearned)
+        );
+        assert_eq!(mp(&world, caster), 100);
+    }
+
+    #[test]
+    fn heal_skill_restores_ally_hp_up_to_max() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let mut rng = SmallRng::seed_from_u64(1);
+        let caster = add_caster(&mut world, 100);
+        let ally = add_player(&mut world, 2, Position { x: 2102, y: 2102 }, 0);
+        let npc = add_npc(&mut world, 1000, Position { x: 2101, y: 2101 }, 1000);
+        if let Some(Mob::Player(player)) = world.get_mob_mut(ally) {
+            player.computed.score.hp = 50;
+        }
+
+        cast(2, vec![ally, npc])
+            .handle(caster, &mut world, &sender, &mut rng, Instant::now())
+            .unwrap();
+        assert_eq!(hp(&world, ally), 80);
+        assert_eq!(hp(&world, npc), 1000);
+
+        cast(2, vec![ally])
+            .handle(caster, &mut world, &sender, &mut rng, Instant::now())
+            .unwrap();
+        assert_eq!(hp(&world, ally), 100);
+        assert_eq!(mp(&world, caster), 80);
+    }
+
+    #[test]
+    fn affect_skill_buffs_the_caster() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let mut rng = SmallRng::seed_from_u64(1);
+        let caster = add_caster(&mut world, 100);
+
+        let killed = cast(3, vec![])
+            .handle(caster, &mut world, &sender, &mut rng, Instant::now())
+            .unwrap();
+
+        assert!(killed.is_empty());
+        let affect = *world
+            .get_mob(caster)
+            .unwrap()
+            .affects()
+            .get(AffectKind::Haste as u8)
+            .unwrap();
+        assert_eq!(affect.value, 20);
+        assert_eq!(affect.source, AffectSource::Skill(3));
+    }
+}
@@ -0,0 +1,475 @@
+use crate::commands::COMMAND_PREFIX;
+use crate::locale::{MessageCatalog, MessageKey};
+use crate::map::EntityId;
+use crate::session::{PacketSender, SessionError};
+use crate::world::{Mob, World};
+use odin_networking::{
+    WritableResourceError,
+    messages::{
+        client::chat::{ChatRaw, WhisperRaw},
+        server::chat::{Chat as ChatBroadcast, Whisper as WhisperMessage},
+    },
+};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+pub const SHOUT_COOLDOWN: Duration = Duration::from_secs(10);
+
+const PARTY_PREFIX: char = '=';
+const GUILD_PREFIX: char = '-';
+const SHOUT_PREFIX: char = '@';
+
+#[derive(Debug)]
+pub struct Chat {
+    message: String,
+}
+
+impl Chat {
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn is_command(&self) -> bool {
+        self.message.starts_with(COMMAND_PREFIX)
+    }
+
+    pub fn handle<P: PacketSender>(
+        &self,
+        entity_id: EntityId,
+        world: &World,
+        sender: &P,
+    ) -> Result<(), ChatError> {
+        let Some(position) = world.map().get_position(entity_id) else {
+            return Err(ChatError::PlayerNotFound);
+        };
+        if self.message.is_empty() {
+            return Ok(());
+        }
+
+        for spectator in world.map().get_spectators(position, entity_id) {
+            sender.send_to(
+                spectator,
+                ChatBroadcast {
+                    sender_id: entity_id.id() as u16,
+                    message: self.message.clone(),
+                },
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<ChatRaw> for Chat {
+    type Error = WritableResourceError;
+
+    fn try_from(value: ChatRaw) -> Result<Self, Self::Error> {
+        Ok(Self {
+            message: value.message.try_into()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WhisperTarget {
+    Player(String),
+    Party,
+    Guild,
+    Shout,
+}
+
+impl WhisperTarget {
+    fn prefix(&self) -> Option<char> {
+        match self {
+            WhisperTarget::Player(_) => None,
+            WhisperTarget::Party => Some(PARTY_PREFIX),
+            WhisperTarget::Guild => Some(GUILD_PREFIX),
+            WhisperTarget::Shout => Some(SHOUT_PREFIX),
+        }
+    }
+}
+
+impl From<&str> for WhisperTarget {
+    fn from(name: &str) -> Self {
+        match name.chars().next() {
+            Some(PARTY_PREFIX) => WhisperTarget::Party,
+            Some(GUILD_PREFIX) => WhisperTarget::Guild,
+            Some(SHOUT_PREFIX) => WhisperTarget::Shout,
+            _ => WhisperTarget::Player(name.to_string()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Whisper {
+    target: WhisperTarget,
+    message: String,
+}
+
+impl Whisper {
+    pub fn handle<P: PacketSender>(
+        &self,
+        entity_id: EntityId,
+        world: &mut World,
+        sender: &P,
+        messages: &MessageCatalog,
+        now: Instant,
+    ) -> Result<(), ChatError> {
+        let result = self.handle_impl(entity_id, world, sender, now);
+        let message = match &result {
+            Err(ChatError::TargetNotFound(_)) => MessageKey::PlayerNotConnected,
+            Err(ChatError::NotInParty) => MessageKey::NotInParty,
+            Err(ChatError::NotInGuild) => MessageKey::NotInGuild,
+            Err(ChatError::ShoutCooldown(_)) => MessageKey::ShoutCooldown,
+            _ => return result,
+        };
+        sender.send_to(entity_id, messages.panel(message))?;
+        result
+    }
+
+    fn handle_impl<P: PacketSender>(
+        &self,
+        entity_id: EntityId,
+        world: &mut World,
+        sender: &P,
+        now: Instant,
+    ) -> Result<(), ChatError> {
+        let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) else {
+            return Err(ChatError::PlayerNotFound);
+        };
+        if self.message.is_empty() {
+            return Ok(());
+        }
+
+        let recipients = match &self.target {
+            WhisperTarget::Player(name) => {
+                let target = world
+                    .find_player_by_name(name)
+                    .filter(|target| *target != entity_id)
+                    .ok_or_else(|| ChatError::TargetNotFound(name.clone()))?;
+                vec![target]
+            }
+            WhisperTarget::Party => {
+                let party = world
+                    .parties()
+                    .party_of(entity_id)
+                    .ok_or(ChatError::NotInParty)?;
+                party
+                    .members()
+                    .iter()
+                    .copied()
+                    .filter(|id| *id != entity_id)
+                    .collect()
+            }
+            WhisperTarget::Guild => {
+                let guild = player.guild.ok_or(ChatError::NotInGuild)?;
+                world
+                    .player_ids()
+                    .into_iter()
+                    .filter(|id| *id != entity_id)
+                    .filter(|id| {
+                        matches!(world.get_mob(*id), Some(Mob::Player(p)) if p.guild == Some(guild))
+                    })
+                    .collect()
+            }
+            WhisperTarget::Shout => {
+                if let Some(last_shout) = player.last_shout {
+                    let elapsed = now.saturating_duration_since(last_shout);
+                    if elapsed < SHOUT_COOLDOWN {
+                        return Err(ChatError::ShoutCooldown(SHOUT_COOLDOWN - elapsed));
+                    }
+                }
+                player.last_shout = Some(now);
+                world
+                    .player_ids()
+                    .into_iter()
+                    .filter(|id| *id != entity_id)
+                    .collect()
+            }
+        };
+
+        let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+            return Err(ChatError::PlayerNotFound);
+        };
+        let name = self
+            .target
+            .prefix()
+            .into_iter()
+            .chain(player.name.chars())
+            .collect::<String>();
+        for recipient in recipients {
+            sender.send_to(
+                recipient,
+                WhisperMessage {
+                    name: name.clone(),
+                    message: self.message.clone(),
+                },
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<WhisperRaw> for Whisper {
+    type Error = WritableResourceError;
+
+    fn try_from(value: WhisperRaw) -> Result<Self, Self::Error> {
+        let name: String = value.name.try_into()?;
+        Ok(Self {
+            target: WhisperTarget::from(name.as_str()),
+            message: value.message.try_into()?,
+        })
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ChatError {
+    #[error("Player not found in world")]
+    PlayerNotFound,
+
+    #[error("Player {0} is not connected")]
+    TargetNotFound(String),
+
+    #[error("Player is not in a party")]
+    NotInParty,
+
+    #[error("Player is not in a guild")]
+    NotInGuild,
+
+    #[error("Shout is on cooldown for {0:?}")]
+    ShoutCooldown(Duration),
+
+    #[error(transparent)]
+    Session(#[from] SessionError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::tests::MockPacketSender;
+    use crate::world::Player;
+    use odin_models::{character::Character, position::Position};
+    use odin_networking::messages::ServerMessage;
+
+    fn add_player(
+        world: &mut World,
+        client_id: usize,
+        pos: Position,
+        guild: Option<i16>,
+    ) -> EntityId {
+        let entity_id = EntityId::Player(client_id);
+        let player = Player::from_character(
+            entity_id,
+            Character {
+                name: format!("Player{}", client_id),
+                guild,
+                ..Default::default()
+            },
+        );
+        world.add_player(entity_id, player, pos).unwrap();
+        entity_id
+    }
+
+    fn whisper(name: &str, message: &str) -> Whisper {
+        Whisper {
+            target: WhisperTarget::from(name),
+            message: message.to_string(),
+        }
+    }
+
+    fn identifiers(sender: &MockPacketSender, entity_id: EntityId) -> Vec<ServerMessage> {
+        sender
+            .messages_for(entity_id)
+            .iter()
+            .map(|packet| packet.identifier)
+            .collect()
+    }
+
+    #[test]
+    fn local_chat_reaches_only_spectators() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let speaker = add_player(&mut world, 1, Position { x: 2100, y: 2100 }, None);
+        let near = add_player(&mut world, 2, Position { x: 2105, y: 2105 }, None);
+        let far = add_player(&mut world, 3, Position { x: 2200, y: 2200 }, None);
+
+        Chat {
+            message: "hello".to_string(),
+        }
+        .handle(speaker, &world, &sender)
+        .unwrap();
+
+        let packets = sender.messages_for(near);
+        assert_eq!(packets.len(), 1);
+        assert_eq!(packets[0].identifier, ServerMessage::Chat);
+        assert!(sender.messages_for(far).is_empty());
+        assert!(sender.messages_for(speaker).is_empty());
+    }
+
+    #[test]
+    fn whisper_reaches_target_by_name() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let speaker = add_player(&mut world, 1, Position { x: 2100, y: 2100 }, None);
+        let target = add_player(&mut world, 2, Position { x: 2500, y: 2500 }, None);
+        let other = add_player(&mut world, 3, Position { x: 2105, y: 2105 }, None);
+
+        whisper("player2", "psst")
+            .handle(
+                speaker,
+                &mut world,
+                &sender,
+                MessageCatalog::builtin(),
+                Instant::now(),
+            )
+            .unwrap();
+
+        assert_eq!(identifiers(&sender, target), vec![ServerMessage::Whisper]);
+        assert!(sender.messages_for(other).is_empty());
+    }
+
+    #[test]
+    fn whisper_to_unknown_player_notifies_sender() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let speaker = add_player(&mut world, 1, Position { x: 2100, y: 2100 }, None);
+
+        let result = whisper("Nobody", "psst").handle(
+            speaker,
+            &mut world,
+            &sender,
+            MessageCatalog::builtin(),
+            Instant::now(),
+        );
+
+        assert_eq!(result, Err(ChatError::TargetNotFound("Nobody".to_string())));
+        assert_eq!(
+            identifiers(&sender, speaker),
+            vec![ServerMessage::MessagePanel]
+        );
+    }
+
+    #[test]
+    fn guild_chat_reaches_only_guild_members() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let speaker = add_player(&mut world, 1, Position { x: 2100, y: 2100 }, Some(7));
+        let member = add_player(&mut world, 2, Position { x: 2500, y: 2500 }, Some(7));
+        let outsider = add_player(&mut world, 3, Position { x: 2105, y: 2105 }, Some(8));
+
+        whisper("-", "guild")
+            .handle(
+                speaker,
+                &mut world,
+                &sender,
+                MessageCatalog::builtin(),
+                Instant::now(),
+            )
+            .unwrap();
+
+        assert_eq!(identifiers(&sender, member), vec![ServerMessage::Whisper]);
+        assert!(sender.messages_for(outsider).is_empty());
+        assert!(sender.messages_for(speaker).is_empty());
+    }
+
+    #[test]
+    fn guild_chat_without_guild_errors() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let speaker = add_player(&mut world, 1, Position { x: 2100, y: 2100 }, None);
+
+        assert_eq!(
+            whisper("-", "guild").handle(
+                speaker,
+                &mut world,
+                &sender,
+                MessageCatalog::builtin(),
+                Instant::now()
+            ),
+            Err(ChatError::NotInGuild)
+        );
+    }
+
+    #[test]
+    fn party_chat_reaches_only_party_members() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let speaker = add_player(&mut world, 1, Position { x: 2100, y: 2100 }, None);
+        let member = add_player(&mut world, 2, Position { x: 2500, y: 2500 }, None);
+        let outsider = add_player(&mut world, 3, Position { x: 2105, y: 2105 }, None);
+
+        assert_eq!(
+            whisper("=", "party").handle(
+                speaker,
+                &mut world,
+                &sender,
+                MessageCatalog::builtin(),
+                Instant::now()
+            ),
+            Err(ChatError::NotInParty)
+        );
+
+        world.parties_mut().invite(speaker, member).unwrap();
+        world.parties_mut().accept(member, speaker).unwrap();
+        whisper("=", "party")
+            .handle(
+                speaker,
+                &mut world,
+                &sender,
+                MessageCatalog::builtin(),
+                Instant::now(),
+            )
+            .unwrap();
+
+        assert_eq!(identifiers(&sender, member), vec![ServerMessage::Whisper]);
+        assert!(sender.messages_for(outsider).is_empty());
+    }
+
+    #[test]
+    fn shout_reaches_everyone_and_respects_cooldown() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let speaker = add_player(&mut world, 1, Position { x: 2100, y: 2100 }, None);
+        let far = add_player(&mut world, 2, Position { x: 3000, y: 3000 }, None);
+        let now = Instant::now();
+
+        whisper("@", "hi all")
+            .handle(speaker, &mut world, &sender, MessageCatalog::builtin(), now)
+            .unwrap();
+        assert_eq!(identifiers(&sender, far), vec![ServerMessage::Whisper]);
+
+        let result = whisper("@", "again").handle(
+            speaker,
+            &mut world,
+            &sender,
+            MessageCatalog::builtin(),
+            now + Duration::from_secs(3),
+        );
+        assert_eq!(
+            result,
+            Err(ChatError::ShoutCooldown(Duration::from_secs(7)))
+        );
+        assert_eq!(sender.messages_for(far).len(), 1);
+
+        whisper("@", "later")
+            .handle(
+                speaker,
+                &mut world,
+                &sender,
+                MessageCatalog::builtin(),
+                now + SHOUT_COOLDOWN,
+            )
+            .unwrap();
+        assert_eq!(sender.messages_for(far).len(), 2);
+    }
+
+    #[test]
+    fn parses_whisper_targets() {
+        assert_eq!(WhisperTarget::from("="), WhisperTarget::Party);
+        assert_eq!(WhisperTarget::from("-"), WhisperTarget::Guild);
+        assert_eq!(WhisperTarget::from("@"), WhisperTarget::Shout);
+        assert_eq!(
+            WhisperTarget::from("Someone"),
+            WhisperTarget::Player("Someone".to_string())
+        );
+    }
+}
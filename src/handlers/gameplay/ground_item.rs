@@ -0,0 +1,352 @@
+use crate::map::{EntityId, MapError, ground::GroundItemId};
+use crate::packets::ToGroundItemPackets;
+use crate::session::{PacketSender, SessionError};
+use crate::world::{Mob, World};
+use odin_models::{MAX_INVENTORY_VISIBLE, position::Position};
+use odin_networking::{
+    WritableResourceError,
+    messages::{
+        client::ground_item::{DropItemRaw, PickupItemRaw},
+        server::create_item::{CreateItem, SlotType},
+    },
+};
+use std::time::{Duration, Instant};
+
+pub const GROUND_ITEM_RANGE: u16 = 3;
+
+#[derive(Debug)]
+pub struct DropItem {
+    pub slot: usize,
+    pub position: Position,
+}
+
+impl DropItem {
+    pub fn handle<P: PacketSender>(
+        &self,
+        entity_id: EntityId,
+        world: &mut World,
+        sender: &P,
+        now: Instant,
+    ) -> Result<(), GroundItemError> {
+        let player_position = world
+            .map()
+            .get_position(entity_id)
+            .ok_or(GroundItemError::PlayerNotFound)?;
+        if player_position.chebyshev_distance(self.position) > GROUND_ITEM_RANGE {
+            return Err(GroundItemError::OutOfRange);
+        }
+
+        let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) else {
+            return Err(GroundItemError::PlayerNotFound);
+        };
+        if player.computed.score.hp == 0 {
+            return Err(GroundItemError::Dead);
+        }
+        let item = player
+            .inventory
+            .take(self.slot)
+            .ok_or(GroundItemError::EmptySlot)?;
+
+        let id = match world.map_mut().drop_item(item, self.position, None, now) {
+            Ok(id) => id,
+            Err(e) => {
+                if let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) {
+                    player.inventory.set(self.slot, item);
+                }
+                return Err(e.into());
+            }
+        };
+
+        sender.send_to(
+            entity_id,
+            CreateItem {
+                mob_id: entity_id.id() as u16,
+                slot_type: SlotType::Inventory,
+                slot: self.slot as u16,
+                item: None,
+            },
+        )?;
+        let ground_item = world
+            .map()
+            .ground_item(id)
+            .expect("dropped item must be on the ground");
+        for viewer in world.map().get_viewers(ground_item.position) {
+            sender.send_to(viewer, ground_item.to_create_ground_item())?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct PickupItem {
+    pub item_id: GroundItemId,
+}
+
+impl PickupItem {
+    pub fn handle<P: PacketSender>(
+        &self,
+        entity_id: EntityId,
+        world: &mut World,
+        sender: &P,
+        owner_grace: Duration,
+        now: Instant,
+    ) -> Result<(), GroundItemError> {
+        let player_position = world
+            .map()
+            .get_position(entity_id)
+            .ok_or(GroundItemError::PlayerNotFound)?;
+        let ground_item = world
+            .map()
+            .ground_item(self.item_id)
+            .ok_or(GroundItemError::ItemNotFound)?;
+        if player_position.chebyshev_distance(ground_item.position) > GROUND_ITEM_RANGE {
+            return Err(GroundItemError::OutOfRange);
+        }
+        if !ground_item.can_pick_up(entity_id, now, owner_grace) {
+            return Err(GroundItemError::NotOwner);
+        }
+
+        let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+            return Err(GroundItemError::PlayerNotFound);
+        };
+        if player.computed.score.hp == 0 {
+            return Err(GroundItemError::Dead);
+        }
+        let slot = player
+            .inventory
+            .first_empty()
+            .ok_or(GroundItemError::InventoryFull)?;
+
+        let ground_item = world
+            .map_mut()
+            .remove_ground_item(self.item_id)
+            .ok_or(GroundItemError::ItemNotFound)?;
+        let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) else {
+            return Err(GroundItemError::PlayerNotFound);
+        };
+        player.inventory.set(slot, ground_item.item);
+
+        sender.send_to(
+            entity_id,
+            CreateItem {
+                mob_id: entity_id.id() as u16,
+                slot_type: SlotType::Inventory,
+                slot: slot as u16,
+                item: Some(ground_item.item),
+            },
+        )?;
+        for viewer in world.map().get_viewers(ground_item.position) {
+            sender.send_to(viewer, ground_item.to_remove_ground_item())?;
+        }
+        Ok(())
+    }
+}
+
+pub fn decay_ground_items<P: PacketSender>(
+    world: &mut World,
+    sender: &P,
+    now: Instant,
+    decay: Duration,
+) -> Result<usize, SessionError> {
+    let expired = world.map().expired_ground_items(now, decay);
+    for id in &expired {
+        let Some(ground_item) = world.map_mut().remove_ground_item(*id) else {
+            continue;
+        };
+        for viewer in world.map().get_viewers(ground_item.position) {
+            sender.send_to(viewer, ground_item.to_remove_ground_item())?;
+        }
+    }
+    Ok(expired.len())
+}
+
+impl TryFrom<DropItemRaw> for DropItem {
+    type Error = WritableResourceError;
+
+    fn try_from(value: DropItemRaw) -> Result<Self, Self::Error> {
+        if value.src_type != 1 {
+            return Err(WritableResourceError::Generic(
+                "Only inventory items can be dropped".to_string(),
+            ));
+        }
+        let slot = usize::try_from(value.src_slot)
+            .ok()
+            .filter(|slot| *slot < MAX_INVENTORY_VISIBLE)
+            .ok_or_else(|| WritableResourceError::Generic("Invalid item slot".to_string()))?;
+        Ok(DropItem {
+            slot,
+            position: Position {
+                x: value.position.x,
+                y: value.position.y,
+            },
+        })
+    }
+}
+
+impl TryFrom<PickupItemRaw> for PickupItem {
+    type Error = WritableResourceError;
+
+    fn try_from(value: PickupItemRaw) -> Result<Self, Self::Error> {
+        let item_id = GroundItemId::from_id(value.item_id as usize)
+            .ok_or_else(|| WritableResourceError::Generic("Invalid ground item id".to_string()))?;
+        Ok(PickupItem { item_id })
+    }
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum GroundItemError {
+    #[error("Player not found in world")]
+    PlayerNotFound,
+
+    #[error("Player is dead")]
+    Dead,
+
+    #[error("Inventory slot is empty")]
+    EmptySlot,
+
+    #[error("Ground item not found")]
+    ItemNotFound,
+
+    #[error("Ground item is out of range")]
+    OutOfRange,
+
+    #[error("Ground item belongs to another player")]
+    NotOwner,
+
+    #[error("Inventory is full")]
+    InventoryFull,
+
+    #[error(transparent)]
+    Map(#[from] MapError),
+
+    #[error(transparent)]
+    Session(#[from] SessionError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::tests::MockPacketSender;
+    use crate::world::Player;
+    use odin_models::{character::Character, item::Item};
+    use odin_networking::messages::ServerMessage;
+
+    fn position(x: u16, y: u16) -> Position {
+        Position { x, y }
+    }
+
+    fn add_player(world: &mut World, client_id: usize, pos: Position) -> EntityId {
+        let entity_id = EntityId::Player(client_id);
+        let mut player = Player::from_character(entity_id, Character::default());
+        player.computed.score.hp = 100;
+        world.add_player(entity_id, player, pos).unwrap();
+        entity_id
+    }
+
+    fn inventory(world: &World, entity_id: EntityId, slot: usize) -> Option<Item> {
+        match world.get_mob(entity_id) {
+            Some(Mob::Player(player)) => player.inventory.get(slot).copied(),
+            _ => panic!("player not found"),
+        }
+    }
+
+    #[test]
+    fn drop_and_pickup_round_trip() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let dropper = add_player(&mut world, 1, position(2100, 2100));
+        let spectator = add_player(&mut world, 2, position(2105, 2105));
+        if let Some(Mob::Player(player)) = world.get_mob_mut(dropper) {
+            player.inventory.set(4, Item::from(1101u16));
+        }
+        let now = Instant::now();
+
+        DropItem {
+            slot: 4,
+            position: position(2101, 2100),
+        }
+        .handle(dropper, &mut world, &sender, now)
+        .unwrap();
+        assert_eq!(inventory(&world, dropper, 4), None);
+        let item_id = world.map().ground_items_in_view(position(2100, 2100))[0].id;
+        assert!(
+            sender
+                .messages_for(spectator)
+                .iter()
+                .any(|m| m.identifier == ServerMessage::CreateGroundItem)
+        );
+
+        assert_eq!(
+            PickupItem { item_id }.handle(spectator, &mut world, &sender, Duration::ZERO, now),
+            Err(GroundItemError::OutOfRange)
+        );
+        PickupItem { item_id }
+            .handle(dropper, &mut world, &sender, Duration::ZERO, now)
+            .unwrap();
+        assert_eq!(
+            inventory(&world, dropper, 0).map(|item| item.id),
+            Some(1101)
+        );
+        assert!(world.map().ground_item(item_id).is_none());
+        assert!(
+            sender
+                .messages_for(spectator)
+                .iter()
+                .any(|m| m.identifier == ServerMessage::RemoveGroundItem)
+        );
+    }
+
+    #[test]
+    fn pickup_respects_owner_grace() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let owner = add_player(&mut world, 1, position(2100, 2100));
+        let other = add_player(&mut world, 2, position(2102, 2100));
+        let now = Instant::now();
+        let item_id = world
+            .map_mut()
+            .drop_item(Item::from(1u16), position(2101, 2100), Some(owner), now)
+            .unwrap();
+        let grace = Duration::from_secs(30);
+
+        assert_eq!(
+            PickupItem { item_id }.handle(other, &mut world, &sender, grace, now),
+            Err(GroundItemError::NotOwner)
+        );
+        PickupItem { item_id }
+            .handle(other, &mut world, &sender, grace, now + grace)
+            .unwrap();
+        assert_eq!(inventory(&world, other, 0).map(|item| item.id), Some(1));
+    }
+
+    #[test]
+    fn decay_removes_expired_items() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let watcher = add_player(&mut world, 1, position(2100, 2100));
+        let now = Instant::now();
+        world
+            .map_mut()
+            .drop_item(Item::from(1u16), position(2101, 2100), None, now)
+            .unwrap();
+        let decay = Duration::from_secs(120);
+
+        assert_eq!(decay_ground_items(&mut world, &sender, now, decay), Ok(0));
+        assert_eq!(
+            decay_ground_items(&mut world, &sender, now + decay, decay),
+            Ok(1)
+        );
+        assert!(
+            world
+                .map()
+                .ground_items_in_view(position(2100, 2100))
+                .is_empty()
+        );
+        assert!(
+            sender
+                .messages_for(watcher)
+                .iter()
+                .any(|m| m.identifier == ServerMessage::RemoveGroundItem)
+        );
+    }
+}
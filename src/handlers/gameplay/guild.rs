@@ -0,0 +1,834 @@
+use crate::commands::COMMAND_PREFIX;
+use crate::handlers::gameplay::move_item::broadcast_appearance;
+use crate::locale::{MessageCatalog, MessageKey};
+use crate::map::EntityId;
+use crate::packets::{ToUpdateEtc, ToUpdateScore};
+use crate::session::{PacketSender, SessionError};
+use crate::world::{Mob, Player, World};
+use odin_models::{character::GuildLevel, nickname::Nickname, uuid::Uuid};
+use odin_networking::messages::server::message_panel::MessagePanel;
+use odin_repositories::guild_repository::{GuildRepository, GuildRepositoryError};
+
+const GUILD_COMMAND: &str = "guild";
+/// Longest notice the guild table stores.
+pub const MAX_GUILD_NOTICE: usize = 128;
+
+/// Guild management typed by players as `/guild <subcommand>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuildCommand {
+    Create {
+        name: String,
+    },
+    Invite {
+        target: String,
+    },
+    Accept,
+    Kick {
+        target: String,
+    },
+    /// `rank` 1 to 3 makes the target the commander of that sub-guild and
+    /// 0 demotes it back to a participant.
+    Promote {
+        target: String,
+        rank: u8,
+    },
+    Leave,
+    Disband,
+    Notice {
+        text: String,
+    },
+    /// Unknown subcommand or malformed arguments.
+    Usage,
+}
+
+impl GuildCommand {
+    /// `None` when `line` is not a `/guild` command.
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = line.trim().strip_prefix(COMMAND_PREFIX)?;
+        let (command, rest) = line.split_once(' ').unwrap_or((line, ""));
+        if !command.eq_ignore_ascii_case(GUILD_COMMAND) {
+            return None;
+        }
+
+        let rest = rest.trim();
+        let (subcommand, rest) = rest.split_once(' ').unwrap_or((rest, ""));
+        let args: Vec<&str> = rest.split_whitespace().collect();
+        let command = match (subcommand.to_ascii_lowercase().as_str(), args.as_slice()) {
+            ("create", [name]) => GuildCommand::Create {
+                name: name.to_string(),
+            },
+            ("invite", [target]) => GuildCommand::Invite {
+                target: target.to_string(),
+            },
+            ("accept", []) => GuildCommand::Accept,
+            ("kick", [target]) => GuildCommand::Kick {
+                target: target.to_string(),
+            },
+            ("promote", [target, rank]) => match rank.parse() {
+                Ok(rank @ 0..=3) => GuildCommand::Promote {
+                    target: target.to_string(),
+                    rank,
+                },
+                _ => return Some(GuildCommand::Usage),
+            },
+            ("leave", []) => GuildCommand::Leave,
+            ("disband", []) => GuildCommand::Disband,
+            ("notice", [_, ..]) if rest.trim().len() <= MAX_GUILD_NOTICE => GuildCommand::Notice {
+                text: rest.trim().to_string(),
+            },
+            _ => return Some(GuildCommand::Usage),
+        };
+        Some(command)
+    }
+
+    pub async fn handle<P: PacketSender, G: GuildRepository>(
+        &self,
+        entity_id: EntityId,
+        world: &mut World,
+        sender: &P,
+        repository: &G,
+        creation_cost: u32,
+        messages: &MessageCatalog,
+    ) -> Result<(), GuildError> {
+        let result = self
+            .handle_impl(
+                entity_id,
+                world,
+                sender,
+                repository,
+                creation_cost,
+                messages,
+            )
+            .await;
+        let message = match &result {
+            Ok(()) => match self {
+                GuildCommand::Create { .. } => MessageKey::GuildCreated,
+                GuildCommand::Accept => MessageKey::GuildJoined,
+                GuildCommand::Leave => MessageKey::GuildLeft,
+                _ => return result,
+            },
+            Err(err) => match err.message() {
+                Some(message) => message,
+                None => return result,
+            },
+        };
+        sender.send_to(entity_id, messages.panel(message))?;
+        result
+    }
+
+    async fn handle_impl<P: PacketSender, G: GuildRepository>(
+        &self,
+        entity_id: EntityId,
+        world: &mut World,
+        sender: &P,
+        repository: &G,
+        creation_cost: u32,
+        messages: &MessageCatalog,
+    ) -> Result<(), GuildError> {
+        let issuer = player(world, entity_id)?;
+        let (identifier, guild, level, coin) = (
+            issuer.identifier,
+            issuer.guild,
+            issuer.guild_level,
+            issuer.coin,
+        );
+
+        match self {
+            GuildCommand::Usage => return Err(GuildError::Usage),
+            GuildCommand::Create { name } => {
+                if guild.is_some() {
+                    return Err(GuildError::AlreadyInGuild);
+                }
+                let name =
+                    Nickname::try_from(name.as_str()).map_err(|_| GuildError::InvalidName)?;
+                if coin < 0 || (coin as u32) < creation_cost {
+                    return Err(GuildError::NotEnoughCoin);
+                }
+                let coin = coin - creation_cost as i32;
+
+                let created = repository
+                    .create_guild(&name, identifier, coin)
+                    .await
+                    .map_err(|err| match err {
+                        GuildRepositoryError::NameTaken => GuildError::NameTaken,
+                        err => GuildError::Repository(err),
+                    })?;
+                if let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) {
+                    player.coin = coin;
+                    sender.send_to(entity_id, player.to_update_etc())?;
+                }
+                update_membership(
+                    world,
+                    entity_id,
+                    Some((created.id, GuildLevel::Leader)),
+                    sender,
+                )?;
+            }
+            GuildCommand::Invite { target } => {
+                let guild = guild.ok_or(GuildError::NotInGuild)?;
+                if level == Some(GuildLevel::Participant) {
+                    return Err(GuildError::NotAllowed);
+                }
+                let target = find_player(world, target)?;
+                let Some(Mob::Player(player)) = world.get_mob_mut(target) else {
+                    return Err(GuildError::PlayerNotFound);
+                };
+                if player.guild.is_some() {
+                    return Err(GuildError::AlreadyInGuild);
+                }
+                player.guild_invite = Some(guild);
+                sender.send_to(target, messages.panel(MessageKey::GuildInvited))?;
+            }
+            GuildCommand::Accept => {
+                if guild.is_some() {
+                    return Err(GuildError::AlreadyInGuild);
+                }
+                let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) else {
+                    return Err(GuildError::PlayerNotFound);
+                };
+                let invite = player.guild_invite.take().ok_or(GuildError::NoInvite)?;
+                // The guild may have been disbanded since the invite was sent
+                repository
+                    .fetch_guild(invite)
+                    .await?
+                    .ok_or(GuildError::NoInvite)?;
+
+                let membership = Some((invite, GuildLevel::Participant));
+                repository.set_membership(identifier, membership).await?;
+                update_membership(world, entity_id, membership, sender)?;
+            }
+            GuildCommand::Kick { target } => {
+                let guild = leader_guild(guild, level)?;
+                let (target_identifier, target) =
+                    guild_member(world, repository, target, guild, entity_id).await?;
+
+                repository.set_membership(target_identifier, None).await?;
+                if let Some(target) = target {
+                    update_membership(world, target, None, sender)?;
+                    sender.send_to(target, messages.panel(MessageKey::GuildKicked))?;
+                }
+            }
+            GuildCommand::Promote { target, rank } => {
+                let guild = leader_guild(guild, level)?;
+                let (target_identifier, target) =
+                    guild_member(world, repository, target, guild, entity_id).await?;
+                let rank = match rank {
+                    1 => GuildLevel::FirstCommander,
+                    2 => GuildLevel::SecondCommander,
+                    3 => GuildLevel::ThirdCommander,
+                    _ => GuildLevel::Participant,
+                };
+
+                let membership = Some((guild, rank));
+                repository
+                    .set_membership(target_identifier, membership)
+                    .await?;
+                if let Some(target) = target {
+                    update_membership(world, target, membership, sender)?;
+                    sender.send_to(target, messages.panel(MessageKey::GuildPromoted))?;
+                }
+            }
+            GuildCommand::Leave => {
+                guild.ok_or(GuildError::NotInGuild)?;
+                // The leader can only disband the guild
+                if level == Some(GuildLevel::Leader) {
+                    return Err(GuildError::NotAllowed);
+                }
+
+                repository.set_membership(identifier, None).await?;
+                update_membership(world, entity_id, None, sender)?;
+            }
+            GuildCommand::Disband => {
+                let guild = leader_guild(guild, level)?;
+
+                repository.disband_guild(guild).await?;
+                for member in online_members(world, guild) {
+                    update_membership(world, member, None, sender)?;
+                    sender.send_to(member, messages.panel(MessageKey::GuildDisbanded))?;
+                }
+            }
+            GuildCommand::Notice { text } => {
+                let guild = guild.ok_or(GuildError::NotInGuild)?;
+                if level == Some(GuildLevel::Participant) {
+                    return Err(GuildError::NotAllowed);
+                }
+
+                repository.set_notice(guild, text).await?;
+                for member in online_members(world, guild) {
+                    sender.send_to(member, MessagePanel::from(text.as_str()))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Shows the guild notice to a player entering the world.
+pub async fn send_guild_notice<P: PacketSender, G: GuildRepository>(
+    entity_id: EntityId,
+    world: &World,
+    sender: &P,
+    repository: &G,
+) -> Result<(), GuildError> {
+    let Some(guild) = player(world, entity_id)?.guild else {
+        return Ok(());
+    };
+    if let Some(guild) = repository.fetch_guild(guild).await?
+        && !guild.notice.is_empty()
+    {
+        sender.send_to(entity_id, MessagePanel::from(guild.notice))?;
+    }
+    Ok(())
+}
+
+fn player(world: &World, entity_id: EntityId) -> Result<&Player, GuildError> {
+    match world.get_mob(entity_id) {
+        Some(Mob::Player(player)) => Ok(player),
+        _ => Err(GuildError::PlayerNotFound),
+    }
+}
+
+fn find_player(world: &World, name: &str) -> Result<EntityId, GuildError> {
+    world
+        .find_player_by_name(name)
+        .ok_or_else(|| GuildError::TargetNotFound(name.to_string()))
+}
+
+fn leader_guild(guild: Option<i16>, level: Option<GuildLevel>) -> Result<i16, GuildError> {
+    let guild = guild.ok_or(GuildError::NotInGuild)?;
+    if level != Some(GuildLevel::Leader) {
+        return Err(GuildError::NotAllowed);
+    }
+    Ok(guild)
+}
+
+/// A member of `guild` other than the issuer, along with its entity when
+/// it is online. Offline members are looked up in the repository.
+async fn guild_member<G: GuildRepository>(
+    world: &World,
+    repository: &G,
+    name: &str,
+    guild: i16,
+    issuer: EntityId,
+) -> Result<(Uuid, Option<EntityId>), GuildError> {
+    let not_member = || GuildError::NotMember(name.to_string());
+    if let Some(target) = world.find_player_by_name(name) {
+        let player = player(world, target)?;
+        if target == issuer || player.guild != Some(guild) {
+            return Err(not_member());
+        }
+        return Ok((player.identifier, Some(target)));
+    }
+
+    let identifier = repository
+        .find_member_by_name(guild, name)
+        .await?
+        .ok_or_else(not_member)?;
+    Ok((identifier, None))
+}
+
+fn online_members(world: &World, guild: i16) -> Vec<EntityId> {
+    world
+        .player_ids()
+        .into_iter()
+        .filter(|id| matches!(world.get_mob(*id), Some(Mob::Player(p)) if p.guild == Some(guild)))
+        .collect()
+}
+
+/// Updates the player's guild in the world and shows it to the player and
+/// its spectators. Persisting it is up to the caller.
+fn update_membership<P: PacketSender>(
+    world: &mut World,
+    entity_id: EntityId,
+    membership: Option<(i16, GuildLevel)>,
+    sender: &P,
+) -> Result<(), SessionError> {
+    let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) else {
+        return Ok(());
+    };
+    player.guild = membership.map(|(guild, _)| guild);
+    player.guild_level = membership.map(|(_, level)| level);
+    player.guild_invite = None;
+
+    sender.send_to(entity_id, player.to_update_score())?;
+    broadcast_appearance(world, entity_id, sender)
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum GuildError {
+    #[error("Player not found in world")]
+    PlayerNotFound,
+
+    #[error("Invalid guild command")]
+    Usage,
+
+    #[error("Invalid guild name")]
+    InvalidName,
+
+    #[error("Guild name is already taken")]
+    NameTaken,
+
+    #[error("Not enough coin to create a guild")]
+    NotEnoughCoin,
+
+    #[error("Player is already in a guild")]
+    AlreadyInGuild,
+
+    #[error("Player is not in a guild")]
+    NotInGuild,
+
+    #[error("Player's guild rank does not allow it")]
+    NotAllowed,
+
+    #[error("Player {0} is not a member of the guild")]
+    NotMember(String),
+
+    #[error("No pending guild invite")]
+    NoInvite,
+
+    #[error("Player {0} is not connected")]
+    TargetNotFound(String),
+
+    #[error(transparent)]
+    Repository(#[from] GuildRepositoryError),
+
+    #[error(transparent)]
+    Session(#[from] SessionError),
+}
+
+impl GuildError {
+    /// The text shown to the issuer, for errors caused by what it typed.
+    fn message(&self) -> Option<MessageKey> {
+        Some(match self {
+            GuildError::Usage => MessageKey::GuildUsage,
+            GuildError::InvalidName => MessageKey::GuildInvalidName,
+            GuildError::NameTaken => MessageKey::GuildNameTaken,
+            GuildError::NotEnoughCoin => MessageKey::GuildNotEnoughCoin,
+            GuildError::AlreadyInGuild => MessageKey::AlreadyInGuild,
+            GuildError::NotInGuild => MessageKey::NotInGuild,
+            GuildError::NotAllowed => MessageKey::GuildNotAllowed,
+            GuildError::NotMember(_) => MessageKey::NotGuildMember,
+            GuildError::NoInvite => MessageKey::NoGuildInvite,
+            GuildError::TargetNotFound(_) => MessageKey::PlayerNotConnected,
+            _ => return None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::tests::{MockPacketSender, TestAccountRepository};
+    use odin_database::guild_repository::DatabaseGuildRepository;
+    use odin_models::{
+        account_charlist::AccountCharlist, character::Character, position::Position, uuid::Uuid,
+    };
+    use odin_networking::messages::ServerMessage;
+    use odin_repositories::account_repository::AccountRepository;
+
+    const COST: u32 = 1000;
+
+    async fn add_player(
+        repository: &TestAccountRepository,
+        world: &mut World,
+        client_id: usize,
+        position: Position,
+    ) -> (EntityId, Uuid) {
+        let account_id = Uuid::new_v4();
+        let character = Character {
+            identifier: Uuid::new_v4(),
+            name: format!("Player{client_id}"),
+            coin: 1500,
+            last_pos: position,
+            ..Default::default()
+        };
+        repository
+            .add_account_with_characters(
+                AccountCharlist {
+                    identifier: account_id,
+                    username: format!("account{client_id}"),
+                    password: "pass".to_string(),
+                    ..Default::default()
+                },
+                vec![character.clone()],
+            )
+            .await;
+
+        let entity_id = EntityId::Player(client_id);
+        world
+            .add_player(
+                entity_id,
+                Player::from_character(entity_id, character),
+                position,
+            )
+            .unwrap();
+        (entity_id, account_id)
+    }
+
+    async fn run(
+        line: &str,
+        entity_id: EntityId,
+        world: &mut World,
+        sender: &MockPacketSender,
+        repository: &DatabaseGuildRepository,
+    ) -> Result<(), GuildError> {
+        GuildCommand::parse(line)
+            .unwrap()
+            .handle(
+                entity_id,
+                world,
+                sender,
+                repository,
+                COST,
+                MessageCatalog::builtin(),
+            )
+            .await
+    }
+
+    fn membership(world: &World, entity_id: EntityId) -> (Option<i16>, Option<GuildLevel>) {
+        let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+            panic!("expected Player");
+        };
+        (player.guild, player.guild_level)
+    }
+
+    fn identifiers(sender: &MockPacketSender, entity_id: EntityId) -> Vec<ServerMessage> {
+        sender
+            .messages_for(entity_id)
+            .iter()
+            .map(|packet| packet.identifier)
+            .collect()
+    }
+
+    #[test]
+    fn parses_guild_commands() {
+        assert_eq!(GuildCommand::parse("/kill Player"), None);
+        assert_eq!(
+            GuildCommand::parse("/GUILD create Odin"),
+            Some(GuildCommand::Create {
+                name: "Odin".to_string()
+            })
+        );
+        assert_eq!(
+            GuildCommand::parse("/guild promote Player1 2"),
+            Some(GuildCommand::Promote {
+                target: "Player1".to_string(),
+                rank: 2
+            })
+        );
+        assert_eq!(
+            GuildCommand::parse("/guild notice war  at noon"),
+            Some(GuildCommand::Notice {
+                text: "war  at noon".to_string()
+            })
+        );
+        assert_eq!(
+            GuildCommand::parse("/guild promote Player1 4"),
+            Some(GuildCommand::Usage)
+        );
+        assert_eq!(GuildCommand::parse("/guild"), Some(GuildCommand::Usage));
+    }
+
+    #[tokio::test]
+    async fn create_charges_coin_and_shows_the_guild() {
+        let repository = TestAccountRepository::new().await;
+        let guilds = repository.guild_repository();
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let (leader, account_id) =
+            add_player(&repository, &mut world, 1, Position { x: 2100, y: 2100 }).await;
+        let (spectator, _) =
+            add_player(&repository, &mut world, 2, Position { x: 2102, y: 2102 }).await;
+
+        run("/guild create Odin", leader, &mut world, &sender, &guilds)
+            .await
+            .unwrap();
+
+        let (guild, level) = membership(&world, leader);
+        assert_eq!(level, Some(GuildLevel::Leader));
+        let Some(Mob::Player(player)) = world.get_mob(leader) else {
+            panic!("expected Player");
+        };
+        assert_eq!(player.coin, 500);
+        assert_eq!(
+            guilds
+                .fetch_guild(guild.unwrap())
+                .await
+                .unwrap()
+                .unwrap()
+                .name,
+            "Odin"
+        );
+        let character = repository
+            .account_repository()
+            .fetch_character(account_id, 0)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(character.guild, guild);
+        assert_eq!(character.guild_level, Some(GuildLevel::Leader));
+        assert_eq!(character.coin, 500);
+        assert_eq!(
+            identifiers(&sender, leader),
+            vec![
+                ServerMessage::UpdateEtc,
+                ServerMessage::UpdateScore,
+                ServerMessage::MessagePanel
+            ]
+        );
+        assert_eq!(
+            identifiers(&sender, spectator),
+            vec![ServerMessage::CreateMob]
+        );
+
+        assert_eq!(
+            run(
+                "/guild create odin",
+                spectator,
+                &mut world,
+                &sender,
+                &guilds
+            )
+            .await,
+            Err(GuildError::NameTaken)
+        );
+        assert_eq!(
+            run("/guild create Other", leader, &mut world, &sender, &guilds).await,
+            Err(GuildError::AlreadyInGuild)
+        );
+    }
+
+    #[tokio::test]
+    async fn create_requires_coin() {
+        let repository = TestAccountRepository::new().await;
+        let guilds = repository.guild_repository();
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let (leader, _) =
+            add_player(&repository, &mut world, 1, Position { x: 2100, y: 2100 }).await;
+
+        let result = GuildCommand::parse("/guild create Odin")
+            .unwrap()
+            .handle(
+                leader,
+                &mut world,
+                &sender,
+                &guilds,
+                2000,
+                MessageCatalog::builtin(),
+            )
+            .await;
+
+        assert_eq!(result, Err(GuildError::NotEnoughCoin));
+        assert_eq!(membership(&world, leader), (None, None));
+        assert_eq!(
+            identifiers(&sender, leader),
+            vec![ServerMessage::MessagePanel]
+        );
+    }
+
+    #[tokio::test]
+    async fn invite_accept_promote_and_kick() {
+        let repository = TestAccountRepository::new().await;
+        let guilds = repository.guild_repository();
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let (leader, _) =
+            add_player(&repository, &mut world, 1, Position { x: 2100, y: 2100 }).await;
+        let (member, account_id) =
+            add_player(&repository, &mut world, 2, Position { x: 2500, y: 2500 }).await;
+        run("/guild create Odin", leader, &mut world, &sender, &guilds)
+            .await
+            .unwrap();
+        let (guild, _) = membership(&world, leader);
+
+        assert_eq!(
+            run("/guild accept", member, &mut world, &sender, &guilds).await,
+            Err(GuildError::NoInvite)
+        );
+        run(
+            "/guild invite player2",
+            leader,
+            &mut world,
+            &sender,
+            &guilds,
+        )
+        .await
+        .unwrap();
+        run("/guild accept", member, &mut world, &sender, &guilds)
+            .await
+            .unwrap();
+        assert_eq!(
+            membership(&world, member),
+            (guild, Some(GuildLevel::Participant))
+        );
+        assert_eq!(
+            run("/guild kick Player1", member, &mut world, &sender, &guilds).await,
+            Err(GuildError::NotAllowed)
+        );
+
+        run(
+            "/guild promote Player2 1",
+            leader,
+            &mut world,
+            &sender,
+            &guilds,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            membership(&world, member),
+            (guild, Some(GuildLevel::FirstCommander))
+        );
+        let character = repository
+            .account_repository()
+            .fetch_character(account_id, 0)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(character.guild_level, Some(GuildLevel::FirstCommander));
+
+        run("/guild kick Player2", leader, &mut world, &sender, &guilds)
+            .await
+            .unwrap();
+        assert_eq!(membership(&world, member), (None, None));
+        let character = repository
+            .account_repository()
+            .fetch_character(account_id, 0)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(character.guild, None);
+        assert_eq!(
+            run("/guild kick Player2", leader, &mut world, &sender, &guilds).await,
+            Err(GuildError::NotMember("Player2".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn promote_and_kick_reach_offline_members() {
+        let repository = TestAccountRepository::new().await;
+        let guilds = repository.guild_repository();
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let (leader, _) =
+            add_player(&repository, &mut world, 1, Position { x: 2100, y: 2100 }).await;
+        let (member, account_id) =
+            add_player(&repository, &mut world, 2, Position { x: 2500, y: 2500 }).await;
+        run("/guild create Odin", leader, &mut world, &sender, &guilds)
+            .await
+            .unwrap();
+        let (guild, _) = membership(&world, leader);
+        run(
+            "/guild invite Player2",
+            leader,
+            &mut world,
+            &sender,
+            &guilds,
+        )
+        .await
+        .unwrap();
+        run("/guild accept", member, &mut world, &sender, &guilds)
+            .await
+            .unwrap();
+        world.remove_entity(member).unwrap();
+        let fetch_member = async || {
+            repository
+                .account_repository()
+                .fetch_character(account_id, 0)
+                .await
+                .unwrap()
+                .unwrap()
+        };
+
+        run(
+            "/guild promote player2 3",
+            leader,
+            &mut world,
+            &sender,
+            &guilds,
+        )
+        .await
+        .unwrap();
+        let character = fetch_member().await;
+        assert_eq!(character.guild, guild);
+        assert_eq!(character.guild_level, Some(GuildLevel::ThirdCommander));
+
+        run("/guild kick Player2", leader, &mut world, &sender, &guilds)
+            .await
+            .unwrap();
+        let character = fetch_member().await;
+        assert_eq!(character.guild, None);
+        assert_eq!(character.guild_level, None);
+        assert_eq!(
+            run("/guild kick Player2", leader, &mut world, &sender, &guilds).await,
+            Err(GuildError::NotMember("Player2".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn disband_removes_every_member_and_notice_reaches_them() {
+        let repository = TestAccountRepository::new().await;
+        let guilds = repository.guild_repository();
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let (leader, _) =
+            add_player(&repository, &mut world, 1, Position { x: 2100, y: 2100 }).await;
+        let (member, _) =
+            add_player(&repository, &mut world, 2, Position { x: 2500, y: 2500 }).await;
+        run("/guild create Odin", leader, &mut world, &sender, &guilds)
+            .await
+            .unwrap();
+        run(
+            "/guild invite Player2",
+            leader,
+            &mut world,
+            &sender,
+            &guilds,
+        )
+        .await
+        .unwrap();
+        run("/guild accept", member, &mut world, &sender, &guilds)
+            .await
+            .unwrap();
+        let (guild, _) = membership(&world, leader);
+
+        assert_eq!(
+            run("/guild notice hi", member, &mut world, &sender, &guilds).await,
+            Err(GuildError::NotAllowed)
+        );
+        let sender = MockPacketSender::default();
+        run(
+            "/guild notice Meet at Armia",
+            leader,
+            &mut world,
+            &sender,
+            &guilds,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            identifiers(&sender, member),
+            vec![ServerMessage::MessagePanel]
+        );
+        let sender = MockPacketSender::default();
+        send_guild_notice(member, &world, &sender, &guilds)
+            .await
+            .unwrap();
+        assert_eq!(
+            identifiers(&sender, member),
+            vec![ServerMessage::MessagePanel]
+        );
+
+        assert_eq!(
+            run("/guild leave", leader, &mut world, &sender, &guilds).await,
+            Err(GuildError::NotAllowed)
+        );
+        run("/guild disband", leader, &mut world, &sender, &guilds)
+            .await
+            .unwrap();
+        assert_eq!(membership(&world, leader), (None, None));
+        assert_eq!(membership(&world, member), (None, None));
+        assert_eq!(guilds.fetch_guild(guild.unwrap()).await.unwrap(), None);
+    }
+}
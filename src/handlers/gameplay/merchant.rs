@@ -0,0 +1,652 @@
+use crate::configuration::MerchantConfig;
+use crate::map::EntityId;
+use crate::npc::{MERCHANT_ITEMS, MERCHANT_RANGE, MERCHANT_STORAGE, Npc};
+use crate::packets::ToUpdateEtc;
+use crate::session::{PacketSender, SessionError};
+use crate::world::{Mob, World};
+use odin_models::{MAX_COIN, MAX_INVENTORY_VISIBLE, item::Item, npc_mob::NpcMob};
+use odin_networking::{
+    WritableResourceError,
+    messages::{
+        client::{
+            open_merchant::OpenMerchantRaw,
+            shop::{BuyItemRaw, SellItemRaw},
+        },
+        server::{
+            create_item::{CreateItem, SlotType},
+            shop_list::{MAX_SHOP_ITEMS, ShopList, ShopType},
+            update_storage_coin::UpdateStorageCoin,
+        },
+    },
+};
+
+/// Percentage added on top of `ItemData::price` when buying from `merchant`.
+/// The configured flat rate implements it until cities collect their own tax.
+pub trait TaxPolicy {
+    fn tax_rate(&self, merchant: EntityId) -> u32;
+}
+
+impl TaxPolicy for MerchantConfig {
+    fn tax_rate(&self, _merchant: EntityId) -> u32 {
+        self.tax
+    }
+}
+
+/// Items a merchant sells, in the order the shop list shows them.
+pub fn shop_items(template: &NpcMob) -> Vec<Item> {
+    template
+        .inventory
+        .iter()
+        .map(|(_, item)| *item)
+        .take(MAX_SHOP_ITEMS)
+        .collect()
+}
+
+/// The merchant `target`, provided the living player `entity_id` stands
+/// within its reach.
+fn reach_merchant(
+    world: &World,
+    entity_id: EntityId,
+    target: EntityId,
+) -> Result<&Npc, OpenMerchantError> {
+    let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+        return Err(OpenMerchantError::PlayerNotFound);
+    };
+    if player.computed.score.hp == 0 {
+        return Err(OpenMerchantError::Dead);
+    }
+    let npc = world
+        .get_npc(target)
+        .ok_or(OpenMerchantError::MerchantNotFound)?;
+
+    let (Some(position), Some(npc_position)) = (
+        world.map().get_position(entity_id),
+        world.map().get_position(target),
+    ) else {
+        return Err(OpenMerchantError::MerchantNotFound);
+    };
+    if position.chebyshev_distance(npc_position) > MERCHANT_RANGE {
+        return Err(OpenMerchantError::OutOfRange);
+    }
+    Ok(npc)
+}
+
+#[derive(Debug)]
+pub struct OpenMerchant {
+    pub target: EntityId,
+}
+
+impl OpenMerchant {
+    pub fn handle<P: PacketSender>(
+        &self,
+        entity_id: EntityId,
+        world: &World,
+        sender: &P,
+        tax: &impl TaxPolicy,
+    ) -> Result<(), OpenMerchantError> {
+        let npc = reach_merchant(world, entity_id, self.target)?;
+        let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+            return Err(OpenMerchantError::PlayerNotFound);
+        };
+
+        match npc.template.merchant {
+            MERCHANT_ITEMS => {
+                sender.send_to(
+                    entity_id,
+                    ShopList {
+                        merchant_id: self.target.id() as u16,
+                        shop_type: ShopType::Items,
+                        items: shop_items(&npc.template),
+                        tax: tax.tax_rate(self.target) as i32,
+                    },
+                )?;
+                Ok(())
+            }
+            MERCHANT_STORAGE => {
+                sender.send_to(
+                    entity_id,
+                    ShopList {
+                        merchant_id: self.target.id() as u16,
+                        shop_type: ShopType::Storage,
+                        items: vec![],
+                        tax: 0,
+                    },
+                )?;
+                sender.send_to(
+                    entity_id,
+                    UpdateStorageCoin {
+                        coin: player.storage.coin,
+                    },
+                )?;
+                Ok(())
+            }
+            _ => Err(OpenMerchantError::NotAMerchant),
+        }
+    }
+}
+
+impl TryFrom<OpenMerchantRaw> for OpenMerchant {
+    type Error = WritableResourceError;
+
+    fn try_from(value: OpenMerchantRaw) -> Result<Self, Self::Error> {
+        Ok(OpenMerchant {
+            target: EntityId::from_id(value.target_id as usize),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct BuyItem {
+    pub target: EntityId,
+    pub shop_slot: usize,
+}
+
+impl BuyItem {
+    pub fn handle<P: PacketSender>(
+        &self,
+        entity_id: EntityId,
+        world: &mut World,
+        sender: &P,
+        tax: &impl TaxPolicy,
+    ) -> Result<(), BuyItemError> {
+        let npc = reach_merchant(world, entity_id, self.target)?;
+        if npc.template.merchant != MERCHANT_ITEMS {
+            return Err(OpenMerchantError::NotAMerchant.into());
+        }
+        let item = *shop_items(&npc.template)
+            .get(self.shop_slot)
+            .ok_or(BuyItemError::NotForSale)?;
+        let price = world
+            .item_db()
+            .get(item.id)
+            .map(|data| data.price)
+            .filter(|price| *price > 0)
+            .ok_or(BuyItemError::NotForSale)? as i64;
+        let cost = price + price * tax.tax_rate(self.target) as i64 / 100;
+
+        let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) else {
+            return Err(OpenMerchantError::PlayerNotFound.into());
+        };
+        if (player.coin as i64) < cost {
+            return Err(BuyItemError::NotEnoughCoin);
+        }
+        let slot = player
+            .inventory
+            .first_empty()
+            .ok_or(BuyItemError::InventoryFull)?;
+
+        player.coin -= cost as i32;
+        player.inventory.set(slot, item);
+        sender.send_to(
+            entity_id,
+            CreateItem {
+                mob_id: entity_id.id() as u16,
+                slot_type: SlotType::Inventory,
+                slot: slot as u16,
+                item: Some(item),
+            },
+        )?;
+        sender.send_to(entity_id, player.to_update_etc())?;
+        Ok(())
+    }
+}
+
+impl TryFrom<BuyItemRaw> for BuyItem {
+    type Error = WritableResourceError;
+
+    fn try_from(value: BuyItemRaw) -> Result<Self, Self::Error> {
+        let shop_slot = usize::try_from(value.shop_slot)
+            .ok()
+            .filter(|slot| *slot < MAX_SHOP_ITEMS)
+            .ok_or_else(|| WritableResourceError::Generic("Invalid shop slot".to_string()))?;
+        Ok(BuyItem {
+            target: EntityId::from_id(value.target_id as usize),
+            shop_slot,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct SellItem {
+    pub target: EntityId,
+    pub slot_type: SlotType,
+    pub slot: usize,
+}
+
+impl SellItem {
+    /// Sells the item back for `sell_ratio` percent of its price. Equipped
+    /// items must be moved to the inventory first.
+    pub fn handle<P: PacketSender>(
+        &self,
+        entity_id: EntityId,
+        world: &mut World,
+        sender: &P,
+        sell_ratio: u32,
+    ) -> Result<(), SellItemError> {
+        let npc = reach_merchant(world, entity_id, self.target)?;
+        if npc.template.merchant != MERCHANT_ITEMS {
+            return Err(OpenMerchantError::NotAMerchant.into());
+        }
+        if self.slot_type != SlotType::Inventory {
+            return Err(SellItemError::Equipped);
+        }
+        let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+            return Err(OpenMerchantError::PlayerNotFound.into());
+        };
+        let item = player
+            .inventory
+            .get(self.slot)
+            .ok_or(SellItemError::EmptySlot)?;
+        let price = world
+            .item_db()
+            .get(item.id)
+            .map(|data| data.price)
+            .filter(|price| *price > 0)
+            .ok_or(SellItemError::NotSellable)? as i64;
+        let value = price * sell_ratio as i64 / 100;
+        if player.coin as i64 + value > MAX_COIN as i64 {
+            return Err(SellItemError::CoinLimit);
+        }
+
+        let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) else {
+            return Err(OpenMerchantError::PlayerNotFound.into());
+        };
+        player.inventory.take(self.slot);
+        player.coin += value as i32;
+        sender.send_to(
+            entity_id,
+            CreateItem {
+                mob_id: entity_id.id() as u16,
+                slot_type: SlotType::Inventory,
+                slot: self.slot as u16,
+                item: None,
+            },
+        )?;
+        sender.send_to(entity_id, player.to_update_etc())?;
+        Ok(())
+    }
+}
+
+impl TryFrom<SellItemRaw> for SellItem {
+    type Error = WritableResourceError;
+
+    fn try_from(value: SellItemRaw) -> Result<Self, Self::Error> {
+        let slot_type = match value.slot_type {
+            0 => SlotType::Equipment,
+            1 => SlotType::Inventory,
+            _ => {
+                return Err(WritableResourceError::Generic(
+                    "Only equipment and inventory items can be sold".to_string(),
+                ));
+            }
+        };
+        let slot = usize::try_from(value.slot)
+            .ok()
+            .filter(|slot| *slot < MAX_INVENTORY_VISIBLE)
+            .ok_or_else(|| WritableResourceError::Generic("Invalid item slot".to_string()))?;
+        Ok(SellItem {
+            target: EntityId::from_id(value.target_id as usize),
+            slot_type,
+            slot,
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum OpenMerchantError {
+    #[error("Player not found in world")]
+    PlayerNotFound,
+
+    #[error("Player is dead")]
+    Dead,
+
+    #[error("Merchant not found in world")]
+    MerchantNotFound,
+
+    #[error("Merchant is out of range")]
+    OutOfRange,
+
+    #[error("Target is not a merchant")]
+    NotAMerchant,
+
+    #[error(transparent)]
+    Session(#[from] SessionError),
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum BuyItemError {
+    #[error("Item is not for sale")]
+    NotForSale,
+
+    #[error("Not enough coin")]
+    NotEnoughCoin,
+
+    #[error("Inventory is full")]
+    InventoryFull,
+
+    #[error(transparent)]
+    Merchant(#[from] OpenMerchantError),
+
+    #[error(transparent)]
+    Session(#[from] SessionError),
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum SellItemError {
+    #[error("Equipped items can't be sold")]
+    Equipped,
+
+    #[error("Inventory slot is empty")]
+    EmptySlot,
+
+    #[error("Item can't be sold")]
+    NotSellable,
+
+    #[error("Coin limit reached")]
+    CoinLimit,
+
+    #[error(transparent)]
+    Merchant(#[from] OpenMerchantError),
+
+    #[error(transparent)]
+    Session(#[from] SessionError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::tests::MockPacketSender;
+    use crate::npc::{
+        Npc,
+        movement::{MovementBehavior, MovementState},
+    };
+    use crate::world::Player;
+    use odin_models::{
+        EquipmentSlot, InventorySlots,
+        character::Character,
+        item_data::{ItemData, ItemDataEffect, ItemDatabase, MAX_ITEM_DATA_EFFECTS},
+        position::Position,
+    };
+    use odin_networking::messages::ServerMessage;
+
+    const SWORD: u16 = 100;
+    const QUEST_ITEM: u16 = 101;
+
+    fn item_data(id: u16, price: i32) -> ItemData {
+        ItemData {
+            id,
+            name: "Test".to_string(),
+            mesh: (0, 0),
+            level: 0,
+            str_req: 0,
+            int_req: 0,
+            dex_req: 0,
+            con_req: 0,
+            effects: [ItemDataEffect::default(); MAX_ITEM_DATA_EFFECTS],
+            price,
+            unique: 0,
+            pos: 0,
+            extreme: 0,
+            grade: 0,
+        }
+    }
+
+    fn setup(merchant: i16, npc_position: Position) -> (World, EntityId, EntityId) {
+        let mut world = World::new(ItemDatabase::from_items([
+            item_data(SWORD, 1000),
+            item_data(QUEST_ITEM, 0),
+        ]));
+        let entity_id = EntityId::Player(1);
+        let mut player = Player::from_character(
+            entity_id,
+            Character {
+                coin: 1000,
+                ..Default::default()
+            },
+        );
+        player.computed.score.hp = 100;
+        player.storage.coin = 500;
+        world
+            .add_player(entity_id, player, Position { x: 2100, y: 2100 })
+            .unwrap();
+
+        let npc_id = EntityId::Mob(1000);
+        let mut inventory = InventorySlots::default();
+        inventory.set(3, Item::from(SWORD));
+        let template = NpcMob {
+            name: "Keeper".to_string(),
+            merchant,
+            inventory,
+            ..Default::default()
+        };
+        let movement = MovementState::new(MovementBehavior::Stationary, 1);
+        world
+            .add_npc(npc_id, Npc::new(npc_id, template, movement), npc_position)
+            .unwrap();
+        (world, entity_id, npc_id)
+    }
+
+    #[test]
+    fn opening_storage_sends_storage_coin() {
+        let (world, entity_id, npc_id) = setup(MERCHANT_STORAGE, Position { x: 2102, y: 2102 });
+        let sender = MockPacketSender::default();
+
+        OpenMerchant { target: npc_id }
+            .handle(entity_id, &world, &sender, &MerchantConfig::default())
+            .unwrap();
+
+        let identifiers: Vec<_> = sender
+            .messages_for(entity_id)
+            .iter()
+            .map(|packet| packet.identifier)
+            .collect();
+        assert_eq!(
+            identifiers,
+            vec![ServerMessage::ShopList, ServerMessage::UpdateStorageCoin]
+        );
+    }
+
+    #[test]
+    fn rejects_far_or_non_merchant_npcs() {
+        let sender = MockPacketSender::default();
+
+        let (world, entity_id, npc_id) = setup(MERCHANT_STORAGE, Position { x: 2110, y: 2110 });
+        assert_eq!(
+            OpenMerchant { target: npc_id }.handle(
+                entity_id,
+                &world,
+                &sender,
+                &MerchantConfig::default()
+            ),
+            Err(OpenMerchantError::OutOfRange)
+        );
+
+        let (world, entity_id, npc_id) = setup(0, Position { x: 2101, y: 2101 });
+        assert_eq!(
+            OpenMerchant { target: npc_id }.handle(
+                entity_id,
+                &world,
+                &sender,
+                &MerchantConfig::default()
+            ),
+            Err(OpenMerchantError::NotAMerchant)
+        );
+        assert!(sender.messages_for(entity_id).is_empty());
+    }
+
+    fn player(world: &World, entity_id: EntityId) -> &Player {
+        let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+            panic!("expected Player");
+        };
+        player
+    }
+
+    fn buy(target: EntityId) -> BuyItem {
+        BuyItem {
+            target,
+            shop_slot: 0,
+        }
+    }
+
+    fn sell(target: EntityId, slot_type: SlotType, slot: usize) -> SellItem {
+        SellItem {
+            target,
+            slot_type,
+            slot,
+        }
+    }
+
+    #[test]
+    fn opening_shop_lists_template_items_with_tax() {
+        let (world, entity_id, npc_id) = setup(MERCHANT_ITEMS, Position { x: 2102, y: 2102 });
+        let sender = MockPacketSender::default();
+
+        OpenMerchant { target: npc_id }
+            .handle(
+                entity_id,
+                &world,
+                &sender,
+                &MerchantConfig {
+                    tax: 10,
+                    sell_ratio: 25,
+                },
+            )
+            .unwrap();
+
+        let packets = sender.messages_for(entity_id);
+        assert_eq!(packets.len(), 1);
+        assert_eq!(packets[0].identifier, ServerMessage::ShopList);
+        assert_eq!(
+            shop_items(&world.get_npc(npc_id).unwrap().template),
+            vec![Item::from(SWORD)]
+        );
+    }
+
+    #[test]
+    fn buying_charges_price_plus_tax() {
+        let (mut world, entity_id, npc_id) = setup(MERCHANT_ITEMS, Position { x: 2102, y: 2102 });
+        let sender = MockPacketSender::default();
+        let config = MerchantConfig {
+            tax: 10,
+            sell_ratio: 25,
+        };
+        if let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) {
+            player.coin = 1150;
+        }
+
+        buy(npc_id)
+            .handle(entity_id, &mut world, &sender, &config)
+            .unwrap();
+
+        assert_eq!(player(&world, entity_id).coin, 50);
+        assert_eq!(
+            player(&world, entity_id).inventory.get(0),
+            Some(&Item::from(SWORD))
+        );
+        let identifiers: Vec<_> = sender
+            .messages_for(entity_id)
+            .iter()
+            .map(|packet| packet.identifier)
+            .collect();
+        assert_eq!(
+            identifiers,
+            vec![ServerMessage::CreateItem, ServerMessage::UpdateEtc]
+        );
+    }
+
+    #[test]
+    fn buying_without_enough_coin_fails() {
+        let (mut world, entity_id, npc_id) = setup(MERCHANT_ITEMS, Position { x: 2102, y: 2102 });
+        let sender = MockPacketSender::default();
+        if let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) {
+            player.coin = 999;
+        }
+
+        assert_eq!(
+            buy(npc_id).handle(entity_id, &mut world, &sender, &MerchantConfig::default()),
+            Err(BuyItemError::NotEnoughCoin)
+        );
+        assert_eq!(player(&world, entity_id).coin, 999);
+        assert!(player(&world, entity_id).inventory.get(0).is_none());
+        assert!(sender.messages_for(entity_id).is_empty());
+    }
+
+    #[test]
+    fn buying_with_full_inventory_fails() {
+        let (mut world, entity_id, npc_id) = setup(MERCHANT_ITEMS, Position { x: 2102, y: 2102 });
+        let sender = MockPacketSender::default();
+        if let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) {
+            for slot in 0..MAX_INVENTORY_VISIBLE {
+                player.inventory.set(slot, Item::from(QUEST_ITEM));
+            }
+        }
+
+        assert_eq!(
+            buy(npc_id).handle(entity_id, &mut world, &sender, &MerchantConfig::default()),
+            Err(BuyItemError::InventoryFull)
+        );
+        assert_eq!(player(&world, entity_id).coin, 1000);
+    }
+
+    #[test]
+    fn buying_from_storage_keeper_fails() {
+        let (mut world, entity_id, npc_id) = setup(MERCHANT_STORAGE, Position { x: 2102, y: 2102 });
+
+        assert_eq!(
+            buy(npc_id).handle(
+                entity_id,
+                &mut world,
+                &MockPacketSender::default(),
+                &MerchantConfig::default()
+            ),
+            Err(BuyItemError::Merchant(OpenMerchantError::NotAMerchant))
+        );
+    }
+
+    #[test]
+    fn selling_pays_back_sell_ratio() {
+        let (mut world, entity_id, npc_id) = setup(MERCHANT_ITEMS, Position { x: 2102, y: 2102 });
+        let sender = MockPacketSender::default();
+        if let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) {
+            player.inventory.set(5, Item::from(SWORD));
+            player.inventory.set(6, Item::from(QUEST_ITEM));
+        }
+
+        sell(npc_id, SlotType::Inventory, 5)
+            .handle(entity_id, &mut world, &sender, 25)
+            .unwrap();
+
+        assert_eq!(player(&world, entity_id).coin, 1250);
+        assert!(player(&world, entity_id).inventory.get(5).is_none());
+        assert_eq!(
+            sell(npc_id, SlotType::Inventory, 6).handle(entity_id, &mut world, &sender, 25),
+            Err(SellItemError::NotSellable)
+        );
+        assert_eq!(
+            sell(npc_id, SlotType::Inventory, 5).handle(entity_id, &mut world, &sender, 25),
+            Err(SellItemError::EmptySlot)
+        );
+    }
+
+    #[test]
+    fn selling_equipped_items_fails() {
+        let (mut world, entity_id, npc_id) = setup(MERCHANT_ITEMS, Position { x: 2102, y: 2102 });
+        let sender = MockPacketSender::default();
+        let slot = EquipmentSlot::RightWeapon;
+        if let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) {
+            player.equipments.set(slot, Item::from(SWORD));
+        }
+
+        assert_eq!(
+            sell(npc_id, SlotType::Equipment, slot.as_index())
+                .handle(entity_id, &mut world, &sender, 25),
+            Err(SellItemError::Equipped)
+        );
+        assert_eq!(
+            player(&world, entity_id).equipments.get(slot),
+            Some(&Item::from(SWORD))
+        );
+        assert_eq!(player(&world, entity_id).coin, 1000);
+        assert!(sender.messages_for(entity_id).is_empty());
+    }
+}
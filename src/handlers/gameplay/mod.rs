@@ -1,2 +1,15 @@
 pub mod action;
 pub mod apply_bonus;
+pub mod attack;
+pub mod chat;
+pub mod ground_item;
+pub mod guild;
+pub mod merchant;
+pub mod move_item;
+pub mod party;
+pub mod restart;
+pub mod skill;
+pub mod stall;
+pub mod storage;
+pub mod trade;
+pub mod use_item;
@@ -0,0 +1,555 @@
+use crate::map::EntityId;
+use crate::npc::MERCHANT_STORAGE;
+use crate::packets::{BroadcastUpdateScore, ToCreateMob};
+use crate::session::{PacketSender, SessionError};
+use crate::world::{Mob, Player, World};
+use odin_models::{
+    EquipmentSlot, MAX_EQUIPS, MAX_INVENTORY_VISIBLE, MAX_STORAGE_ITEMS, effect::Effect,
+    item::Item, item_data::ItemData,
+};
+use odin_networking::{
+    WritableResourceError,
+    messages::{
+        client::move_item::MoveItemRaw,
+        server::create_item::{CreateItem, SlotType},
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ItemLocation {
+    pub slot_type: SlotType,
+    pub slot: usize,
+}
+
+#[derive(Debug)]
+pub struct MoveItem {
+    pub source: ItemLocation,
+    pub destination: ItemLocation,
+}
+
+impl MoveItem {
+    pub fn handle<P: PacketSender>(
+        &self,
+        entity_id: EntityId,
+        world: &mut World,
+        sender: &P,
+    ) -> Result<(), MoveItemError> {
+        if self.source == self.destination {
+            return Err(MoveItemError::SameSlot);
+        }
+        let uses_storage = [self.source, self.destination]
+            .iter()
+            .any(|location| location.slot_type == SlotType::Storage);
+        if uses_storage && !world.merchant_in_reach(entity_id, MERCHANT_STORAGE) {
+            return Err(MoveItemError::StorageOutOfReach);
+        }
+
+        {
+            let item_db = world.item_db();
+            let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+                return Err(MoveItemError::PlayerNotFound);
+            };
+            if player.computed.score.hp == 0 {
+                return Err(MoveItemError::Dead);
+            }
+
+            let source_item = get(player, self.source)?.ok_or(MoveItemError::EmptySource)?;
+            let destination_item = get(player, self.destination)?;
+
+            for (item, location) in [
+                (Some(source_item), self.destination),
+                (destination_item, self.source),
+            ] {
+                let (Some(item), SlotType::Equipment) = (item, location.slot_type) else {
+                    continue;
+                };
+                let data = item_db
+                    .get(item.id)
+                    .ok_or(MoveItemError::UnknownItem(item.id))?;
+                check_requirements(player, data, location.slot)?;
+            }
+        }
+
+        let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) else {
+            return Err(MoveItemError::PlayerNotFound);
+        };
+        let source_item = take(player, self.source);
+        let destination_item = take(player, self.destination);
+        put(player, self.destination, source_item);
+        put(player, self.source, destination_item);
+
+        for (location, item) in [
+            (self.source, destination_item),
+            (self.destination, source_item),
+        ] {
+            sender.send_to(
+                entity_id,
+                CreateItem {
+                    mob_id: entity_id.id() as u16,
+                    slot_type: location.slot_type,
+                    slot: location.slot as u16,
+                    item,
+                },
+            )?;
+        }
+
+        let equipment_changed = [self.source, self.destination]
+            .iter()
+            .any(|location| location.slot_type == SlotType::Equipment);
+        if equipment_changed {
+            world.recalculate_score(entity_id);
+            broadcast_appearance(world, entity_id, sender)?;
+            world.broadcast_update_score(entity_id, sender)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn check_requirements(player: &Player, data: &ItemData, slot: usize) -> Result<(), MoveItemError> {
+    if data.pos & (1 << slot) == 0 {
+        return Err(MoveItemError::WrongSlot);
+    }
+
+    let score = &player.score;
+    if (score.level as i32) < data.level as i32
+        || (score.strength as i32) < data.str_req as i32
+        || (score.intelligence as i32) < data.int_req as i32
+        || (score.dexterity as i32) < data.dex_req as i32
+        || (score.constitution as i32) < data.con_req as i32
+    {
+        return Err(MoveItemError::RequirementsNotMet);
+    }
+
+    let class_mask = data
+        .effects
+        .iter()
+        .find(|effect| effect.index == Effect::Class as u8)
+        .map(|effect| effect.value as i32)
+        .unwrap_or(0);
+    if class_mask != 0 && class_mask & (1 << i32::from(player.class)) == 0 {
+        return Err(MoveItemError::WrongClass);
+    }
+
+    Ok(())
+}
+
+fn get(player: &Player, location: ItemLocation) -> Result<Option<Item>, MoveItemError> {
+    Ok(match location.slot_type {
+        SlotType::Equipment => player.equipments.get(equipment_slot(location.slot)?),
+        SlotType::Inventory => player.inventory.get(location.slot),
+        SlotType::Storage => player.storage.items.get(location.slot),
+    }
+    .copied())
+}
+
+fn take(player: &mut Player, location: ItemLocation) -> Option<Item> {
+    match location.slot_type {
+        SlotType::Equipment => player
+            .equipments
+            .take(EquipmentSlot::try_from(location.slot).ok()?),
+        SlotType::Inventory => player.inventory.take(location.slot),
+        SlotType::Storage => player.storage.items.take(location.slot),
+    }
+}
+
+fn put(player: &mut Player, location: ItemLocation, item: Option<Item>) {
+    let Some(item) = item else {
+        return;
+    };
+    match location.slot_type {
+        SlotType::Equipment => {
+            if let Ok(slot) = EquipmentSlot::try_from(location.slot) {
+                player.equipments.set(slot, item);
+            }
+        }
+        SlotType::Inventory => player.inventory.set(location.slot, item),
+        SlotType::Storage => player.storage.items.set(location.slot, item),
+    }
+}
+
+fn equipment_slot(slot: usize) -> Result<EquipmentSlot, MoveItemError> {
+    EquipmentSlot::try_from(slot).map_err(|_| MoveItemError::InvalidSlot)
+}
+
+/// Sends the mob's `CreateMob` again to its spectators, e.g. after its
+/// equipment or guild changed.
+pub fn broadcast_appearance<P: PacketSender>(
+    world: &World,
+    entity_id: EntityId,
+    sender: &P,
+) -> Result<(), SessionError> {
+    let (Some(mob), Some(position)) = (
+        world.get_mob(entity_id),
+        world.map().get_position(entity_id),
+    ) else {
+        return Ok(());
+    };
+    if mob.is_invisible() {
+        return Ok(());
+    }
+    for spectator in world.map().get_spectators(position, entity_id) {
+        mob.send_create_mob(spectator, position, sender)?;
+    }
+    Ok(())
+}
+
+fn location(slot_type: i8, slot: i8) -> Result<ItemLocation, WritableResourceError> {
+    let (slot_type, max) = match slot_type {
+        0 => (SlotType::Equipment, MAX_EQUIPS),
+        1 => (SlotType::Inventory, MAX_INVENTORY_VISIBLE),
+        2 => (SlotType::Storage, MAX_STORAGE_ITEMS),
+        _ => {
+            return Err(WritableResourceError::Generic(
+                "Invalid item slot type".to_string(),
+            ));
+        }
+    };
+    let slot = slot as u8 as usize;
+    if slot >= max {
+        return Err(WritableResourceError::Generic(
+            "Invalid item slot".to_string(),
+        ));
+    }
+    Ok(ItemLocation { slot_type, slot })
+}
+
+impl TryFrom<MoveItemRaw> for MoveItem {
+    type Error = WritableResourceError;
+
+    fn try_from(value: MoveItemRaw) -> Result<Self, Self::Error> {
+        Ok(MoveItem {
+            source: location(value.src_type, value.src_slot)?,
+            destination: location(value.dest_type, value.dest_slot)?,
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum MoveItemError {
+    #[error("Player not found in world")]
+    PlayerNotFound,
+
+    #[error("Player is dead")]
+    Dead,
+
+    #[error("Source and destination are the same slot")]
+    SameSlot,
+
+    #[error("Invalid item slot")]
+    InvalidSlot,
+
+    #[error("Source slot is empty")]
+    EmptySource,
+
+    #[error("Item {0} has no item data")]
+    UnknownItem(u16),
+
+    #[error("Item cannot be equipped in this slot")]
+    WrongSlot,
+
+    #[error("Item requirements not met")]
+    RequirementsNotMet,
+
+    #[error("Item cannot be used by this class")]
+    WrongClass,
+
+    #[error("No storage keeper in reach")]
+    StorageOutOfReach,
+
+    #[error(transparent)]
+    Session(#[from] SessionError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::tests::MockPacketSender;
+    use crate::npc::{
+        Npc,
+        movement::{MovementBehavior, MovementState},
+    };
+    use odin_models::{
+        character::{Character, Class},
+        item_data::{ItemDataEffect, ItemDatabase, MAX_ITEM_DATA_EFFECTS},
+        npc_mob::NpcMob,
+        position::Position,
+        status::Score,
+    };
+    use odin_networking::messages::ServerMessage;
+
+    const SWORD: u16 = 100;
+    const STAFF: u16 = 200;
+
+    fn item_data(id: u16, pos: i32, level: i16, class_mask: i16) -> ItemData {
+        let mut effects = [ItemDataEffect::default(); MAX_ITEM_DATA_EFFECTS];
+        effects[0] = ItemDataEffect {
+            index: Effect::Damage as u8,
+            value: 50,
+        };
+        effects[1] = ItemDataEffect {
+            index: Effect::Class as u8,
+            value: class_mask,
+        };
+        ItemData {
+            id,
+            name: format!("Item{}", id),
+            mesh: (0, 0),
+            level,
+            str_req: 0,
+            int_req: 0,
+            dex_req: 0,
+            con_req: 0,
+            effects,
+            price: 0,
+            unique: 0,
+            pos,
+            extreme: 0,
+            grade: 0,
+        }
+    }
+
+    fn setup() -> (World, EntityId) {
+        let weapon_pos =
+            (1 << EquipmentSlot::LeftWeapon as i32) | (1 << EquipmentSlot::RightWeapon as i32);
+        let mut world = World::new(ItemDatabase::from_items([
+            item_data(SWORD, weapon_pos, 10, 1),
+            item_data(STAFF, weapon_pos, 50, 2),
+        ]));
+        let entity_id = add_player(&mut world, 1, Position { x: 2100, y: 2100 });
+        if let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) {
+            player.inventory.set(0, Item::from(SWORD));
+            player.inventory.set(1, Item::from(STAFF));
+        }
+        (world, entity_id)
+    }
+
+    fn add_player(world: &mut World, client_id: usize, position: Position) -> EntityId {
+        let entity_id = EntityId::Player(client_id);
+        let mut player = Player::from_character(
+            entity_id,
+            Character {
+                name: format!("Player{}", client_id),
+                class: Class::TransKnight,
+                score: Score {
+                    level: 20,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+        player.computed.score.hp = 100;
+        world.add_player(entity_id, player, position).unwrap();
+        entity_id
+    }
+
+    fn inventory(slot: usize) -> ItemLocation {
+        ItemLocation {
+            slot_type: SlotType::Inventory,
+            slot,
+        }
+    }
+
+    fn equipment(slot: EquipmentSlot) -> ItemLocation {
+        ItemLocation {
+            slot_type: SlotType::Equipment,
+            slot: slot.as_index(),
+        }
+    }
+
+    fn player(world: &World, entity_id: EntityId) -> &Player {
+        match world.get_mob(entity_id) {
+            Some(Mob::Player(player)) => player,
+            _ => panic!("player not found"),
+        }
+    }
+
+    #[test]
+    fn equipping_recalculates_and_broadcasts() {
+        let (mut world, entity_id) = setup();
+        let spectator = add_player(&mut world, 2, Position { x: 2105, y: 2105 });
+        let sender = MockPacketSender::default();
+        world.recalculate_score(entity_id);
+        let damage = player(&world, entity_id).computed.score.damage;
+
+        MoveItem {
+            source: inventory(0),
+            destination: equipment(EquipmentSlot::LeftWeapon),
+        }
+        .handle(entity_id, &mut world, &sender)
+        .unwrap();
+
+        let player = player(&world, entity_id);
+        assert!(player.inventory.get(0).is_none());
+        assert_eq!(
+            player.equipments.get(EquipmentSlot::LeftWeapon).unwrap().id,
+            SWORD
+        );
+        assert_eq!(player.computed.score.damage, damage + 50);
+
+        let identifiers: Vec<_> = sender
+            .messages_for(entity_id)
+            .iter()
+            .map(|packet| packet.identifier)
+            .collect();
+        assert_eq!(
+            identifiers,
+            vec![
+                ServerMessage::CreateItem,
+                ServerMessage::CreateItem,
+                ServerMessage::UpdateScore
+            ]
+        );
+        let identifiers: Vec<_> = sender
+            .messages_for(spectator)
+            .iter()
+            .map(|packet| packet.identifier)
+            .collect();
+        assert_eq!(
+            identifiers,
+            vec![ServerMessage::CreateMob, ServerMessage::UpdateScore]
+        );
+    }
+
+    #[test]
+    fn unmet_requirements_are_rejected() {
+        let (mut world, entity_id) = setup();
+        let sender = MockPacketSender::default();
+
+        assert_eq!(
+            MoveItem {
+                source: inventory(1),
+                destination: equipment(EquipmentSlot::LeftWeapon),
+            }
+            .handle(entity_id, &mut world, &sender),
+            Err(MoveItemError::RequirementsNotMet)
+        );
+        if let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) {
+            player.score.level = 60;
+        }
+        assert_eq!(
+            MoveItem {
+                source: inventory(1),
+                destination: equipment(EquipmentSlot::LeftWeapon),
+            }
+            .handle(entity_id, &mut world, &sender),
+            Err(MoveItemError::WrongClass)
+        );
+        assert_eq!(
+            MoveItem {
+                source: inventory(0),
+                destination: equipment(EquipmentSlot::Helmet),
+            }
+            .handle(entity_id, &mut world, &sender),
+            Err(MoveItemError::WrongSlot)
+        );
+        assert!(sender.messages_for(entity_id).is_empty());
+        assert_eq!(
+            player(&world, entity_id).inventory.get(1).unwrap().id,
+            STAFF
+        );
+    }
+
+    #[test]
+    fn moving_between_inventory_and_storage_swaps() {
+        let (mut world, entity_id) = setup();
+        let sender = MockPacketSender::default();
+        let storage = ItemLocation {
+            slot_type: SlotType::Storage,
+            slot: 5,
+        };
+        assert_eq!(
+            MoveItem {
+                source: inventory(0),
+                destination: storage,
+            }
+            .handle(entity_id, &mut world, &sender),
+            Err(MoveItemError::StorageOutOfReach)
+        );
+
+        let keeper = EntityId::Mob(1000);
+        let template = NpcMob {
+            merchant: MERCHANT_STORAGE,
+            ..Default::default()
+        };
+        let movement = MovementState::new(MovementBehavior::Stationary, 1);
+        world
+            .add_npc(
+                keeper,
+                Npc::new(keeper, template, movement),
+                Position { x: 2101, y: 2100 },
+            )
+            .unwrap();
+
+        MoveItem {
+            source: inventory(0),
+            destination: storage,
+        }
+        .handle(entity_id, &mut world, &sender)
+        .unwrap();
+        MoveItem {
+            source: inventory(1),
+            destination: storage,
+        }
+        .handle(entity_id, &mut world, &sender)
+        .unwrap();
+
+        let player = player(&world, entity_id);
+        assert_eq!(player.storage.items.get(5).unwrap().id, STAFF);
+        assert_eq!(player.inventory.get(1).unwrap().id, SWORD);
+        assert!(player.inventory.get(0).is_none());
+        assert!(
+            sender
+                .messages_for(entity_id)
+                .iter()
+                .all(|packet| packet.identifier == ServerMessage::CreateItem)
+        );
+    }
+
+    #[test]
+    fn unequipping_into_an_occupied_slot_checks_the_swapped_item() {
+        let (mut world, entity_id) = setup();
+        let sender = MockPacketSender::default();
+        MoveItem {
+            source: inventory(0),
+            destination: equipment(EquipmentSlot::LeftWeapon),
+        }
+        .handle(entity_id, &mut world, &sender)
+        .unwrap();
+
+        assert_eq!(
+            MoveItem {
+                source: equipment(EquipmentSlot::LeftWeapon),
+                destination: inventory(1),
+            }
+            .handle(entity_id, &mut world, &sender),
+            Err(MoveItemError::RequirementsNotMet)
+        );
+        assert_eq!(
+            MoveItem {
+                source: inventory(5),
+                destination: inventory(6),
+            }
+            .handle(entity_id, &mut world, &sender),
+            Err(MoveItemError::EmptySource)
+        );
+    }
+
+    #[test]
+    fn raw_locations_are_validated() {
+        let raw = |src_type, src_slot| MoveItemRaw {
+            dest_type: 1,
+            dest_slot: 0,
+            src_type,
+            src_slot,
+            warp_id: 0,
+        };
+
+        let parsed = MoveItem::try_from(raw(0, 7)).unwrap();
+        assert_eq!(parsed.source, equipment(EquipmentSlot::RightWeapon));
+        assert_eq!(parsed.destination, inventory(0));
+        assert!(MoveItem::try_from(raw(0, 18)).is_err());
+        assert!(MoveItem::try_from(raw(3, 0)).is_err());
+        assert!(MoveItem::try_from(raw(1, -1)).is_err());
+    }
+}
@@ -0,0 +1,371 @@
+use crate::map::EntityId;
+use crate::party::{PartyDeparture, PartyError};
+use crate::session::PacketSender;
+use crate::world::{Mob, Player, World};
+use odin_networking::{
+    WritableResourceError,
+    messages::{
+        client::party::{AcceptPartyRaw, PartyInviteRaw, RemovePartyRaw},
+        server::party::{AddPartyMember, PartyInvite, PartyMemberPosition, RemovePartyMember},
+    },
+};
+
+fn player(world: &World, entity_id: EntityId) -> Result<&Player, PartyError> {
+    match world.get_mob(entity_id) {
+        Some(Mob::Player(player)) => Ok(player),
+        _ => Err(PartyError::PlayerNotFound),
+    }
+}
+
+fn add_party_member(leader: EntityId, member: &Player) -> AddPartyMember {
+    AddPartyMember {
+        leader_id: leader.id() as u16,
+        member_id: member.entity_id.id() as u16,
+        level: member.computed.score.level,
+        max_hp: member.computed.score.max_hp,
+        hp: member.computed.score.hp,
+        name: member.name.clone(),
+    }
+}
+
+#[derive(Debug)]
+pub struct InviteParty {
+    pub target: EntityId,
+}
+
+impl InviteParty {
+    pub fn handle<P: PacketSender>(
+        &self,
+        entity_id: EntityId,
+        world: &mut World,
+        sender: &P,
+    ) -> Result<(), PartyError> {
+        let leader = player(world, entity_id)?;
+        if leader.computed.score.hp == 0 {
+            return Err(PartyError::PlayerNotFound);
+        }
+        if !matches!(self.target, EntityId::Player(_)) {
+            return Err(PartyError::InvalidTarget);
+        }
+        player(world, self.target)?;
+        let invite = PartyInvite {
+            leader_id: entity_id.id() as u16,
+            level: leader.computed.score.level,
+            max_hp: leader.computed.score.max_hp,
+            hp: leader.computed.score.hp,
+            name: leader.name.clone(),
+            target_id: self.target.id() as u16,
+        };
+
+        world.parties_mut().invite(entity_id, self.target)?;
+        sender.send_to(self.target, invite)?;
+        Ok(())
+    }
+}
+
+impl TryFrom<PartyInviteRaw> for InviteParty {
+    type Error = WritableResourceError;
+
+    fn try_from(value: PartyInviteRaw) -> Result<Self, Self::Error> {
+        Ok(InviteParty {
+            target: EntityId::from_id(value.target_id as usize),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct AcceptParty {
+    pub leader: EntityId,
+}
+
+impl AcceptParty {
+    /// Joins the party and sends the member lists: the newcomer gets every
+    /// member, the others get the newcomer.
+    pub fn handle<P: PacketSender>(
+        &self,
+        entity_id: EntityId,
+        world: &mut World,
+        sender: &P,
+    ) -> Result<(), PartyError> {
+        player(world, entity_id)?;
+        player(world, self.leader)?;
+        let members = world
+            .parties_mut()
+            .accept(entity_id, self.leader)?
+            .members()
+            .to_vec();
+
+        let newcomer = add_party_member(self.leader, player(world, entity_id)?);
+        for &member in &members {
+            let Ok(member_player) = player(world, member) else {
+                continue;
+            };
+            sender.send_to(entity_id, add_party_member(self.leader, member_player))?;
+            if member != entity_id {
+                sender.send_to(member, newcomer.clone())?;
+            }
+        }
+        if members.len() == 2 {
+            let leader = player(world, self.leader)?;
+            sender.send_to(self.leader, add_party_member(self.leader, leader))?;
+        }
+
+        for member in world.party_members_out_of_view(entity_id) {
+            for (from, to) in [(member, entity_id), (entity_id, member)] {
+                if let Some(position) = world.map().get_position(from) {
+                    sender.send_to(
+                        to,
+                        PartyMemberPosition {
+                            member_id: from.id() as u16,
+                            position,
+                        },
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<AcceptPartyRaw> for AcceptParty {
+    type Error = WritableResourceError;
+
+    fn try_from(value: AcceptPartyRaw) -> Result<Self, Self::Error> {
+        Ok(AcceptParty {
+            leader: EntityId::from_id(value.leader_id as usize),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct RemoveParty {
+    pub target: EntityId,
+}
+
+impl RemoveParty {
+    pub fn handle<P: PacketSender>(
+        &self,
+        entity_id: EntityId,
+        world: &mut World,
+        sender: &P,
+    ) -> Result<(), PartyError> {
+        if self.target == entity_id {
+            return leave_party(world, entity_id, sender);
+        }
+        let departure = world.parties_mut().kick(entity_id, self.target)?;
+        notify_departure(sender, self.target, &departure)
+    }
+}
+
+impl TryFrom<RemovePartyRaw> for RemoveParty {
+    type Error = WritableResourceError;
+
+    fn try_from(value: RemovePartyRaw) -> Result<Self, Self::Error> {
+        Ok(RemoveParty {
+            target: EntityId::from_id(value.target_id as usize),
+        })
+    }
+}
+
+/// Takes the player out of its party, e.g. on disconnect, and tells every
+/// member who is gone.
+pub fn leave_party<P: PacketSender>(
+    world: &mut World,
+    entity_id: EntityId,
+    sender: &P,
+) -> Result<(), PartyError> {
+    let departure = world
+        .parties_mut()
+        .leave(entity_id)
+        .ok_or(PartyError::NotInParty)?;
+    notify_departure(sender, entity_id, &departure)
+}
+
+fn notify_departure<P: PacketSender>(
+    sender: &P,
+    departed: EntityId,
+    departure: &PartyDeparture,
+) -> Result<(), PartyError> {
+    let remove = |member: EntityId| RemovePartyMember {
+        member_id: member.id() as u16,
+    };
+    sender.send_to(departed, remove(departed))?;
+    match departure {
+        PartyDeparture::Left { remaining } => {
+            for &member in remaining {
+                sender.send_to(member, remove(departed))?;
+            }
+        }
+        PartyDeparture::Disbanded { remaining } => {
+            for &member in remaining {
+                sender.send_to(member, remove(departed))?;
+                for &other in remaining {
+                    sender.send_to(member, remove(other))?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::tests::MockPacketSender;
+    use crate::packets::BroadcastUpdateScore;
+    use odin_models::{character::Character, position::Position};
+    use odin_networking::messages::ServerMessage;
+
+    fn add_player(world: &mut World, client_id: usize, position: Position) -> EntityId {
+        let entity_id = EntityId::Player(client_id);
+        let mut player = Player::from_character(
+            entity_id,
+            Character {
+                name: format!("Player{client_id}"),
+                ..Default::default()
+            },
+        );
+        player.computed.score.hp = 100;
+        player.computed.score.max_hp = 100;
+        world.add_player(entity_id, player, position).unwrap();
+        entity_id
+    }
+
+    fn identifiers(sender: &MockPacketSender, entity_id: EntityId) -> Vec<ServerMessage> {
+        sender
+            .messages_for(entity_id)
+            .iter()
+            .map(|packet| packet.identifier)
+            .collect()
+    }
+
+    fn group(world: &mut World, leader: EntityId, member: EntityId) {
+        let sender = MockPacketSender::default();
+        InviteParty { target: member }
+            .handle(leader, world, &sender)
+            .unwrap();
+        AcceptParty { leader }
+            .handle(member, world, &sender)
+            .unwrap();
+    }
+
+    #[test]
+    fn invite_and_accept_exchange_member_lists() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let leader = add_player(&mut world, 1, Position { x: 2100, y: 2100 });
+        let member = add_player(&mut world, 2, Position { x: 2102, y: 2102 });
+
+        InviteParty { target: member }
+            .handle(leader, &mut world, &sender)
+            .unwrap();
+        assert_eq!(
+            identifiers(&sender, member),
+            vec![ServerMessage::PartyInvite]
+        );
+
+        AcceptParty { leader }
+            .handle(member, &mut world, &sender)
+            .unwrap();
+        assert_eq!(world.parties().members_of(member), vec![leader, member]);
+        assert_eq!(
+            identifiers(&sender, member)[1..],
+            [ServerMessage::AddPartyMember, ServerMessage::AddPartyMember]
+        );
+        assert_eq!(
+            identifiers(&sender, leader),
+            vec![ServerMessage::AddPartyMember, ServerMessage::AddPartyMember]
+        );
+    }
+
+    #[test]
+    fn accepting_without_invite_fails() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let leader = add_player(&mut world, 1, Position { x: 2100, y: 2100 });
+        let member = add_player(&mut world, 2, Position { x: 2102, y: 2102 });
+
+        assert_eq!(
+            AcceptParty { leader }.handle(member, &mut world, &sender),
+            Err(PartyError::NoInvite)
+        );
+        assert!(world.parties().party_of(member).is_none());
+    }
+
+    #[test]
+    fn distant_members_receive_hp_and_position() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let leader = add_player(&mut world, 1, Position { x: 2100, y: 2100 });
+        let member = add_player(&mut world, 2, Position { x: 2500, y: 2500 });
+        let stranger = add_player(&mut world, 3, Position { x: 2500, y: 2501 });
+        group(&mut world, leader, member);
+
+        world.broadcast_update_score(leader, &sender).unwrap();
+        crate::handlers::gameplay::action::teleport(
+            &mut world,
+            &sender,
+            leader,
+            Position { x: 2110, y: 2110 },
+        )
+        .unwrap();
+
+        assert_eq!(
+            identifiers(&sender, member),
+            vec![
+                ServerMessage::UpdateScore,
+                ServerMessage::PartyMemberPosition
+            ]
+        );
+        assert!(sender.messages_for(stranger).is_empty());
+    }
+
+    #[test]
+    fn kick_and_leave_notify_members() {
+        let mut world = World::default();
+        let leader = add_player(&mut world, 1, Position { x: 2100, y: 2100 });
+        let first = add_player(&mut world, 2, Position { x: 2101, y: 2101 });
+        let second = add_player(&mut world, 3, Position { x: 2102, y: 2102 });
+        group(&mut world, leader, first);
+        group(&mut world, leader, second);
+
+        let sender = MockPacketSender::default();
+        assert_eq!(
+            RemoveParty { target: second }.handle(first, &mut world, &sender),
+            Err(PartyError::NotLeader)
+        );
+        RemoveParty { target: second }
+            .handle(leader, &mut world, &sender)
+            .unwrap();
+        assert!(world.parties().party_of(second).is_none());
+        for entity_id in [leader, first, second] {
+            assert_eq!(
+                identifiers(&sender, entity_id),
+                vec![ServerMessage::RemovePartyMember]
+            );
+        }
+
+        leave_party(&mut world, first, &sender).unwrap();
+        assert!(world.parties().party_of(leader).is_none());
+        assert_eq!(
+            leave_party(&mut world, first, &sender),
+            Err(PartyError::NotInParty)
+        );
+    }
+
+    #[test]
+    fn members_are_split_by_distance() {
+        let mut world = World::default();
+        let killer = add_player(&mut world, 1, Position { x: 2100, y: 2100 });
+        let near = add_player(&mut world, 2, Position { x: 2110, y: 2110 });
+        let far = add_player(&mut world, 3, Position { x: 2500, y: 2500 });
+        group(&mut world, killer, near);
+        group(&mut world, killer, far);
+
+        assert_eq!(
+            world.party_members_in_range(killer, crate::party::PARTY_EXPERIENCE_RANGE),
+            vec![killer, near]
+        );
+        assert_eq!(world.party_members_out_of_view(killer), vec![far]);
+    }
+}
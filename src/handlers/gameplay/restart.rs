@@ -0,0 +1,80 @@
+use crate::map::EntityId;
+use crate::packets::BroadcastUpdateScore;
+use crate::session::{PacketSender, SessionError};
+use crate::world::{Mob, World};
+use odin_networking::{WritableResourceError, messages::client::restart::RestartRaw};
+
+#[derive(Debug)]
+pub struct Restart;
+
+impl Restart {
+    pub fn handle<P: PacketSender>(
+        &self,
+        entity_id: EntityId,
+        world: &mut World,
+        sender: &P,
+    ) -> Result<(), RestartError> {
+        let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) else {
+            return Err(RestartError::PlayerNotFound);
+        };
+        if !player.revive() {
+            return Err(RestartError::NotDead);
+        }
+
+        world.broadcast_update_score(entity_id, sender)?;
+        Ok(())
+    }
+}
+
+impl TryFrom<RestartRaw> for Restart {
+    type Error = WritableResourceError;
+
+    fn try_from(_: RestartRaw) -> Result<Self, Self::Error> {
+        Ok(Restart)
+    }
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum RestartError {
+    #[error("Player not found in world")]
+    PlayerNotFound,
+
+    #[error("Player is not dead")]
+    NotDead,
+
+    #[error(transparent)]
+    Session(#[from] SessionError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::tests::MockPacketSender;
+    use crate::world::Player;
+    use odin_models::{character::Character, position::Position};
+
+    #[test]
+    fn restart_revives_dead_player() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let entity_id = EntityId::Player(1);
+        let player = Player::from_character(entity_id, Character::default());
+        world
+            .add_player(entity_id, player, Position { x: 2100, y: 2100 })
+            .unwrap();
+
+        if let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) {
+            player.computed.score.hp = 0;
+        }
+        Restart.handle(entity_id, &mut world, &sender).unwrap();
+
+        assert!(matches!(
+            world.get_mob(entity_id),
+            Some(Mob::Player(player)) if player.computed.score.hp > 0
+        ));
+        assert_eq!(
+            Restart.handle(entity_id, &mut world, &sender),
+            Err(RestartError::NotDead)
+        );
+    }
+}
@@ -0,0 +1,224 @@
+use crate::map::EntityId;
+use crate::packets::ToUpdateEtc;
+use crate::session::{PacketSender, SessionError};
+use crate::world::{Mob, World};
+use odin_models::skill::SkillBar;
+use odin_networking::{WritableResourceError, messages::client::set_short_skill::SetShortSkillRaw};
+
+/// Teaches `skill_id` to a player, spending the skill points it costs.
+pub fn learn_skill<P: PacketSender>(
+    world: &mut World,
+    entity_id: EntityId,
+    skill_id: u8,
+    sender: &P,
+) -> Result<(), LearnSkillError> {
+    let skill = world
+        .skills()
+        .get(skill_id)
+        .cloned()
+        .ok_or(LearnSkillError::UnknownSkill(skill_id))?;
+    let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) else {
+        return Err(LearnSkillError::PlayerNotFound);
+    };
+    if player.learned_skills.knows(skill_id) {
+        return Err(LearnSkillError::AlreadyLearned);
+    }
+    if !skill.can_learn(player.class) {
+        return Err(LearnSkillError::WrongClass);
+    }
+    if player.score.level < skill.level {
+        return Err(LearnSkillError::LevelTooLow);
+    }
+    if player.skill_bonus < skill.points {
+        return Err(LearnSkillError::NotEnoughPoints);
+    }
+
+    player.learned_skills.learn(skill_id);
+    player.skill_bonus -= skill.points;
+    sender.send_to(entity_id, player.to_update_etc())?;
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct SetShortSkill {
+    pub skill_bar: SkillBar,
+}
+
+impl SetShortSkill {
+    pub fn handle(&self, entity_id: EntityId, world: &mut World) -> Result<(), SetShortSkillError> {
+        let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) else {
+            return Err(SetShortSkillError::PlayerNotFound);
+        };
+        if let Some(skill) = self
+            .skill_bar
+            .skills()
+            .find(|skill| !player.learned_skills.knows(*skill))
+        {
+            return Err(SetShortSkillError::NotLearned(skill));
+        }
+
+        player.skill_bar = self.skill_bar;
+        Ok(())
+    }
+}
+
+impl TryFrom<SetShortSkillRaw> for SetShortSkill {
+    type Error = WritableResourceError;
+
+    fn try_from(value: SetShortSkillRaw) -> Result<Self, Self::Error> {
+        Ok(SetShortSkill {
+            skill_bar: SkillBar::from_raw(&value.skills),
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum LearnSkillError {
+    #[error("Player not found in world")]
+    PlayerNotFound,
+
+    #[error("Skill {0} has no skill data")]
+    UnknownSkill(u8),
+
+    #[error("Skill is already learned")]
+    AlreadyLearned,
+
+    #[error("Skill belongs to another class")]
+    WrongClass,
+
+    #[error("Level is too low to learn the skill")]
+    LevelTooLow,
+
+    #[error("Not enough skill points")]
+    NotEnoughPoints,
+
+    #[error(transparent)]
+    Session(#[from] SessionError),
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum SetShortSkillError {
+    #[error("Player not found in world")]
+    PlayerNotFound,
+
+    #[error("Skill {0} is not learned")]
+    NotLearned(u8),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::tests::MockPacketSender;
+    use crate::skill::SkillTable;
+    use crate::world::Player;
+    use odin_models::{
+        character::{Character, Class},
+        position::Position,
+        skill::{EMPTY_SKILL_SLOT, MAX_SKILL_BAR},
+        status::Score,
+    };
+    use odin_networking::messages::ServerMessage;
+
+    const SKILLS: &str = r#"
+        [[skills]]
+        id = 4
+        name = "Fire Ball"
+        class = 1
+        level = 10
+        points = 2
+        range = 8
+        target = "enemy"
+        effect = { type = "damage", base = 100 }
+    "#;
+
+    fn setup(class: Class, level: u16, skill_bonus: i16) -> (World, EntityId) {
+        let mut world = World::default();
+        world.set_skill_table(SkillTable::from_toml(SKILLS).unwrap());
+        let entity_id = EntityId::Player(1);
+        let player = Player::from_character(
+            entity_id,
+            Character {
+                class,
+                skill_bonus,
+                score: Score {
+                    level,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+        world
+            .add_player(entity_id, player, Position { x: 2100, y: 2100 })
+            .unwrap();
+        (world, entity_id)
+    }
+
+    fn player(world: &World, entity_id: EntityId) -> &Player {
+        let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+            panic!("expected Player");
+        };
+        player
+    }
+
+    #[test]
+    fn learn_skill_spends_points_and_updates_client() {
+        let (mut world, entity_id) = setup(Class::Foema, 10, 3);
+        let sender = MockPacketSender::default();
+
+        learn_skill(&mut world, entity_id, 4, &sender).unwrap();
+
+        assert!(player(&world, entity_id).learned_skills.knows(4));
+        assert_eq!(player(&world, entity_id).skill_bonus, 1);
+        assert_eq!(
+            sender.messages_for(entity_id)[0].identifier,
+            ServerMessage::UpdateEtc
+        );
+        assert_eq!(
+            learn_skill(&mut world, entity_id, 4, &sender),
+            Err(LearnSkillError::AlreadyLearned)
+        );
+    }
+
+    #[test]
+    fn learn_skill_checks_requirements() {
+        let sender = MockPacketSender::default();
+
+        let (mut world, entity_id) = setup(Class::TransKnight, 10, 3);
+        assert_eq!(
+            learn_skill(&mut world, entity_id, 4, &sender),
+            Err(LearnSkillError::WrongClass)
+        );
+        let (mut world, entity_id) = setup(Class::Foema, 9, 3);
+        assert_eq!(
+            learn_skill(&mut world, entity_id, 4, &sender),
+            Err(LearnSkillError::LevelTooLow)
+        );
+        let (mut world, entity_id) = setup(Class::Foema, 10, 1);
+        assert_eq!(
+            learn_skill(&mut world, entity_id, 4, &sender),
+            Err(LearnSkillError::NotEnoughPoints)
+        );
+        assert_eq!(
+            learn_skill(&mut world, entity_id, 5, &sender),
+            Err(LearnSkillError::UnknownSkill(5))
+        );
+        assert!(!player(&world, entity_id).learned_skills.knows(4));
+    }
+
+    #[test]
+    fn skill_bar_only_accepts_learned_skills() {
+        let (mut world, entity_id) = setup(Class::Foema, 10, 3);
+        let mut raw = [EMPTY_SKILL_SLOT; MAX_SKILL_BAR];
+        raw[2] = 4;
+        let set_short_skill = SetShortSkill::try_from(SetShortSkillRaw { skills: raw }).unwrap();
+
+        assert_eq!(
+            set_short_skill.handle(entity_id, &mut world),
+            Err(SetShortSkillError::NotLearned(4))
+        );
+
+        learn_skill(&mut world, entity_id, 4, &MockPacketSender::default()).unwrap();
+        set_short_skill.handle(entity_id, &mut world).unwrap();
+        assert_eq!(player(&world, entity_id).skill_bar.to_raw(), raw);
+    }
+}
@@ -0,0 +1,549 @@
+use crate::handlers::gameplay::move_item::broadcast_appearance;
+use crate::map::EntityId;
+use crate::packets::{ToCreateMob, ToUpdateEtc};
+use crate::persistence::character_snapshot;
+use crate::session::{PacketSender, SessionError};
+use crate::stall::{STALL_RANGE, Stall, StallError, StallItem};
+use crate::world::{Mob, Player, World};
+use odin_models::{MAX_COIN, MAX_INVENTORY_VISIBLE, item::Item};
+use odin_networking::{
+    WritableResourceError,
+    messages::{
+        client::stall::{BrowseStallRaw, BuyStallItemRaw, MAX_STALL_ITEMS, StallRaw},
+        server::create_item::{CreateItem, SlotType},
+    },
+};
+use odin_repositories::account_repository::AccountRepository;
+
+fn player(world: &World, entity_id: EntityId) -> Result<&Player, StallError> {
+    match world.get_mob(entity_id) {
+        Some(Mob::Player(player)) if player.computed.score.hp > 0 => Ok(player),
+        _ => Err(StallError::PlayerNotFound),
+    }
+}
+
+/// The personal shop of `owner`, provided `entity_id` stands within its
+/// reach.
+fn reach_stall(world: &World, entity_id: EntityId, owner: EntityId) -> Result<&Stall, StallError> {
+    if owner == entity_id {
+        return Err(StallError::NoStall);
+    }
+    let stall = player(world, owner)?
+        .stall
+        .as_ref()
+        .ok_or(StallError::NoStall)?;
+    let (Some(position), Some(owner_position)) = (
+        world.map().get_position(entity_id),
+        world.map().get_position(owner),
+    ) else {
+        return Err(StallError::PlayerNotFound);
+    };
+    if position.chebyshev_distance(owner_position) > STALL_RANGE {
+        return Err(StallError::OutOfRange);
+    }
+    Ok(stall)
+}
+
+/// Shows the owner's appearance, with or without the shop title, to itself
+/// and its spectators.
+fn refresh_owner<P: PacketSender>(
+    world: &World,
+    entity_id: EntityId,
+    sender: &P,
+) -> Result<(), SessionError> {
+    if let (Some(mob), Some(position)) = (
+        world.get_mob(entity_id),
+        world.map().get_position(entity_id),
+    ) {
+        mob.send_create_mob(entity_id, position, sender)?;
+    }
+    broadcast_appearance(world, entity_id, sender)
+}
+
+#[derive(Debug)]
+pub struct OpenStall {
+    pub title: String,
+    /// Shop slot, inventory slot and price of every item for sale.
+    pub items: Vec<(usize, usize, u32)>,
+}
+
+impl OpenStall {
+    /// Opens the personal shop, replacing the one already open.
+    pub fn handle<P: PacketSender>(
+        &self,
+        entity_id: EntityId,
+        world: &mut World,
+        sender: &P,
+    ) -> Result<(), StallError> {
+        let title = self.title.trim();
+        if title.is_empty() {
+            return Err(StallError::InvalidTitle);
+        }
+        if world.trades().partner_of(entity_id).is_some() {
+            return Err(StallError::Trading);
+        }
+        let owner = player(world, entity_id)?;
+
+        let mut items = [None; MAX_STALL_ITEMS];
+        for &(stall_slot, inventory_slot, price) in &self.items {
+            let item = owner
+                .inventory
+                .get(inventory_slot)
+                .ok_or(StallError::InvalidItem)?;
+            if price > MAX_COIN as u32 {
+                return Err(StallError::InvalidItem);
+            }
+            items[stall_slot] = Some(StallItem {
+                inventory_slot,
+                item: *item,
+                price,
+            });
+        }
+        if items.iter().all(Option::is_none) {
+            return Err(StallError::InvalidItem);
+        }
+
+        let Some(Mob::Player(owner)) = world.get_mob_mut(entity_id) else {
+            return Err(StallError::PlayerNotFound);
+        };
+        owner.stall = Some(Box::new(Stall {
+            title: title.to_string(),
+            items,
+        }));
+        refresh_owner(world, entity_id, sender)?;
+        Ok(())
+    }
+}
+
+impl TryFrom<StallRaw> for OpenStall {
+    type Error = WritableResourceError;
+
+    fn try_from(value: StallRaw) -> Result<Self, Self::Error> {
+        let invalid = || WritableResourceError::Generic("Invalid stall item".to_string());
+
+        let mut items: Vec<(usize, usize, u32)> = vec![];
+        for (stall_slot, (inventory_slot, price)) in value
+            .inventory_slots
+            .into_iter()
+            .zip(value.prices)
+            .enumerate()
+            .filter(|(_, (inventory_slot, _))| *inventory_slot >= 0)
+        {
+            let inventory_slot = inventory_slot as usize;
+            if inventory_slot >= MAX_INVENTORY_VISIBLE
+                || items.iter().any(|(_, slot, _)| *slot == inventory_slot)
+            {
+                return Err(invalid());
+            }
+            let price = u32::try_from(price)
+                .ok()
+                .filter(|price| *price > 0)
+                .ok_or_else(invalid)?;
+            items.push((stall_slot, inventory_slot, price));
+        }
+        Ok(OpenStall {
+            title: value.title.try_into()?,
+            items,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct BrowseStall {
+    pub target: EntityId,
+}
+
+impl BrowseStall {
+    pub fn handle<P: PacketSender>(
+        &self,
+        entity_id: EntityId,
+        world: &World,
+        sender: &P,
+    ) -> Result<(), StallError> {
+        player(world, entity_id)?;
+        let stall = reach_stall(world, entity_id, self.target)?;
+        sender.send_to(entity_id, stall.to_stall_list(self.target.id() as u16))?;
+        Ok(())
+    }
+}
+
+impl TryFrom<BrowseStallRaw> for BrowseStall {
+    type Error = WritableResourceError;
+
+    fn try_from(value: BrowseStallRaw) -> Result<Self, Self::Error> {
+        Ok(BrowseStall {
+            target: EntityId::from_id(value.target_id as usize),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct BuyStallItem {
+    pub target: EntityId,
+    pub stall_slot: usize,
+    pub price: u32,
+    pub item: Item,
+}
+
+impl BuyStallItem {
+    /// Buys the item from the owner's personal shop. Both characters are
+    /// saved in a single transaction before the sale is applied, so the
+    /// item and coin either change hands on both sides or not at all.
+    pub async fn handle<P: PacketSender, A: AccountRepository>(
+        &self,
+        entity_id: EntityId,
+        world: &mut World,
+        sender: &P,
+        account_repository: &A,
+    ) -> Result<(), StallError> {
+        let stall = reach_stall(world, entity_id, self.target)?;
+        let stall_item = stall.items[self.stall_slot].ok_or(StallError::StallChanged)?;
+        if stall_item.price != self.price || stall_item.item != self.item {
+            return Err(StallError::StallChanged);
+        }
+
+        let owner = player(world, self.target)?;
+        if owner.inventory.get(stall_item.inventory_slot) != Some(&stall_item.item) {
+            return Err(StallError::StallChanged);
+        }
+        let owner_coin = owner.coin as i64 + stall_item.price as i64;
+        if owner_coin > MAX_COIN as i64 {
+            return Err(StallError::CoinLimit);
+        }
+        let buyer = player(world, entity_id)?;
+        let buyer_coin = buyer.coin as i64 - stall_item.price as i64;
+        if buyer_coin < 0 {
+            return Err(StallError::NotEnoughCoin);
+        }
+        let buyer_slot = buyer
+            .inventory
+            .first_empty()
+            .ok_or(StallError::InventoryFull)?;
+        let buyer_name = buyer.name.clone();
+
+        let mut buyer_character =
+            character_snapshot(world, entity_id).ok_or(StallError::PlayerNotFound)?;
+        buyer_character.coin = buyer_coin as i32;
+        buyer_character.inventory.set(buyer_slot, stall_item.item);
+        let mut owner_character =
+            character_snapshot(world, self.target).ok_or(StallError::PlayerNotFound)?;
+        owner_character.coin = owner_coin as i32;
+        owner_character.inventory.take(stall_item.inventory_slot);
+        account_repository
+            .save_characters(&[buyer_character, owner_character])
+            .await?;
+
+        if let Some(Mob::Player(owner)) = world.get_mob_mut(self.target) {
+            owner.coin = owner_coin as i32;
+            owner.inventory.take(stall_item.inventory_slot);
+            if let Some(stall) = owner.stall.as_mut() {
+                stall.items[self.stall_slot] = None;
+            }
+            log::info!(
+                "{} sold item {} to {} for {} coin",
+                owner.name,
+                stall_item.item.id,
+                buyer_name,
+                stall_item.price
+            );
+        }
+        if let Some(Mob::Player(buyer)) = world.get_mob_mut(entity_id) {
+            buyer.coin = buyer_coin as i32;
+            buyer.inventory.set(buyer_slot, stall_item.item);
+        }
+
+        for (trader, slot) in [
+            (self.target, stall_item.inventory_slot),
+            (entity_id, buyer_slot),
+        ] {
+            let trader_player = player(world, trader)?;
+            sender.send_to(
+                trader,
+                CreateItem {
+                    mob_id: trader.id() as u16,
+                    slot_type: SlotType::Inventory,
+                    slot: slot as u16,
+                    item: trader_player.inventory.get(slot).copied(),
+                },
+            )?;
+            sender.send_to(trader, trader_player.to_update_etc())?;
+        }
+        if let Some(stall) = player(world, self.target)?.stall.as_ref() {
+            sender.send_to(entity_id, stall.to_stall_list(self.target.id() as u16))?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<BuyStallItemRaw> for BuyStallItem {
+    type Error = WritableResourceError;
+
+    fn try_from(value: BuyStallItemRaw) -> Result<Self, Self::Error> {
+        let stall_slot = usize::try_from(value.stall_slot)
+            .ok()
+            .filter(|slot| *slot < MAX_STALL_ITEMS)
+            .ok_or_else(|| WritableResourceError::Generic("Invalid stall slot".to_string()))?;
+        let price = u32::try_from(value.price)
+            .map_err(|_| WritableResourceError::Generic("Invalid stall price".to_string()))?;
+        Ok(BuyStallItem {
+            target: EntityId::from_id(value.target_id as usize),
+            stall_slot,
+            price,
+            item: value.item.into(),
+        })
+    }
+}
+
+/// Closes the personal shop of `entity_id`, if it has one open, e.g. when
+/// the owner moves.
+pub fn close_stall<P: PacketSender>(
+    world: &mut World,
+    entity_id: EntityId,
+    sender: &P,
+) -> Result<(), SessionError> {
+    let Some(Mob::Player(owner)) = world.get_mob_mut(entity_id) else {
+        return Ok(());
+    };
+    if owner.stall.take().is_some() {
+        refresh_owner(world, entity_id, sender)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::gameplay::action::{Action, ActionType};
+    use crate::handlers::tests::{MockPacketSender, TestAccountRepository};
+    use odin_models::{
+        account_charlist::AccountCharlist, character::Character, position::Position, status::Score,
+        uuid::Uuid,
+    };
+    use odin_networking::messages::ServerMessage;
+
+    async fn add_player(
+        repository: &TestAccountRepository,
+        world: &mut World,
+        client_id: usize,
+        character: Character,
+    ) -> (EntityId, Uuid) {
+        let account_id = Uuid::new_v4();
+        let character = Character {
+            identifier: Uuid::new_v4(),
+            name: format!("Player{client_id}"),
+            score: Score {
+                hp: 100,
+                ..Default::default()
+            },
+            ..character
+        };
+        repository
+            .add_account_with_characters(
+                AccountCharlist {
+                    identifier: account_id,
+                    username: format!("account{client_id}"),
+                    password: "pass".to_string(),
+                    ..Default::default()
+                },
+                vec![character.clone()],
+            )
+            .await;
+
+        let entity_id = EntityId::Player(client_id);
+        let position = character.last_pos;
+        world
+            .add_player(
+                entity_id,
+                Player::from_character(entity_id, character),
+                position,
+            )
+            .unwrap();
+        (entity_id, account_id)
+    }
+
+    fn player(world: &World, entity_id: EntityId) -> &Player {
+        let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+            panic!("expected Player");
+        };
+        player
+    }
+
+    fn identifiers(sender: &MockPacketSender, entity_id: EntityId) -> Vec<ServerMessage> {
+        sender
+            .messages_for(entity_id)
+            .iter()
+            .map(|packet| packet.identifier)
+            .collect()
+    }
+
+    /// Owner with a sword for 300 coin in shop slot 2, and a buyer next to
+    /// it.
+    async fn setup(
+        sender: &MockPacketSender,
+    ) -> (
+        World,
+        TestAccountRepository,
+        (EntityId, Uuid),
+        (EntityId, Uuid),
+    ) {
+        let repository = TestAccountRepository::new().await;
+        let mut world = World::default();
+        let owner = add_player(
+            &repository,
+            &mut world,
+            1,
+            Character {
+                coin: 100,
+                last_pos: Position { x: 2100, y: 2100 },
+                inventory: vec![(4, Item::from(1101u16))].into(),
+                ..Default::default()
+            },
+        )
+        .await;
+        let buyer = add_player(
+            &repository,
+            &mut world,
+            2,
+            Character {
+                coin: 1000,
+                last_pos: Position { x: 2102, y: 2100 },
+                inventory: vec![(0, Item::from(400u16))].into(),
+                ..Default::default()
+            },
+        )
+        .await;
+
+        OpenStall {
+            title: "Cheap swords".to_string(),
+            items: vec![(2, 4, 300)],
+        }
+        .handle(owner.0, &mut world, sender)
+        .unwrap();
+        (world, repository, owner, buyer)
+    }
+
+    fn buy(owner: EntityId, price: u32) -> BuyStallItem {
+        BuyStallItem {
+            target: owner,
+            stall_slot: 2,
+            price,
+            item: Item::from(1101u16),
+        }
+    }
+
+    #[tokio::test]
+    async fn open_stall_shows_its_title_to_spectators() {
+        let sender = MockPacketSender::default();
+        let (world, _repository, (owner, _), (buyer, _)) = setup(&sender).await;
+
+        assert_eq!(
+            player(&world, owner).stall.as_ref().unwrap().title,
+            "Cheap swords"
+        );
+        assert_eq!(
+            identifiers(&sender, buyer),
+            vec![ServerMessage::CreateMobTrade]
+        );
+
+        BrowseStall { target: owner }
+            .handle(buyer, &world, &sender)
+            .unwrap();
+        assert_eq!(
+            identifiers(&sender, buyer).last(),
+            Some(&ServerMessage::StallList)
+        );
+    }
+
+    #[tokio::test]
+    async fn buying_moves_item_and_coin_and_persists_both() {
+        let sender = MockPacketSender::default();
+        let (mut world, repository, (owner, owner_account), (buyer, buyer_account)) =
+            setup(&sender).await;
+        let accounts = repository.account_repository();
+
+        buy(owner, 300)
+            .handle(buyer, &mut world, &sender, &accounts)
+            .await
+            .unwrap();
+
+        let owner_player = player(&world, owner);
+        assert_eq!(owner_player.coin, 400);
+        assert!(owner_player.inventory.get(4).is_none());
+        assert_eq!(owner_player.stall.as_ref().unwrap().items[2], None);
+        let buyer_player = player(&world, buyer);
+        assert_eq!(buyer_player.coin, 700);
+        assert_eq!(buyer_player.inventory.get(1), Some(&Item::from(1101u16)));
+
+        let owner_saved = accounts
+            .fetch_character(owner_account, 0)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(owner_saved.coin, 400);
+        assert!(owner_saved.inventory.get(4).is_none());
+        let buyer_saved = accounts
+            .fetch_character(buyer_account, 0)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(buyer_saved.coin, 700);
+        assert_eq!(buyer_saved.inventory.get(1), Some(&Item::from(1101u16)));
+
+        assert_eq!(
+            buy(owner, 300)
+                .handle(buyer, &mut world, &sender, &accounts)
+                .await,
+            Err(StallError::StallChanged)
+        );
+    }
+
+    #[tokio::test]
+    async fn sale_is_refused_when_the_shop_changed() {
+        let sender = MockPacketSender::default();
+        let (mut world, repository, (owner, _), (buyer, _)) = setup(&sender).await;
+        let accounts = repository.account_repository();
+
+        assert_eq!(
+            buy(owner, 100)
+                .handle(buyer, &mut world, &sender, &accounts)
+                .await,
+            Err(StallError::StallChanged)
+        );
+
+        if let Some(Mob::Player(owner_player)) = world.get_mob_mut(owner) {
+            let item = owner_player.inventory.take(4).unwrap();
+            owner_player.inventory.set(5, item);
+        }
+        assert_eq!(
+            buy(owner, 300)
+                .handle(buyer, &mut world, &sender, &accounts)
+                .await,
+            Err(StallError::StallChanged)
+        );
+        assert_eq!(player(&world, buyer).coin, 1000);
+        assert_eq!(player(&world, owner).coin, 100);
+    }
+
+    #[tokio::test]
+    async fn moving_closes_the_stall() {
+        let sender = MockPacketSender::default();
+        let (mut world, _repository, (owner, _), (buyer, _)) = setup(&sender).await;
+
+        Action {
+            last_pos: Position { x: 2100, y: 2100 },
+            move_type: 0,
+            move_speed: 3,
+            command: [0; 24],
+            destiny: Position { x: 2101, y: 2101 },
+        }
+        .handle(owner, &mut world, &sender, ActionType::Walk)
+        .unwrap();
+
+        assert!(player(&world, owner).stall.is_none());
+        assert!(identifiers(&sender, buyer).contains(&ServerMessage::CreateMob));
+        assert_eq!(
+            BrowseStall { target: owner }.handle(buyer, &world, &sender),
+            Err(StallError::NoStall)
+        );
+    }
+}
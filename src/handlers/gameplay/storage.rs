@@ -0,0 +1,214 @@
+use crate::map::EntityId;
+use crate::npc::MERCHANT_STORAGE;
+use crate::packets::ToUpdateEtc;
+use crate::session::{PacketSender, SessionError};
+use crate::world::{Mob, World};
+use odin_models::MAX_COIN;
+use odin_networking::{
+    WritableResourceError,
+    messages::{
+        client::storage_coin::StorageCoinRaw, server::update_storage_coin::UpdateStorageCoin,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoinTransfer {
+    Deposit,
+    Withdraw,
+}
+
+#[derive(Debug)]
+pub struct StorageCoin {
+    pub coin: i32,
+}
+
+impl StorageCoin {
+    pub fn handle<P: PacketSender>(
+        &self,
+        entity_id: EntityId,
+        world: &mut World,
+        sender: &P,
+        transfer: CoinTransfer,
+    ) -> Result<(), StorageCoinError> {
+        if self.coin <= 0 {
+            return Err(StorageCoinError::InvalidAmount);
+        }
+        if !world.merchant_in_reach(entity_id, MERCHANT_STORAGE) {
+            return Err(StorageCoinError::StorageOutOfReach);
+        }
+        let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) else {
+            return Err(StorageCoinError::PlayerNotFound);
+        };
+
+        let coin = self.coin as u64;
+        match transfer {
+            CoinTransfer::Deposit => {
+                if player.coin < self.coin {
+                    return Err(StorageCoinError::NotEnoughCoin);
+                }
+                if player.storage.coin + coin > MAX_COIN as u64 {
+                    return Err(StorageCoinError::CoinLimit);
+                }
+                player.coin -= self.coin;
+                player.storage.coin += coin;
+            }
+            CoinTransfer::Withdraw => {
+                if player.storage.coin < coin {
+                    return Err(StorageCoinError::NotEnoughCoin);
+                }
+                if player.coin as i64 + self.coin as i64 > MAX_COIN as i64 {
+                    return Err(StorageCoinError::CoinLimit);
+                }
+                player.coin += self.coin;
+                player.storage.coin -= coin;
+            }
+        }
+
+        sender.send_to(entity_id, player.to_update_etc())?;
+        sender.send_to(
+            entity_id,
+            UpdateStorageCoin {
+                coin: player.storage.coin,
+            },
+        )?;
+        Ok(())
+    }
+}
+
+impl TryFrom<StorageCoinRaw> for StorageCoin {
+    type Error = WritableResourceError;
+
+    fn try_from(value: StorageCoinRaw) -> Result<Self, Self::Error> {
+        Ok(StorageCoin { coin: value.coin })
+    }
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum StorageCoinError {
+    #[error("Player not found in world")]
+    PlayerNotFound,
+
+    #[error("Invalid coin amount")]
+    InvalidAmount,
+
+    #[error("No storage keeper in reach")]
+    StorageOutOfReach,
+
+    #[error("Not enough coin")]
+    NotEnoughCoin,
+
+    #[error("Coin limit reached")]
+    CoinLimit,
+
+    #[error(transparent)]
+    Session(#[from] SessionError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::tests::MockPacketSender;
+    use crate::npc::{
+        Npc,
+        movement::{MovementBehavior, MovementState},
+    };
+    use crate::world::Player;
+    use odin_models::{character::Character, npc_mob::NpcMob, position::Position};
+    use odin_networking::messages::ServerMessage;
+
+    fn setup(keeper: bool) -> (World, EntityId) {
+        let mut world = World::default();
+        let entity_id = EntityId::Player(1);
+        let mut player = Player::from_character(
+            entity_id,
+            Character {
+                coin: 1000,
+                ..Default::default()
+            },
+        );
+        player.storage.coin = 300;
+        world
+            .add_player(entity_id, player, Position { x: 2100, y: 2100 })
+            .unwrap();
+
+        if keeper {
+            let npc_id = EntityId::Mob(1000);
+            let template = NpcMob {
+                merchant: MERCHANT_STORAGE,
+                ..Default::default()
+            };
+            let movement = MovementState::new(MovementBehavior::Stationary, 1);
+            world
+                .add_npc(
+                    npc_id,
+                    Npc::new(npc_id, template, movement),
+                    Position { x: 2102, y: 2100 },
+                )
+                .unwrap();
+        }
+        (world, entity_id)
+    }
+
+    fn coins(world: &World, entity_id: EntityId) -> (i32, u64) {
+        match world.get_mob(entity_id) {
+            Some(Mob::Player(player)) => (player.coin, player.storage.coin),
+            _ => panic!("player not found"),
+        }
+    }
+
+    #[test]
+    fn deposit_and_withdraw_move_coin() {
+        let (mut world, entity_id) = setup(true);
+        let sender = MockPacketSender::default();
+
+        StorageCoin { coin: 400 }
+            .handle(entity_id, &mut world, &sender, CoinTransfer::Deposit)
+            .unwrap();
+        assert_eq!(coins(&world, entity_id), (600, 700));
+
+        StorageCoin { coin: 700 }
+            .handle(entity_id, &mut world, &sender, CoinTransfer::Withdraw)
+            .unwrap();
+        assert_eq!(coins(&world, entity_id), (1300, 0));
+
+        let identifiers: Vec<_> = sender
+            .messages_for(entity_id)
+            .iter()
+            .map(|packet| packet.identifier)
+            .collect();
+        assert_eq!(
+            identifiers,
+            vec![
+                ServerMessage::UpdateEtc,
+                ServerMessage::UpdateStorageCoin,
+                ServerMessage::UpdateEtc,
+                ServerMessage::UpdateStorageCoin
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_transfers() {
+        let (mut world, entity_id) = setup(true);
+        let sender = MockPacketSender::default();
+
+        for (coin, transfer, error) in [
+            (0, CoinTransfer::Deposit, StorageCoinError::InvalidAmount),
+            (-5, CoinTransfer::Withdraw, StorageCoinError::InvalidAmount),
+            (1001, CoinTransfer::Deposit, StorageCoinError::NotEnoughCoin),
+            (301, CoinTransfer::Withdraw, StorageCoinError::NotEnoughCoin),
+        ] {
+            assert_eq!(
+                StorageCoin { coin }.handle(entity_id, &mut world, &sender, transfer),
+                Err(error)
+            );
+        }
+        assert_eq!(coins(&world, entity_id), (1000, 300));
+
+        let (mut world, entity_id) = setup(false);
+        assert_eq!(
+            StorageCoin { coin: 10 }.handle(entity_id, &mut world, &sender, CoinTransfer::Deposit),
+            Err(StorageCoinError::StorageOutOfReach)
+        );
+    }
+}
@@ -0,0 +1,655 @@
+use crate::handlers::gameplay::stall::close_stall;
+use crate::map::EntityId;
+use crate::packets::ToUpdateEtc;
+use crate::persistence::character_snapshot;
+use crate::session::{PacketSender, SessionError};
+use crate::trade::{TRADE_RANGE, TradeError, TradeOffer};
+use crate::world::{Mob, Player, World};
+use odin_models::{InventorySlots, MAX_COIN, MAX_INVENTORY_VISIBLE};
+use odin_networking::{
+    WritableResourceError,
+    messages::{
+        client::trade::{QuitTradeRaw, TradeRaw},
+        server::{
+            create_item::{CreateItem, SlotType},
+            trade::{QuitTrade as CloseTrade, Trade as TradeWindow},
+        },
+    },
+};
+use odin_repositories::account_repository::AccountRepository;
+
+/// The offer changed.
+pub const TRADE_OFFER: u8 = 0;
+/// The offer is locked and can't change anymore.
+pub const TRADE_LOCK: u8 = 1;
+/// The locked offers are accepted.
+pub const TRADE_CONFIRM: u8 = 2;
+
+fn trader(world: &World, entity_id: EntityId) -> Result<&Player, TradeError> {
+    match world.get_mob(entity_id) {
+        Some(Mob::Player(player)) if player.computed.score.hp > 0 => Ok(player),
+        _ => Err(TradeError::PlayerNotFound),
+    }
+}
+
+fn in_range(world: &World, entity_id: EntityId, other: EntityId) -> bool {
+    match (
+        world.map().get_position(entity_id),
+        world.map().get_position(other),
+    ) {
+        (Some(position), Some(other)) => position.chebyshev_distance(other) <= TRADE_RANGE,
+        _ => false,
+    }
+}
+
+/// What `entity_id` shows in its partner's trade window.
+fn trade_window(world: &World, entity_id: EntityId, check: u8) -> TradeWindow {
+    let offer = world
+        .trades()
+        .side(entity_id)
+        .map(|side| side.offer.clone())
+        .unwrap_or_default();
+    TradeWindow {
+        opponent_id: entity_id.id() as u16,
+        items: offer.items,
+        coin: offer.coin,
+        check,
+    }
+}
+
+fn empty_window(entity_id: EntityId) -> TradeWindow {
+    TradeWindow {
+        opponent_id: entity_id.id() as u16,
+        items: vec![],
+        coin: 0,
+        check: TRADE_OFFER,
+    }
+}
+
+#[derive(Debug)]
+pub struct Trade {
+    pub opponent: EntityId,
+    pub slots: Vec<usize>,
+    pub coin: u32,
+    pub check: u8,
+}
+
+impl Trade {
+    /// Requests a trade with `opponent`, or accepts its request, when not
+    /// trading yet. Otherwise updates, locks or confirms the offer as told
+    /// by `check`; the trade is carried out once both sides confirm.
+    pub async fn handle<P: PacketSender, A: AccountRepository>(
+        &self,
+        entity_id: EntityId,
+        world: &mut World,
+        sender: &P,
+        account_repository: &A,
+    ) -> Result<(), TradeError> {
+        trader(world, entity_id)?;
+        if !matches!(self.opponent, EntityId::Player(_)) || self.opponent == entity_id {
+            return Err(TradeError::InvalidTarget);
+        }
+        trader(world, self.opponent)?;
+        if !in_range(world, entity_id, self.opponent) {
+            return Err(TradeError::OutOfRange);
+        }
+
+        match world.trades().partner_of(entity_id) {
+            None if world.trades().pending_request(entity_id) == Some(self.opponent) => {
+                world.trades_mut().accept(entity_id, self.opponent)?;
+                sender.send_to(entity_id, empty_window(self.opponent))?;
+                sender.send_to(self.opponent, empty_window(entity_id))?;
+                return Ok(());
+            }
+            None => {
+                world.trades_mut().request(entity_id, self.opponent)?;
+                sender.send_to(self.opponent, empty_window(entity_id))?;
+                return Ok(());
+            }
+            Some(partner) if partner != self.opponent => return Err(TradeError::InvalidTarget),
+            Some(_) => {}
+        }
+
+        match self.check {
+            TRADE_OFFER => {
+                let offer = self.offer(trader(world, entity_id)?)?;
+                world.trades_mut().set_offer(entity_id, offer)?;
+            }
+            TRADE_LOCK => world.trades_mut().lock(entity_id)?,
+            TRADE_CONFIRM => {
+                if world.trades_mut().confirm(entity_id)? {
+                    return complete_trade(world, entity_id, sender, account_repository).await;
+                }
+            }
+            _ => return Err(TradeError::InvalidOffer),
+        }
+        sender.send_to(self.opponent, trade_window(world, entity_id, self.check))?;
+        Ok(())
+    }
+
+    /// The offer as taken from the player's inventory, so the partner sees
+    /// the real items rather than what the client claims.
+    fn offer(&self, player: &Player) -> Result<TradeOffer, TradeError> {
+        if self.coin as i64 > player.coin as i64 {
+            return Err(TradeError::NotEnoughCoin);
+        }
+        let items = self
+            .slots
+            .iter()
+            .map(|slot| {
+                player
+                    .inventory
+                    .get(*slot)
+                    .map(|item| (*slot, *item))
+                    .ok_or(TradeError::InvalidOffer)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(TradeOffer {
+            items,
+            coin: self.coin,
+        })
+    }
+}
+
+impl TryFrom<TradeRaw> for Trade {
+    type Error = WritableResourceError;
+
+    fn try_from(value: TradeRaw) -> Result<Self, Self::Error> {
+        let mut slots: Vec<usize> = vec![];
+        for slot in value.inventory_slots.into_iter().filter(|slot| *slot >= 0) {
+            let slot = slot as usize;
+            if slot >= MAX_INVENTORY_VISIBLE || slots.contains(&slot) {
+                return Err(WritableResourceError::Generic(
+                    "Invalid trade slot".to_string(),
+                ));
+            }
+            slots.push(slot);
+        }
+        let coin = u32::try_from(value.coin)
+            .map_err(|_| WritableResourceError::Generic("Invalid trade coin".to_string()))?;
+        Ok(Trade {
+            opponent: EntityId::from_id(value.opponent_id as usize),
+            slots,
+            coin,
+            check: value.check,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct QuitTrade;
+
+impl QuitTrade {
+    /// The client also sends it to close its personal shop.
+    pub fn handle<P: PacketSender>(
+        &self,
+        entity_id: EntityId,
+        world: &mut World,
+        sender: &P,
+    ) -> Result<(), TradeError> {
+        cancel_trade(world, entity_id, sender)?;
+        close_stall(world, entity_id, sender)?;
+        Ok(())
+    }
+}
+
+impl TryFrom<QuitTradeRaw> for QuitTrade {
+    type Error = WritableResourceError;
+
+    fn try_from(_: QuitTradeRaw) -> Result<Self, Self::Error> {
+        Ok(QuitTrade)
+    }
+}
+
+/// Ends any trade of `entity_id`, e.g. on disconnect, closing both trade
+/// windows. Nothing changed hands yet, so there is nothing to give back.
+pub fn cancel_trade<P: PacketSender>(
+    world: &mut World,
+    entity_id: EntityId,
+    sender: &P,
+) -> Result<(), SessionError> {
+    if let Some(partner) = world.trades_mut().close(entity_id) {
+        sender.send_to(entity_id, CloseTrade)?;
+        sender.send_to(partner, CloseTrade)?;
+    }
+    Ok(())
+}
+
+/// Cancels the trade of `entity_id` when its partner is no longer within
+/// [`TRADE_RANGE`], e.g. after one of them moved.
+pub fn cancel_trade_out_of_range<P: PacketSender>(
+    world: &mut World,
+    entity_id: EntityId,
+    sender: &P,
+) -> Result<(), SessionError> {
+    match world.trades().partner_of(entity_id) {
+        Some(partner) if !in_range(world, entity_id, partner) => {
+            cancel_trade(world, entity_id, sender)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// One side of the swap, computed before anything is applied.
+struct Exchange {
+    inventory: InventorySlots,
+    coin: i32,
+    changed_slots: Vec<usize>,
+}
+
+fn exchange(
+    player: &Player,
+    given: &TradeOffer,
+    received: &TradeOffer,
+) -> Result<Exchange, TradeError> {
+    let mut inventory = player.inventory.clone();
+    let mut changed_slots = vec![];
+    for (slot, item) in &given.items {
+        // The item must still be the one the partner agreed to
+        if inventory.take(*slot) != Some(*item) {
+            return Err(TradeError::InvalidOffer);
+        }
+        changed_slots.push(*slot);
+    }
+    for (_, item) in &received.items {
+        let slot = inventory.first_empty().ok_or(TradeError::InventoryFull)?;
+        inventory.set(slot, *item);
+        if !changed_slots.contains(&slot) {
+            changed_slots.push(slot);
+        }
+    }
+
+    if given.coin as i64 > player.coin as i64 {
+        return Err(TradeError::NotEnoughCoin);
+    }
+    let coin = player.coin as i64 - given.coin as i64 + received.coin as i64;
+    if coin > MAX_COIN as i64 {
+        return Err(TradeError::CoinLimit);
+    }
+    Ok(Exchange {
+        inventory,
+        coin: coin as i32,
+        changed_slots,
+    })
+}
+
+/// Swaps both offers. The outcome is computed for both players and saved
+/// in a single transaction before it is applied, so either both sides
+/// change, in memory and in the database, or none does. A failure cancels
+/// the trade.
+async fn complete_trade<P: PacketSender, A: AccountRepository>(
+    world: &mut World,
+    entity_id: EntityId,
+    sender: &P,
+    account_repository: &A,
+) -> Result<(), TradeError> {
+    let result = swap(world, entity_id, sender, account_repository).await;
+    cancel_trade(world, entity_id, sender)?;
+    result
+}
+
+async fn swap<P: PacketSender, A: AccountRepository>(
+    world: &mut World,
+    entity_id: EntityId,
+    sender: &P,
+    account_repository: &A,
+) -> Result<(), TradeError> {
+    let side = world
+        .trades()
+        .side(entity_id)
+        .ok_or(TradeError::NotTrading)?;
+    let partner = side.partner;
+    let offer = side.offer.clone();
+    let partner_offer = world
+        .trades()
+        .side(partner)
+        .ok_or(TradeError::NotTrading)?
+        .offer
+        .clone();
+
+    let traders = [
+        (
+            entity_id,
+            exchange(trader(world, entity_id)?, &offer, &partner_offer)?,
+        ),
+        (
+            partner,
+            exchange(trader(world, partner)?, &partner_offer, &offer)?,
+        ),
+    ];
+
+    let characters = traders
+        .iter()
+        .map(|(trader_id, exchange)| {
+            let mut character =
+                character_snapshot(world, *trader_id).ok_or(TradeError::PlayerNotFound)?;
+            character.inventory = exchange.inventory.clone();
+            character.coin = exchange.coin;
+            Ok(character)
+        })
+        .collect::<Result<Vec<_>, TradeError>>()?;
+    account_repository.save_characters(&characters).await?;
+
+    for (trader_id, exchange) in traders {
+        let Some(Mob::Player(player)) = world.get_mob_mut(trader_id) else {
+            continue;
+        };
+        player.inventory = exchange.inventory;
+        player.coin = exchange.coin;
+        for slot in exchange.changed_slots {
+            sender.send_to(
+                trader_id,
+                CreateItem {
+                    mob_id: trader_id.id() as u16,
+                    slot_type: SlotType::Inventory,
+                    slot: slot as u16,
+                    item: player.inventory.get(slot).copied(),
+                },
+            )?;
+        }
+        sender.send_to(trader_id, player.to_update_etc())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::tests::{MockPacketSender, TestAccountRepository};
+    use odin_models::{
+        account_charlist::AccountCharlist, character::Character, item::Item, position::Position,
+        status::Score, uuid::Uuid,
+    };
+    use odin_networking::messages::ServerMessage;
+
+    async fn add_player(
+        repository: &TestAccountRepository,
+        world: &mut World,
+        client_id: usize,
+        character: Character,
+    ) -> (EntityId, Uuid) {
+        let account_id = Uuid::new_v4();
+        let character = Character {
+            identifier: Uuid::new_v4(),
+            name: format!("Player{client_id}"),
+            score: Score {
+                hp: 100,
+                ..Default::default()
+            },
+            ..character
+        };
+        repository
+            .add_account_with_characters(
+                AccountCharlist {
+                    identifier: account_id,
+                    username: format!("account{client_id}"),
+                    password: "pass".to_string(),
+                    ..Default::default()
+                },
+                vec![character.clone()],
+            )
+            .await;
+
+        let entity_id = EntityId::Player(client_id);
+        let position = character.last_pos;
+        world
+            .add_player(
+                entity_id,
+                Player::from_character(entity_id, character),
+                position,
+            )
+            .unwrap();
+        (entity_id, account_id)
+    }
+
+    fn trade(opponent: EntityId, slots: &[usize], coin: u32, check: u8) -> Trade {
+        Trade {
+            opponent,
+            slots: slots.to_vec(),
+            coin,
+            check,
+        }
+    }
+
+    async fn step(
+        world: &mut World,
+        sender: &MockPacketSender,
+        repository: &TestAccountRepository,
+        entity_id: EntityId,
+        message: Trade,
+    ) -> Result<(), TradeError> {
+        message
+            .handle(entity_id, world, sender, &repository.account_repository())
+            .await
+    }
+
+    fn player(world: &World, entity_id: EntityId) -> &Player {
+        let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+            panic!("expected Player");
+        };
+        player
+    }
+
+    async fn setup() -> (World, TestAccountRepository, [(EntityId, Uuid); 2]) {
+        let repository = TestAccountRepository::new().await;
+        let mut world = World::default();
+        let alice = add_player(
+            &repository,
+            &mut world,
+            1,
+            Character {
+                coin: 1000,
+                last_pos: Position { x: 2100, y: 2100 },
+                inventory: vec![(0, Item::from(400u16)), (3, Item::from(401u16))].into(),
+                ..Default::default()
+            },
+        )
+        .await;
+        let bob = add_player(
+            &repository,
+            &mut world,
+            2,
+            Character {
+                coin: 50,
+                last_pos: Position { x: 2102, y: 2100 },
+                inventory: vec![(0, Item::from(1101u16))].into(),
+                ..Default::default()
+            },
+        )
+        .await;
+        (world, repository, [alice, bob])
+    }
+
+    #[tokio::test]
+    async fn confirmed_trade_swaps_and_persists_both_sides() {
+        let (mut world, repository, [(alice, alice_account), (bob, bob_account)]) = setup().await;
+        let sender = MockPacketSender::default();
+
+        for (entity_id, message) in [
+            (alice, trade(bob, &[], 0, TRADE_OFFER)),
+            (bob, trade(alice, &[], 0, TRADE_OFFER)),
+            (alice, trade(bob, &[3], 300, TRADE_OFFER)),
+            (bob, trade(alice, &[0], 0, TRADE_OFFER)),
+            (alice, trade(bob, &[], 0, TRADE_LOCK)),
+            (bob, trade(alice, &[], 0, TRADE_LOCK)),
+            (alice, trade(bob, &[], 0, TRADE_CONFIRM)),
+        ] {
+            step(&mut world, &sender, &repository, entity_id, message)
+                .await
+                .unwrap();
+        }
+        assert_eq!(player(&world, alice).coin, 1000);
+
+        step(
+            &mut world,
+            &sender,
+            &repository,
+            bob,
+            trade(alice, &[], 0, TRADE_CONFIRM),
+        )
+        .await
+        .unwrap();
+
+        let alice_player = player(&world, alice);
+        assert_eq!(alice_player.coin, 700);
+        assert!(alice_player.inventory.get(3).is_none());
+        assert_eq!(alice_player.inventory.get(1), Some(&Item::from(1101u16)));
+        let bob_player = player(&world, bob);
+        assert_eq!(bob_player.coin, 350);
+        assert_eq!(bob_player.inventory.get(0), Some(&Item::from(401u16)));
+        assert_eq!(world.trades().partner_of(alice), None);
+        assert!(
+            sender
+                .messages_for(bob)
+                .iter()
+                .any(|packet| packet.identifier == ServerMessage::QuitTrade)
+        );
+
+        let accounts = repository.account_repository();
+        let alice_saved = accounts
+            .fetch_character(alice_account, 0)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(alice_saved.coin, 700);
+        assert_eq!(alice_saved.inventory.get(1), Some(&Item::from(1101u16)));
+        let bob_saved = accounts
+            .fetch_character(bob_account, 0)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(bob_saved.coin, 350);
+        assert_eq!(bob_saved.inventory.get(0), Some(&Item::from(401u16)));
+    }
+
+    #[tokio::test]
+    async fn moved_item_aborts_the_trade() {
+        let (mut world, repository, [(alice, _), (bob, _)]) = setup().await;
+        let sender = MockPacketSender::default();
+
+        for (entity_id, message) in [
+            (alice, trade(bob, &[], 0, TRADE_OFFER)),
+            (bob, trade(alice, &[], 0, TRADE_OFFER)),
+            (alice, trade(bob, &[3], 0, TRADE_OFFER)),
+            (alice, trade(bob, &[], 0, TRADE_LOCK)),
+            (bob, trade(alice, &[], 0, TRADE_LOCK)),
+            (alice, trade(bob, &[], 0, TRADE_CONFIRM)),
+        ] {
+            step(&mut world, &sender, &repository, entity_id, message)
+                .await
+                .unwrap();
+        }
+        if let Some(Mob::Player(player)) = world.get_mob_mut(alice) {
+            let item = player.inventory.take(3).unwrap();
+            player.inventory.set(4, item);
+        }
+
+        assert_eq!(
+            step(
+                &mut world,
+                &sender,
+                &repository,
+                bob,
+                trade(alice, &[], 0, TRADE_CONFIRM),
+            )
+            .await,
+            Err(TradeError::InvalidOffer)
+        );
+        assert_eq!(
+            player(&world, alice).inventory.get(4),
+            Some(&Item::from(401u16))
+        );
+        assert!(player(&world, bob).inventory.get(1).is_none());
+        assert_eq!(world.trades().partner_of(alice), None);
+    }
+
+    #[tokio::test]
+    async fn offer_change_requires_locking_again() {
+        let (mut world, repository, [(alice, _), (bob, _)]) = setup().await;
+        let sender = MockPacketSender::default();
+
+        for (entity_id, message) in [
+            (alice, trade(bob, &[], 0, TRADE_OFFER)),
+            (bob, trade(alice, &[], 0, TRADE_OFFER)),
+            (alice, trade(bob, &[], 0, TRADE_LOCK)),
+            (bob, trade(alice, &[0], 0, TRADE_OFFER)),
+        ] {
+            step(&mut world, &sender, &repository, entity_id, message)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            step(
+                &mut world,
+                &sender,
+                &repository,
+                bob,
+                trade(alice, &[], 0, TRADE_LOCK),
+            )
+            .await,
+            Ok(())
+        );
+        assert_eq!(
+            step(
+                &mut world,
+                &sender,
+                &repository,
+                bob,
+                trade(alice, &[], 0, TRADE_CONFIRM),
+            )
+            .await,
+            Err(TradeError::NotLocked)
+        );
+        assert_eq!(
+            step(
+                &mut world,
+                &sender,
+                &repository,
+                alice,
+                trade(bob, &[], 5000, TRADE_OFFER),
+            )
+            .await,
+            Err(TradeError::NotEnoughCoin)
+        );
+    }
+
+    #[tokio::test]
+    async fn walking_away_cancels_the_trade() {
+        let (mut world, repository, [(alice, _), (bob, _)]) = setup().await;
+        let sender = MockPacketSender::default();
+        step(
+            &mut world,
+            &sender,
+            &repository,
+            alice,
+            trade(bob, &[], 0, 0),
+        )
+        .await
+        .unwrap();
+        step(
+            &mut world,
+            &sender,
+            &repository,
+            bob,
+            trade(alice, &[], 0, 0),
+        )
+        .await
+        .unwrap();
+
+        world
+            .move_entity(bob, Position { x: 2100, y: 2110 })
+            .unwrap();
+        cancel_trade_out_of_range(&mut world, bob, &sender).unwrap();
+
+        assert_eq!(world.trades().partner_of(alice), None);
+        for entity_id in [alice, bob] {
+            assert!(
+                sender
+                    .messages_for(entity_id)
+                    .iter()
+                    .any(|packet| packet.identifier == ServerMessage::QuitTrade)
+            );
+        }
+    }
+}
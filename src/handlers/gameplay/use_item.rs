@@ -0,0 +1,370 @@
+use crate::handlers::gameplay::action::{self, ActionError};
+use crate::handlers::gameplay::skill::{self, LearnSkillError};
+use crate::map::EntityId;
+use crate::packets::BroadcastUpdateScore;
+use crate::score::equipment::item_ability;
+use crate::session::{PacketSender, SessionError};
+use crate::world::{Mob, Player, World};
+use odin_models::{MAX_INVENTORY_VISIBLE, effect::Effect, item::Item, position::Position};
+use odin_networking::{
+    WritableResourceError,
+    messages::{
+        client::use_item::UseItemRaw,
+        server::create_item::{CreateItem, SlotType},
+    },
+};
+use std::time::{Duration, Instant};
+
+pub const ITEM_COOLDOWN: Duration = Duration::from_secs(1);
+
+const VOLATILE_POTION: i32 = 1;
+const VOLATILE_RETURN: i32 = 2;
+const VOLATILE_TELEPORT: i32 = 3;
+
+/// Armia, Erion, Azran and Noatun, in the order teleport scrolls index them
+/// through `Effect::Param2`.
+pub const CITIES: [Position; 4] = [
+    Position { x: 2100, y: 2100 },
+    Position { x: 2464, y: 1994 },
+    Position { x: 2480, y: 1716 },
+    Position { x: 1054, y: 1724 },
+];
+
+#[derive(Debug)]
+pub struct UseItem {
+    pub slot: usize,
+}
+
+impl UseItem {
+    pub fn handle<P: PacketSender>(
+        &self,
+        entity_id: EntityId,
+        world: &mut World,
+        sender: &P,
+        now: Instant,
+    ) -> Result<(), UseItemError> {
+        let item_db = world.item_db();
+        let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+            return Err(UseItemError::PlayerNotFound);
+        };
+        if player.computed.score.hp == 0 {
+            return Err(UseItemError::Dead);
+        }
+        let item = *player
+            .inventory
+            .get(self.slot)
+            .ok_or(UseItemError::EmptySlot)?;
+        if item_db.get(item.id).is_none() {
+            return Err(UseItemError::UnknownItem(item.id));
+        }
+        if player
+            .item_cooldowns
+            .get(&item.id)
+            .is_some_and(|ready_at| now < *ready_at)
+        {
+            return Err(UseItemError::OnCooldown);
+        }
+
+        if let Some(book) = world.skills().by_book(item.id) {
+            skill::learn_skill(world, entity_id, book.id, sender)?;
+            consume(world, entity_id, self.slot, now)?;
+            send_slot(world, sender, entity_id, self.slot)?;
+            return Ok(());
+        }
+
+        let volatile = item_ability(&item, Effect::Volatile, item_db);
+        match volatile {
+            VOLATILE_POTION => {
+                let scale = |amount: i32| {
+                    (amount.max(0) as i64 * (100 + player.computed.potion_bonus as i64) / 100)
+                        .max(0) as u32
+                };
+                let hp = scale(item_ability(&item, Effect::HpAdd, item_db));
+                let mp = scale(item_ability(&item, Effect::MpAdd, item_db));
+
+                let player = consume(world, entity_id, self.slot, now)?;
+                let score = &mut player.computed.score;
+                score.hp = score.hp.saturating_add(hp).min(score.max_hp);
+                score.mp = score.mp.saturating_add(mp).min(score.max_mp);
+                send_slot(world, sender, entity_id, self.slot)?;
+                world.broadcast_update_score(entity_id, sender)?;
+            }
+            VOLATILE_RETURN | VOLATILE_TELEPORT => {
+                let destination = if volatile == VOLATILE_RETURN {
+                    let position = world
+                        .map()
+                        .get_position(entity_id)
+                        .ok_or(UseItemError::PlayerNotFound)?;
+                    nearest_city(position)
+                } else {
+                    let city = item_ability(&item, Effect::Param2, item_db);
+                    *usize::try_from(city)
+                        .ok()
+                        .and_then(|city| CITIES.get(city))
+                        .ok_or(UseItemError::InvalidDestination)?
+                };
+
+                action::teleport(world, sender, entity_id, destination)?;
+                consume(world, entity_id, self.slot, now)?;
+                send_slot(world, sender, entity_id, self.slot)?;
+            }
+            _ => return Err(UseItemError::NotUsable),
+        }
+        Ok(())
+    }
+}
+
+/// Takes one unit of the item in `slot`, decrementing its `Effect::Amount`
+/// stack when it has more than one, and starts the item's cooldown.
+fn consume(
+    world: &mut World,
+    entity_id: EntityId,
+    slot: usize,
+    now: Instant,
+) -> Result<&mut Player, UseItemError> {
+    let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) else {
+        return Err(UseItemError::PlayerNotFound);
+    };
+    let mut item = player.inventory.take(slot).ok_or(UseItemError::EmptySlot)?;
+    player.item_cooldowns.insert(item.id, now + ITEM_COOLDOWN);
+    if decrement_amount(&mut item) {
+        player.inventory.set(slot, item);
+    }
+    Ok(player)
+}
+
+fn decrement_amount(item: &mut Item) -> bool {
+    match item
+        .effects
+        .iter_mut()
+        .find(|effect| effect.index == Effect::Amount as u8)
+    {
+        Some(amount) if amount.value > 1 => {
+            amount.value -= 1;
+            true
+        }
+        _ => false,
+    }
+}
+
+fn send_slot<P: PacketSender>(
+    world: &World,
+    sender: &P,
+    entity_id: EntityId,
+    slot: usize,
+) -> Result<(), SessionError> {
+    let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+        return Ok(());
+    };
+    sender.send_to(
+        entity_id,
+        CreateItem {
+            mob_id: entity_id.id() as u16,
+            slot_type: SlotType::Inventory,
+            slot: slot as u16,
+            item: player.inventory.get(slot).copied(),
+        },
+    )
+}
+
+fn nearest_city(position: Position) -> Position {
+    CITIES
+        .into_iter()
+        .min_by_key(|city| city.chebyshev_distance(position))
+        .expect("at least one city")
+}
+
+impl TryFrom<UseItemRaw> for UseItem {
+    type Error = WritableResourceError;
+
+    fn try_from(value: UseItemRaw) -> Result<Self, Self::Error> {
+        if value.src_type != 1 {
+            return Err(WritableResourceError::Generic(
+                "Only inventory items can be used".to_string(),
+            ));
+        }
+        let slot = usize::try_from(value.src_slot)
+            .ok()
+            .filter(|slot| *slot < MAX_INVENTORY_VISIBLE)
+            .ok_or_else(|| WritableResourceError::Generic("Invalid item slot".to_string()))?;
+        Ok(UseItem { slot })
+    }
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum UseItemError {
+    #[error("Player not found in world")]
+    PlayerNotFound,
+
+    #[error("Player is dead")]
+    Dead,
+
+    #[error("Inventory slot is empty")]
+    EmptySlot,
+
+    #[error("Item {0} has no item data")]
+    UnknownItem(u16),
+
+    #[error("Item cannot be used")]
+    NotUsable,
+
+    #[error("Item is on cooldown")]
+    OnCooldown,
+
+    #[error("Teleport item has no valid destination")]
+    InvalidDestination,
+
+    #[error(transparent)]
+    Action(#[from] ActionError),
+
+    #[error(transparent)]
+    LearnSkill(#[from] LearnSkillError),
+
+    #[error(transparent)]
+    Session(#[from] SessionError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::tests::MockPacketSender;
+    use odin_models::{
+        character::Character,
+        item_data::{ItemData, ItemDataEffect, ItemDatabase, MAX_ITEM_DATA_EFFECTS},
+    };
+    use odin_networking::messages::ServerMessage;
+
+    const POTION: u16 = 400;
+    const RETURN_SCROLL: u16 = 401;
+
+    fn item_data(id: u16, effects: &[(Effect, i16)]) -> ItemData {
+        let mut data_effects = [ItemDataEffect::default(); MAX_ITEM_DATA_EFFECTS];
+        for (i, (effect, value)) in effects.iter().enumerate() {
+            data_effects[i] = ItemDataEffect {
+                index: *effect as u8,
+                value: *value,
+            };
+        }
+        ItemData {
+            id,
+            name: "Test".to_string(),
+            mesh: (0, 0),
+            level: 0,
+            str_req: 0,
+            int_req: 0,
+            dex_req: 0,
+            con_req: 0,
+            effects: data_effects,
+            price: 0,
+            unique: 0,
+            pos: 0,
+            extreme: 0,
+            grade: 0,
+        }
+    }
+
+    fn setup(position: Position) -> (World, EntityId) {
+        let mut world = World::new(ItemDatabase::from_items([
+            item_data(
+                POTION,
+                &[
+                    (Effect::Volatile, VOLATILE_POTION as i16),
+                    (Effect::HpAdd, 100),
+                    (Effect::MpAdd, 40),
+                ],
+            ),
+            item_data(RETURN_SCROLL, &[(Effect::Volatile, VOLATILE_RETURN as i16)]),
+        ]));
+        let entity_id = EntityId::Player(1);
+        let mut player = Player::from_character(entity_id, Character::default());
+        player.computed.score.max_hp = 1000;
+        player.computed.score.max_mp = 1000;
+        player.computed.score.hp = 10;
+        player.computed.score.mp = 10;
+        player.computed.potion_bonus = 50;
+        player
+            .inventory
+            .set(0, Item::from((POTION, Effect::Amount as u8, 2u8)));
+        player.inventory.set(1, Item::from(RETURN_SCROLL));
+        world.add_player(entity_id, player, position).unwrap();
+        (world, entity_id)
+    }
+
+    fn player(world: &World, entity_id: EntityId) -> &Player {
+        match world.get_mob(entity_id) {
+            Some(Mob::Player(player)) => player,
+            _ => panic!("player not found"),
+        }
+    }
+
+    #[test]
+    fn potion_restores_scaled_hp_and_consumes_the_stack() {
+        let (mut world, entity_id) = setup(Position { x: 2100, y: 2100 });
+        let sender = MockPacketSender::default();
+        let now = Instant::now();
+
+        UseItem { slot: 0 }
+            .handle(entity_id, &mut world, &sender, now)
+            .unwrap();
+        let score = player(&world, entity_id).computed.score;
+        assert_eq!((score.hp, score.mp), (160, 70));
+        let stack = player(&world, entity_id).inventory.get(0).unwrap();
+        assert_eq!(stack.effects[0].value, 1);
+
+        assert_eq!(
+            UseItem { slot: 0 }.handle(entity_id, &mut world, &sender, now),
+            Err(UseItemError::OnCooldown)
+        );
+        UseItem { slot: 0 }
+            .handle(entity_id, &mut world, &sender, now + ITEM_COOLDOWN)
+            .unwrap();
+        assert!(player(&world, entity_id).inventory.get(0).is_none());
+
+        let identifiers: Vec<_> = sender
+            .messages_for(entity_id)
+            .iter()
+            .map(|packet| packet.identifier)
+            .collect();
+        assert_eq!(
+            identifiers,
+            vec![
+                ServerMessage::CreateItem,
+                ServerMessage::UpdateScore,
+                ServerMessage::CreateItem,
+                ServerMessage::UpdateScore
+            ]
+        );
+    }
+
+    #[test]
+    fn return_scroll_teleports_to_the_nearest_city() {
+        let (mut world, entity_id) = setup(Position { x: 2450, y: 1980 });
+        let sender = MockPacketSender::default();
+
+        UseItem { slot: 1 }
+            .handle(entity_id, &mut world, &sender, Instant::now())
+            .unwrap();
+
+        let position = world.map().get_position(entity_id).unwrap();
+        assert!(position.chebyshev_distance(CITIES[1]) <= 1);
+        assert!(player(&world, entity_id).inventory.get(1).is_none());
+    }
+
+    #[test]
+    fn rejects_unusable_items() {
+        let (mut world, entity_id) = setup(Position { x: 2100, y: 2100 });
+        let sender = MockPacketSender::default();
+        if let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) {
+            player.inventory.set(2, Item::from(999u16));
+        }
+
+        assert_eq!(
+            UseItem { slot: 2 }.handle(entity_id, &mut world, &sender, Instant::now()),
+            Err(UseItemError::UnknownItem(999))
+        );
+        assert_eq!(
+            UseItem { slot: 3 }.handle(entity_id, &mut world, &sender, Instant::now()),
+            Err(UseItemError::EmptySlot)
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     configuration::{CliVer, Configuration, ServerState},
+    locale::MessageKey,
     session::{SessionError, SessionTrait},
 };
 use chrono::{Local, NaiveDateTime};
@@ -8,10 +9,7 @@ use odin_networking::{
     WritableResourceError,
     messages::{
         client::login::LoginMessageRaw,
-        server::{
-            charlist::{CharlistInfo, FirstCharlist},
-            message_panel::MessagePanel,
-        },
+        server::charlist::{CharlistInfo, FirstCharlist},
     },
 };
 use odin_repositories::account_repository::{AccountRepository, AccountRepositoryError};
@@ -40,19 +38,19 @@ impl Authentication {
                 log::error!("{:?}", err);
 
                 let message = match err {
-                    AuthenticationError::InvalidCliVer(_) => {
-                        "Baixe as atualizações pelo launcher ou pelo site"
-                    }
+                    AuthenticationError::InvalidCliVer(_) => MessageKey::OutdatedClient,
                     AuthenticationError::AccountRepositoryError(_)
                     | AuthenticationError::InvalidPassword
                     | AuthenticationError::AccountNotFound
-                    | AuthenticationError::SendError(_) => "Usuário ou senha inválidos",
-                    AuthenticationError::AccountInAnalysis(_) => "Conta está em análise",
-                    AuthenticationError::AccountBlocked(_) => "Conta está banida",
-                    AuthenticationError::Maintenance => "Servidor está em manutenção",
+                    | AuthenticationError::SendError(_) => MessageKey::InvalidCredentials,
+                    AuthenticationError::AccountInAnalysis(_) => MessageKey::AccountInAnalysis,
+                    AuthenticationError::AccountBlocked(_) => MessageKey::AccountBlocked,
+                    AuthenticationError::Maintenance => MessageKey::Maintenance,
                 };
 
-                session.send::<MessagePanel>(message.into()).unwrap();
+                session
+                    .send(configuration.get_message_catalog().panel(message))
+                    .unwrap();
                 Err(err)
             }
         }
@@ -73,7 +71,10 @@ impl Authentication {
             .await?
             .ok_or(AuthenticationError::AccountNotFound)?;
 
-        if account.password != self.password {
+        if !account_repository
+            .check_password(account.identifier, &self.password)
+            .await?
+        {
             return Err(AuthenticationError::InvalidPassword);
         }
 
@@ -172,6 +173,7 @@ mod tests {
         handlers::tests::{MockConfiguration, MockSession, TestAccountRepository},
     };
     use chrono::{Days, Local};
+    use odin_database::password::{PasswordMatch, hash_password, verify_password};
     use odin_models::{
         account::{AccessLevel, Ban, BanType},
         account_charlist::AccountCharlist,
@@ -443,4 +445,90 @@ mod tests {
 
         assert!(result.is_ok())
     }
+
+    #[tokio::test]
+    async fn it_upgrades_a_legacy_plaintext_password_on_login() {
+        let account_repository = TestAccountRepository::new().await;
+        let account_id = Uuid::new_v4();
+        account_repository
+            .add_account(
+                AccountCharlist {
+                    identifier: account_id,
+                    username: "admin".to_string(),
+                    password: "admin".to_string(),
+                    ..Default::default()
+                },
+                None,
+            )
+            .await;
+
+        get_login_message()
+            .handle_impl(
+                &MockSession::default(),
+                &MockConfiguration(CliVer::new(1), ServerState::Open),
+                account_repository.account_repository(),
+            )
+            .await
+            .unwrap();
+
+        let stored = account_repository
+            .account_repository()
+            .fetch_account("admin")
+            .await
+            .unwrap()
+            .unwrap()
+            .password;
+        assert_ne!(stored, "admin");
+        assert_eq!(verify_password("admin", &stored), PasswordMatch::Hashed);
+
+        assert!(
+            get_login_message()
+                .handle_impl(
+                    &MockSession::default(),
+                    &MockConfiguration(CliVer::new(1), ServerState::Open),
+                    account_repository.account_repository(),
+                )
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn it_verifies_a_hashed_password() {
+        let account_repository = TestAccountRepository::new().await;
+        account_repository
+            .add_account(
+                AccountCharlist {
+                    identifier: Uuid::new_v4(),
+                    username: "admin".to_string(),
+                    password: hash_password("admin").unwrap(),
+                    ..Default::default()
+                },
+                None,
+            )
+            .await;
+
+        assert!(
+            get_login_message()
+                .handle_impl(
+                    &MockSession::default(),
+                    &MockConfiguration(CliVer::new(1), ServerState::Open),
+                    account_repository.account_repository(),
+                )
+                .await
+                .is_ok()
+        );
+
+        let result = Authentication {
+            password: "admin2".to_string(),
+            ..get_login_message()
+        }
+        .handle_impl(
+            &MockSession::default(),
+            &MockConfiguration(CliVer::new(1), ServerState::Open),
+            account_repository.account_repository(),
+        )
+        .await;
+        assert!(matches!(result, Err(AuthenticationError::InvalidPassword)));
+    }
 }
@@ -1,3 +1,5 @@
+use crate::configuration::Configuration;
+use crate::locale::MessageKey;
 use crate::session::{SessionError, SessionTrait};
 use odin_models::{
     MAX_CHARACTERS,
@@ -10,10 +12,7 @@ use odin_networking::{
     WritableResourceError,
     messages::{
         client::create_character::CreateCharacterRaw,
-        server::{
-            charlist::{NameAlreadyExistsError, UpdateCharlist},
-            message_panel::MessagePanel,
-        },
+        server::charlist::{NameAlreadyExistsError, UpdateCharlist},
     },
 };
 use odin_repositories::account_repository::{AccountRepository, AccountRepositoryError};
@@ -26,9 +25,10 @@ pub struct CreateCharacter {
     slot: u32,
 }
 impl CreateCharacter {
-    pub async fn handle<A: AccountRepository, S: SessionTrait>(
+    pub async fn handle<A: AccountRepository, S: SessionTrait, C: Configuration>(
         &self,
         session: &S,
+        configuration: &C,
         account_id: Uuid,
         account_repository: A,
     ) -> Result<Vec<(usize, CharacterInfo)>, CreateCharacterError> {
@@ -48,9 +48,11 @@ impl CreateCharacter {
                 log::error!("Could not create character: {e:?}");
 
                 match e {
-                    CreateCharacterError::InvalidNickname(_) => {
-                        session.send::<MessagePanel>("Nome inadequado".into())
-                    }
+                    CreateCharacterError::InvalidNickname(_) => session.send(
+                        configuration
+                            .get_message_catalog()
+                            .panel(MessageKey::InvalidNickname),
+                    ),
                     _ => session.send(NameAlreadyExistsError),
                 }?;
 
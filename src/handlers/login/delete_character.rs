@@ -1,13 +1,12 @@
+use crate::configuration::Configuration;
+use crate::locale::MessageKey;
 use crate::session::{SessionError, SessionTrait};
 use odin_models::{
     EquipmentSlot, account_charlist::CharacterInfo, character::Evolution, uuid::Uuid,
 };
 use odin_networking::{
     WritableResourceError,
-    messages::{
-        client::delete_character::DeleteCharacterRaw,
-        server::{charlist::UpdateCharlist, message_panel::MessagePanel},
-    },
+    messages::{client::delete_character::DeleteCharacterRaw, server::charlist::UpdateCharlist},
 };
 use odin_repositories::account_repository::{AccountRepository, AccountRepositoryError};
 use thiserror::Error;
@@ -18,9 +17,10 @@ pub struct DeleteCharacter {
     password: String,
 }
 impl DeleteCharacter {
-    pub async fn handle<S: SessionTrait, A: AccountRepository>(
+    pub async fn handle<S: SessionTrait, A: AccountRepository, C: Configuration>(
         &self,
         session: &S,
+        configuration: &C,
         account_id: Uuid,
         account_repository: A,
     ) -> Result<Vec<(usize, CharacterInfo)>, DeleteCharacterError> {
@@ -37,22 +37,15 @@ impl DeleteCharacter {
                 Ok(new_charlist)
             }
             Err(e) => {
-                match e {
-                    DeleteCharacterError::EquippedItems => session.send::<MessagePanel>(
-                        "Desequipe todos os itens do personagem antes de deletar".into(),
-                    )?,
-                    DeleteCharacterError::InventoryItems => session.send::<MessagePanel>(
-                        "Limpe seu inventário antes de deletar o personagem".into(),
-                    )?,
-                    DeleteCharacterError::IncorrectPassword => {
-                        session.send::<MessagePanel>("Senha incorreta".into())?
-                    }
-                    DeleteCharacterError::Evolution(_) => session
-                        .send::<MessagePanel>("Só é possível deletar personagens mortais".into())?,
-                    DeleteCharacterError::Coin => session
-                        .send::<MessagePanel>("Remova o gold do inventário para deletar".into())?,
-                    _ => session.send::<MessagePanel>("Falha ao deletar".into())?,
+                let message = match e {
+                    DeleteCharacterError::EquippedItems => MessageKey::DeleteEquippedItems,
+                    DeleteCharacterError::InventoryItems => MessageKey::DeleteInventoryItems,
+                    DeleteCharacterError::IncorrectPassword => MessageKey::IncorrectPassword,
+                    DeleteCharacterError::Evolution(_) => MessageKey::DeleteMortalOnly,
+                    DeleteCharacterError::Coin => MessageKey::DeleteCoin,
+                    _ => MessageKey::DeleteFailed,
                 };
+                session.send(configuration.get_message_catalog().panel(message))?;
 
                 Err(e)
             }
@@ -161,6 +154,7 @@ impl From<AccountRepositoryError> for DeleteCharacterError {
 mod tests {
     use super::*;
     use crate::handlers::tests::TestAccountRepository;
+    use odin_database::password::hash_password;
     use odin_models::{
         account_charlist::AccountCharlist, character::Character, item::Item, uuid::Uuid,
     };
@@ -447,4 +441,40 @@ mod tests {
             Err(e) => panic!("Expected DeleteCharacterError::IncorrectPassword, got {e:?}"),
         }
     }
+
+    #[tokio::test]
+    async fn it_checks_a_hashed_password() {
+        let repository = TestAccountRepository::new().await;
+        let account_id = repository
+            .add_account_with_characters(
+                AccountCharlist {
+                    identifier: Uuid::new_v4(),
+                    username: "admin".to_string(),
+                    password: hash_password("admin").unwrap(),
+                    ..Default::default()
+                },
+                vec![Character {
+                    name: "charlist".to_string(),
+                    ..Default::default()
+                }],
+            )
+            .await;
+
+        let r = DeleteCharacter {
+            password: "admin2".to_string(),
+            slot: 0,
+        }
+        .handle_impl(account_id, repository.account_repository())
+        .await;
+        assert!(matches!(r, Err(DeleteCharacterError::IncorrectPassword)));
+
+        let charlist = DeleteCharacter {
+            password: "admin".to_string(),
+            slot: 0,
+        }
+        .handle_impl(account_id, repository.account_repository())
+        .await
+        .unwrap();
+        assert!(charlist.is_empty());
+    }
 }
@@ -1,5 +1,5 @@
 use crate::map::EntityId;
-use crate::packets::{BroadcastUpdateScore, ToCharacterLogin};
+use crate::packets::{BroadcastUpdateScore, ToCharacterLogin, ToGroundItemPackets};
 use crate::session::{PacketSender, SessionError};
 use crate::world::{Mob, Player, World};
 use crate::{map::MapError, packets::ToCreateMob};
@@ -26,14 +26,14 @@ impl EnterWorld {
             .fetch_character(account_id, self.slot as usize)
             .await?
             .ok_or(EnterWorldError::CharacterNotFound)?;
+        let storage = account_repository.fetch_storage(account_id).await?;
 
         let position = character.last_pos;
         let entity_id = EntityId::Player(client_id);
-        let insert_result = world.add_player(
-            entity_id,
-            Player::from_character(entity_id, character),
-            position,
-        )?;
+        let mut player = Player::from_character(entity_id, character);
+        player.account_id = account_id;
+        *player.storage = storage;
+        let insert_result = world.add_player(entity_id, player, position)?;
         world.recalculate_score(entity_id);
 
         {
@@ -68,10 +68,16 @@ impl EnterWorld {
                 .get_position(spectator_entity)
                 .expect("spectator from map must have a position");
 
-            sender.send_to(entity_id, spectator.to_create_mob(spectator_pos))?;
+            if !spectator.is_invisible() {
+                spectator.send_create_mob(entity_id, spectator_pos, sender)?;
+            }
             sender.send_to(spectator_entity, my_create_mob.clone())?;
         }
 
+        for ground_item in world.map().ground_items_in_view(position) {
+            sender.send_to(entity_id, ground_item.to_create_ground_item())?;
+        }
+
         Ok(())
     }
 }
@@ -356,6 +362,59 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn enter_world_player_does_not_receive_invisible_spectator() {
+        let repository = TestAccountRepository::new().await;
+        let sender = MockPacketSender::default();
+        let mut world = World::default();
+
+        let gm_id = EntityId::Player(10);
+        let mut gm = Player::from_character(
+            gm_id,
+            Character {
+                identifier: Uuid::new_v4(),
+                name: "Hidden".to_string(),
+                last_pos: Position { x: 2101, y: 2101 },
+                ..Default::default()
+            },
+        );
+        gm.invisible = true;
+        world
+            .add_player(gm_id, gm, Position { x: 2101, y: 2101 })
+            .unwrap();
+
+        let entity_id = EntityId::Player(1);
+        let account_id =
+            setup_account_with_character(&repository, Position { x: 2100, y: 2100 }).await;
+
+        enter_world(0)
+            .handle(
+                account_id,
+                entity_id.id(),
+                repository.account_repository(),
+                &sender,
+                &mut world,
+            )
+            .await
+            .unwrap();
+
+        let create_mobs = sender
+            .messages_for(entity_id)
+            .into_iter()
+            .filter(|message| message.identifier == ServerMessage::CreateMob)
+            .count();
+        // Only the player's own CreateMob
+        assert_eq!(create_mobs, 1);
+
+        let gm_messages = sender.messages_for(gm_id);
+        assert!(
+            gm_messages
+                .iter()
+                .any(|message| message.identifier == ServerMessage::CreateMob),
+            "the invisible GM still sees the entering player"
+        );
+    }
+
     #[tokio::test]
     async fn enter_world_at_occupied_position_finds_nearby() {
         let repository = TestAccountRepository::new().await;
@@ -1,4 +1,9 @@
-use crate::session::{SessionError, SessionTrait};
+use crate::{
+    configuration::Configuration,
+    locale::MessageKey,
+    session::{SessionError, SessionTrait},
+};
+use chrono::{Local, NaiveDateTime, TimeDelta};
 use odin_models::uuid::Uuid;
 use odin_networking::{
     WritableResourceError,
@@ -16,15 +21,16 @@ pub struct NumericToken {
     changing: bool,
 }
 impl NumericToken {
-    pub async fn handle<A: AccountRepository, S: SessionTrait>(
+    pub async fn handle<A: AccountRepository, S: SessionTrait, C: Configuration>(
         &self,
         session: &S,
+        configuration: &C,
         account_id: Uuid,
         valid_token: bool,
         account_repository: A,
     ) -> Result<(), NumericTokenError> {
         match self
-            .handle_impl(account_id, valid_token, account_repository)
+            .handle_impl(configuration, account_id, valid_token, account_repository)
             .await
         {
             Ok(_) => {
@@ -37,26 +43,44 @@ impl NumericToken {
             }
             Err(err) => {
                 session.send(IncorrectNumericToken)?;
+                if err.should_disconnect() {
+                    session.send(
+                        configuration
+                            .get_message_catalog()
+                            .panel(MessageKey::NumericTokenLocked),
+                    )?;
+                }
 
                 Err(err)
             }
         }
     }
 
-    pub async fn handle_impl<A: AccountRepository>(
+    pub async fn handle_impl<A: AccountRepository, C: Configuration>(
         &self,
+        configuration: &C,
         account_id: Uuid,
         valid_token: bool,
         account_repository: A,
     ) -> Result<(), NumericTokenError> {
+        let now = Local::now().naive_local();
+        if let Some(locked_until) = account_repository.get_token_lock(account_id).await?
+            && locked_until > now
+        {
+            return Err(NumericTokenError::Locked(locked_until));
+        }
+
         let current_token = account_repository.get_token(account_id).await?;
         match current_token {
             Some(current_token) => match (self.changing, valid_token) {
                 (true, false) => return Err(NumericTokenError::IncorrectState),
                 (false, false) => {
                     if *current_token != self.token {
-                        return Err(NumericTokenError::IncorrectToken(self.token.clone()));
+                        return Err(self
+                            .register_failure(configuration, account_id, now, &account_repository)
+                            .await);
                     }
+                    account_repository.reset_token_failures(account_id).await?;
                 }
                 (true, true) => {
                     account_repository
@@ -74,6 +98,36 @@ impl NumericToken {
 
         Ok(())
     }
+
+    async fn register_failure<A: AccountRepository, C: Configuration>(
+        &self,
+        configuration: &C,
+        account_id: Uuid,
+        now: NaiveDateTime,
+        account_repository: &A,
+    ) -> NumericTokenError {
+        let failures = match account_repository.record_token_failure(account_id).await {
+            Ok(failures) => failures,
+            Err(err) => return err.into(),
+        };
+
+        let config = configuration.get_numeric_token_config();
+        if failures < config.max_attempts {
+            return NumericTokenError::IncorrectToken(self.token.clone());
+        }
+
+        let locked_until = TimeDelta::from_std(config.lockout())
+            .ok()
+            .and_then(|lockout| now.checked_add_signed(lockout))
+            .unwrap_or(NaiveDateTime::MAX);
+        match account_repository
+            .lock_token(account_id, locked_until)
+            .await
+        {
+            Ok(()) => NumericTokenError::TooManyAttempts(failures),
+            Err(err) => err.into(),
+        }
+    }
 }
 impl TryFrom<NumericTokenRaw> for NumericToken {
     type Error = WritableResourceError;
@@ -96,6 +150,12 @@ pub enum NumericTokenError {
     #[error("You need to input the token before changing the password")]
     IncorrectState,
 
+    #[error("Too many incorrect tokens: {0}")]
+    TooManyAttempts(u32),
+
+    #[error("Token is locked until {0}")]
+    Locked(NaiveDateTime),
+
     #[error(transparent)]
     AccountRepositoryError(#[from] AccountRepositoryError),
 
@@ -103,12 +163,28 @@ pub enum NumericTokenError {
     SessionError(#[from] SessionError),
 }
 
+impl NumericTokenError {
+    pub fn should_disconnect(&self) -> bool {
+        matches!(
+            self,
+            NumericTokenError::TooManyAttempts(_) | NumericTokenError::Locked(_)
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::handlers::tests::{MockSession, TestAccountRepository};
+    use crate::{
+        configuration::{CliVer, ServerState},
+        handlers::tests::{MockConfiguration, MockSession, TestAccountRepository},
+    };
     use odin_models::account_charlist::AccountCharlist;
 
+    fn configuration() -> MockConfiguration {
+        MockConfiguration(CliVer::new(0), ServerState::Open)
+    }
+
     fn new_account() -> AccountCharlist {
         AccountCharlist {
             identifier: Uuid::new_v4(),
@@ -129,7 +205,12 @@ mod tests {
             token: "1208".to_string(),
             changing: false,
         }
-        .handle_impl(account.identifier, false, repository.account_repository())
+        .handle_impl(
+            &configuration(),
+            account.identifier,
+            false,
+            repository.account_repository(),
+        )
         .await
         .unwrap();
 
@@ -159,7 +240,12 @@ mod tests {
                     token: "1111".to_string(),
                     changing: false,
                 }
-                .handle_impl(account.identifier, false, repository.account_repository(),)
+                .handle_impl(
+                    &configuration(),
+                    account.identifier,
+                    false,
+                    repository.account_repository(),
+                )
                 .await,
                 Err(NumericTokenError::IncorrectToken(_))
             ));
@@ -188,6 +274,7 @@ mod tests {
                     changing: false,
                 }
                 .handle_impl(
+                    &configuration(),
                     account.identifier,
                     false,
                     repository.account_repository().clone()
@@ -207,6 +294,126 @@ mod tests {
             assert_eq!(token_after, original_token);
         }
 
+        async fn try_token(
+            repository: &TestAccountRepository,
+            account_id: Uuid,
+            token: &str,
+        ) -> Result<(), NumericTokenError> {
+            NumericToken {
+                token: token.to_string(),
+                changing: false,
+            }
+            .handle(
+                &MockSession::default(),
+                &configuration(),
+                account_id,
+                false,
+                repository.account_repository(),
+            )
+            .await
+        }
+
+        #[tokio::test]
+        async fn when_the_user_reaches_the_attempt_limit_then_the_token_is_locked() {
+            let repository = TestAccountRepository::new().await;
+            let account = new_account();
+            repository
+                .add_account(account.clone(), Some("1208".to_string()))
+                .await;
+
+            for _ in 0..2 {
+                assert!(matches!(
+                    try_token(&repository, account.identifier, "1111").await,
+                    Err(NumericTokenError::IncorrectToken(_))
+                ));
+            }
+            let err = try_token(&repository, account.identifier, "1111")
+                .await
+                .unwrap_err();
+            assert_eq!(err, NumericTokenError::TooManyAttempts(3));
+            assert!(err.should_disconnect());
+
+            let err = try_token(&repository, account.identifier, "1208")
+                .await
+                .unwrap_err();
+            assert!(matches!(err, NumericTokenError::Locked(_)));
+            assert!(err.should_disconnect());
+        }
+
+        #[tokio::test]
+        async fn when_the_user_reconnects_then_the_failures_are_not_reset() {
+            let repository = TestAccountRepository::new().await;
+            let account = new_account();
+            repository
+                .add_account(account.clone(), Some("1208".to_string()))
+                .await;
+
+            // Every attempt runs through a new session, as after reconnecting
+            try_token(&repository, account.identifier, "1111")
+                .await
+                .unwrap_err();
+            try_token(&repository, account.identifier, "2222")
+                .await
+                .unwrap_err();
+
+            assert_eq!(
+                try_token(&repository, account.identifier, "3333").await,
+                Err(NumericTokenError::TooManyAttempts(3))
+            );
+            assert!(matches!(
+                try_token(&repository, account.identifier, "1208").await,
+                Err(NumericTokenError::Locked(_))
+            ));
+        }
+
+        #[tokio::test]
+        async fn when_the_user_enters_the_correct_token_then_the_failures_are_reset() {
+            let repository = TestAccountRepository::new().await;
+            let account = new_account();
+            repository
+                .add_account(account.clone(), Some("1208".to_string()))
+                .await;
+
+            for _ in 0..2 {
+                try_token(&repository, account.identifier, "1111")
+                    .await
+                    .unwrap_err();
+            }
+            try_token(&repository, account.identifier, "1208")
+                .await
+                .unwrap();
+
+            for _ in 0..2 {
+                assert!(matches!(
+                    try_token(&repository, account.identifier, "1111").await,
+                    Err(NumericTokenError::IncorrectToken(_))
+                ));
+            }
+        }
+
+        #[tokio::test]
+        async fn when_the_lockout_expired_then_the_user_can_enter_the_token() {
+            let repository = TestAccountRepository::new().await;
+            let account = new_account();
+            repository
+                .add_account(account.clone(), Some("1208".to_string()))
+                .await;
+            repository
+                .account_repository()
+                .lock_token(
+                    account.identifier,
+                    Local::now().naive_local() - TimeDelta::minutes(1),
+                )
+                .await
+                .unwrap();
+
+            assert!(
+                try_token(&repository, account.identifier, "1208")
+                    .await
+                    .is_ok()
+            );
+        }
+
         mod when_the_user_tries_to_change_the_token {
             use super::*;
 
@@ -225,6 +432,7 @@ mod tests {
                     }
                     .handle(
                         &MockSession::default(),
+                        &configuration(),
                         account.identifier,
                         false,
                         repository.account_repository()
@@ -246,7 +454,12 @@ mod tests {
                     token: "1111".to_string(),
                     changing: true,
                 }
-                .handle_impl(account.identifier, true, repository.account_repository())
+                .handle_impl(
+                    &configuration(),
+                    account.identifier,
+                    true,
+                    repository.account_repository(),
+                )
                 .await
                 .unwrap();
 
@@ -0,0 +1,335 @@
+use odin_networking::messages::server::message_panel::MessagePanel;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Locale of the built-in catalog, used when the configured locale can't be
+/// loaded.
+pub const DEFAULT_LOCALE: &str = "pt-BR";
+static DEFAULT_CATALOG: LazyLock<MessageCatalog> = LazyLock::new(|| {
+    MessageCatalog::from_toml(include_str!("../locales/pt-BR.toml"))
+        .expect("built-in locale must parse")
+});
+
+/// Texts sent to players. Each key lives under a `[section]` of the locale
+/// file, e.g. `login.maintenance`. Texts may have `{name}` placeholders,
+/// filled in by [`MessageCatalog::format`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageKey {
+    OutdatedClient,
+    InvalidCredentials,
+    AccountInAnalysis,
+    AccountBlocked,
+    Maintenance,
+    NumericTokenLocked,
+    InvalidNickname,
+    DeleteEquippedItems,
+    DeleteInventoryItems,
+    IncorrectPassword,
+    DeleteMortalOnly,
+    DeleteCoin,
+    DeleteFailed,
+    PlayerNotConnected,
+    NotInParty,
+    NotInGuild,
+    ShoutCooldown,
+    GuildUsage,
+    GuildInvalidName,
+    GuildNameTaken,
+    GuildNotEnoughCoin,
+    AlreadyInGuild,
+    GuildNotAllowed,
+    NotGuildMember,
+    NoGuildInvite,
+    GuildCreated,
+    GuildInvited,
+    GuildJoined,
+    GuildKicked,
+    GuildLeft,
+    GuildPromoted,
+    GuildDisbanded,
+    CommandEmpty,
+    CommandUnknown,
+    CommandPermissionDenied,
+    CommandUsage,
+    CommandPlayerNotFound,
+    CommandMobNotFound,
+    CommandIssuerNotFound,
+    CommandOutOfBounds,
+    CommandTeleported,
+    CommandSummoned,
+    CommandInvisibilityEnabled,
+    CommandInvisibilityDisabled,
+    CommandUnknownSpawnGroup,
+    CommandSpawned,
+    CommandKilled,
+    CommandUnknownItem,
+    CommandInventoryFull,
+    CommandItemCreated,
+    CommandMaxLevel,
+    CommandLevelSet,
+    CommandNegativeCoin,
+    CommandCoinSet,
+    CommandMobsLoadFailed,
+    CommandSpawnsLoadFailed,
+    CommandReloaded,
+}
+impl MessageKey {
+    pub const ALL: [MessageKey; 57] = [
+        MessageKey::OutdatedClient,
+        MessageKey::InvalidCredentials,
+        MessageKey::AccountInAnalysis,
+        MessageKey::AccountBlocked,
+        MessageKey::Maintenance,
+        MessageKey::NumericTokenLocked,
+        MessageKey::InvalidNickname,
+        MessageKey::DeleteEquippedItems,
+        MessageKey::DeleteInventoryItems,
+        MessageKey::IncorrectPassword,
+        MessageKey::DeleteMortalOnly,
+        MessageKey::DeleteCoin,
+        MessageKey::DeleteFailed,
+        MessageKey::PlayerNotConnected,
+        MessageKey::NotInParty,
+        MessageKey::NotInGuild,
+        MessageKey::ShoutCooldown,
+        MessageKey::GuildUsage,
+        MessageKey::GuildInvalidName,
+        MessageKey::GuildNameTaken,
+        MessageKey::GuildNotEnoughCoin,
+        MessageKey::AlreadyInGuild,
+        MessageKey::GuildNotAllowed,
+        MessageKey::NotGuildMember,
+        MessageKey::NoGuildInvite,
+        MessageKey::GuildCreated,
+        MessageKey::GuildInvited,
+        MessageKey::GuildJoined,
+        MessageKey::GuildKicked,
+        MessageKey::GuildLeft,
+        MessageKey::GuildPromoted,
+        MessageKey::GuildDisbanded,
+        MessageKey::CommandEmpty,
+        MessageKey::CommandUnknown,
+        MessageKey::CommandPermissionDenied,
+        MessageKey::CommandUsage,
+        MessageKey::CommandPlayerNotFound,
+        MessageKey::CommandMobNotFound,
+        MessageKey::CommandIssuerNotFound,
+        MessageKey::CommandOutOfBounds,
+        MessageKey::CommandTeleported,
+        MessageKey::CommandSummoned,
+        MessageKey::CommandInvisibilityEnabled,
+        MessageKey::CommandInvisibilityDisabled,
+        MessageKey::CommandUnknownSpawnGroup,
+        MessageKey::CommandSpawned,
+        MessageKey::CommandKilled,
+        MessageKey::CommandUnknownItem,
+        MessageKey::CommandInventoryFull,
+        MessageKey::CommandItemCreated,
+        MessageKey::CommandMaxLevel,
+        MessageKey::CommandLevelSet,
+        MessageKey::CommandNegativeCoin,
+        MessageKey::CommandCoinSet,
+        MessageKey::CommandMobsLoadFailed,
+        MessageKey::CommandSpawnsLoadFailed,
+        MessageKey::CommandReloaded,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            MessageKey::OutdatedClient => "login.outdated_client",
+            MessageKey::InvalidCredentials => "login.invalid_credentials",
+            MessageKey::AccountInAnalysis => "login.account_in_analysis",
+            MessageKey::AccountBlocked => "login.account_blocked",
+            MessageKey::Maintenance => "login.maintenance",
+            MessageKey::NumericTokenLocked => "login.numeric_token_locked",
+            MessageKey::InvalidNickname => "character.invalid_nickname",
+            MessageKey::DeleteEquippedItems => "character.delete_equipped_items",
+            MessageKey::DeleteInventoryItems => "character.delete_inventory_items",
+            MessageKey::IncorrectPassword => "character.incorrect_password",
+            MessageKey::DeleteMortalOnly => "character.delete_mortal_only",
+            MessageKey::DeleteCoin => "character.delete_coin",
+            MessageKey::DeleteFailed => "character.delete_failed",
+            MessageKey::PlayerNotConnected => "chat.player_not_connected",
+            MessageKey::NotInParty => "chat.not_in_party",
+            MessageKey::NotInGuild => "chat.not_in_guild",
+            MessageKey::ShoutCooldown => "chat.shout_cooldown",
+            MessageKey::GuildUsage => "guild.usage",
+            MessageKey::GuildInvalidName => "guild.invalid_name",
+            MessageKey::GuildNameTaken => "guild.name_taken",
+            MessageKey::GuildNotEnoughCoin => "guild.not_enough_coin",
+            MessageKey::AlreadyInGuild => "guild.already_in_guild",
+            MessageKey::GuildNotAllowed => "guild.not_allowed",
+            MessageKey::NotGuildMember => "guild.not_member",
+            MessageKey::NoGuildInvite => "guild.no_invite",
+            MessageKey::GuildCreated => "guild.created",
+            MessageKey::GuildInvited => "guild.invited",
+            MessageKey::GuildJoined => "guild.joined",
+            MessageKey::GuildKicked => "guild.kicked",
+            MessageKey::GuildLeft => "guild.left",
+            MessageKey::GuildPromoted => "guild.promoted",
+            MessageKey::GuildDisbanded => "guild.disbanded",
+            MessageKey::CommandEmpty => "command.empty",
+            MessageKey::CommandUnknown => "command.unknown",
+            MessageKey::CommandPermissionDenied => "command.permission_denied",
+            MessageKey::CommandUsage => "command.usage",
+            MessageKey::CommandPlayerNotFound => "command.player_not_found",
+            MessageKey::CommandMobNotFound => "command.mob_not_found",
+            MessageKey::CommandIssuerNotFound => "command.issuer_not_found",
+            MessageKey::CommandOutOfBounds => "command.out_of_bounds",
+            MessageKey::CommandTeleported => "command.teleported",
+            MessageKey::CommandSummoned => "command.summoned",
+            MessageKey::CommandInvisibilityEnabled => "command.invisibility_enabled",
+            MessageKey::CommandInvisibilityDisabled => "command.invisibility_disabled",
+            MessageKey::CommandUnknownSpawnGroup => "command.unknown_spawn_group",
+            MessageKey::CommandSpawned => "command.spawned",
+            MessageKey::CommandKilled => "command.killed",
+            MessageKey::CommandUnknownItem => "command.unknown_item",
+            MessageKey::CommandInventoryFull => "command.inventory_full",
+            MessageKey::CommandItemCreated => "command.item_created",
+            MessageKey::CommandMaxLevel => "command.max_level",
+            MessageKey::CommandLevelSet => "command.level_set",
+            MessageKey::CommandNegativeCoin => "command.negative_coin",
+            MessageKey::CommandCoinSet => "command.coin_set",
+            MessageKey::CommandMobsLoadFailed => "command.mobs_load_failed",
+            MessageKey::CommandSpawnsLoadFailed => "command.spawns_load_failed",
+            MessageKey::CommandReloaded => "command.reloaded",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MessageCatalog {
+    messages: HashMap<String, String>,
+}
+
+impl MessageCatalog {
+    /// The built-in catalog of the default locale.
+    pub fn builtin() -> &'static MessageCatalog {
+        &DEFAULT_CATALOG
+    }
+
+    /// Loads `<locale>.toml` from the `locales` directory.
+    pub fn load(locales: &Path, locale: &str) -> Result<Self, MessageCatalogError> {
+        let contents = std::fs::read_to_string(locales.join(format!("{locale}.toml")))?;
+        Self::from_toml(&contents)
+    }
+
+    pub fn from_toml(contents: &str) -> Result<Self, MessageCatalogError> {
+        let sections: HashMap<String, HashMap<String, String>> = toml::from_str(contents)?;
+        let messages = sections
+            .into_iter()
+            .flat_map(|(section, messages)| {
+                messages
+                    .into_iter()
+                    .map(move |(name, message)| (format!("{section}.{name}"), message))
+            })
+            .collect();
+        Ok(Self { messages })
+    }
+
+    /// The localized text, or the key itself when the locale lacks it.
+    pub fn get(&self, key: MessageKey) -> &str {
+        self.messages
+            .get(key.key())
+            .map_or(key.key(), String::as_str)
+    }
+
+    /// The localized text with every `{name}` placeholder replaced by the
+    /// value given for `name`.
+    pub fn format(&self, key: MessageKey, args: &[(&str, &dyn Display)]) -> String {
+        args.iter()
+            .fold(self.get(key).to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{name}}}"), &value.to_string())
+            })
+    }
+
+    pub fn panel(&self, key: MessageKey) -> MessagePanel {
+        MessagePanel::from(self.get(key))
+    }
+
+    pub fn missing_keys(&self) -> Vec<MessageKey> {
+        MessageKey::ALL
+            .into_iter()
+            .filter(|key| !self.messages.contains_key(key.key()))
+            .collect()
+    }
+}
+
+impl Default for MessageCatalog {
+    fn default() -> Self {
+        Self::builtin().clone()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MessageCatalogError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("TOML parse error: {0}")]
+    TomlParse(#[from] toml::de::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn shipped_locales() -> Vec<(String, MessageCatalog)> {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("locales");
+        std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .map(|path| {
+                let locale = path.file_stem().unwrap().to_string_lossy().into_owned();
+                let catalog = MessageCatalog::load(&dir, &locale).unwrap();
+                (locale, catalog)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn every_shipped_locale_has_every_key() {
+        let locales = shipped_locales();
+        assert!(locales.iter().any(|(locale, _)| locale == DEFAULT_LOCALE));
+        for (locale, catalog) in locales {
+            assert_eq!(catalog.missing_keys(), vec![], "{locale} is missing keys");
+        }
+    }
+
+    #[test]
+    fn keys_are_unique() {
+        let keys: HashSet<_> = MessageKey::ALL.iter().map(|key| key.key()).collect();
+        assert_eq!(keys.len(), MessageKey::ALL.len());
+    }
+
+    #[test]
+    fn format_fills_in_placeholders() {
+        let catalog =
+            MessageCatalog::from_toml("[command]\nlevel_set = \"{name} is now {level}\"").unwrap();
+
+        assert_eq!(
+            catalog.format(
+                MessageKey::CommandLevelSet,
+                &[("name", &"Gm"), ("level", &50)]
+            ),
+            "Gm is now 50"
+        );
+        assert_eq!(
+            catalog.format(MessageKey::CommandCoinSet, &[("name", &"Gm")]),
+            "command.coin_set"
+        );
+    }
+
+    #[test]
+    fn missing_messages_fall_back_to_the_key() {
+        let catalog = MessageCatalog::from_toml("[login]\nmaintenance = \"Closed\"").unwrap();
+
+        assert_eq!(catalog.get(MessageKey::Maintenance), "Closed");
+        assert_eq!(catalog.get(MessageKey::NotInParty), "chat.not_in_party");
+        assert!(catalog.missing_keys().contains(&MessageKey::NotInParty));
+    }
+}
@@ -1,30 +1,40 @@
+pub mod admin;
+pub mod affect;
 pub mod client_id_manager;
+pub mod commands;
 pub mod configuration;
 pub mod game_server_context;
 pub mod handlers;
+pub mod locale;
 pub mod map;
 pub mod message;
 pub mod npc;
 pub mod packets;
+pub mod party;
+pub mod persistence;
+pub mod regen;
 pub mod score;
 pub mod session;
+pub mod skill;
+pub mod stall;
+pub mod trade;
 pub mod user_session;
 pub mod world;
 
 use bytes::Bytes;
 use clap::Parser;
-use client_id_manager::ClientIdManager;
+use configuration::ServerConfig;
 use deku::DekuContainerRead;
 use game_server_context::GameServerContext;
-use map::EntityId;
+use handlers::gameplay::ground_item::decay_ground_items;
 use message::{Message, MessageError};
 use odin_database::DatabaseService;
 use odin_models::item_data::ItemDatabase;
 use odin_networking::{
     enc_session::EncDecSession, framed_message::HandshakeState, messages::header::Header,
 };
-use session::PacketSender;
-use std::{net::SocketAddr, path::Path, rc::Rc, time::Instant};
+use score::experience::ExperienceTable;
+use std::{net::SocketAddr, path::PathBuf, rc::Rc, time::Instant};
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt},
     net::TcpListener,
@@ -33,41 +43,6 @@ use tokio::{
 use user_session::{SenderSession, UserSession};
 use world::World;
 
-const KEYTABLE: [u8; 512] = [
-    0x14, 0x17, 0x47, 0x67, 0x7A, 0x09, 0x21, 0x0D, 0x5B, 0x5B, 0x15, 0x0D, 0x17, 0x11, 0x21, 0x0C,
-    0x1F, 0x03, 0x21, 0x21, 0x17, 0x0D, 0x1D, 0x0D, 0x16, 0x1F, 0x03, 0x1F, 0x71, 0x6D, 0x15, 0x0D,
-    0x15, 0x0D, 0x15, 0x13, 0x17, 0x2C, 0x15, 0x43, 0x1D, 0x72, 0x17, 0x29, 0x1F, 0x09, 0x15, 0x16,
-    0x47, 0x0D, 0x67, 0x6D, 0x79, 0x0D, 0x67, 0x0D, 0x15, 0x09, 0x15, 0x0D, 0x1F, 0x71, 0x17, 0x0E,
-    0x33, 0x17, 0x05, 0x09, 0x6F, 0x73, 0x5B, 0x13, 0x33, 0x32, 0x3E, 0x1E, 0x24, 0x0D, 0x6E, 0x0E,
-    0x15, 0x0A, 0x15, 0x3F, 0x5D, 0x0D, 0x17, 0x35, 0x17, 0x0D, 0x71, 0x0D, 0x18, 0x0D, 0x25, 0x21,
-    0x33, 0x0D, 0x17, 0x0C, 0x1D, 0x0A, 0x15, 0x17, 0x27, 0x0C, 0x15, 0x0D, 0x3C, 0x10, 0x4B, 0x09,
-    0x14, 0x2B, 0x6B, 0x35, 0x67, 0x1F, 0x15, 0x1F, 0x15, 0x0E, 0x15, 0x10, 0x15, 0x28, 0x05, 0x2D,
-    0x33, 0x2A, 0x1D, 0x29, 0x17, 0x0C, 0x15, 0x0D, 0x14, 0x0D, 0x15, 0x0E, 0x77, 0x27, 0x1D, 0x1F,
-    0x15, 0x0B, 0x7A, 0x0D, 0x3D, 0x10, 0x3D, 0x0D, 0x47, 0x3F, 0x1D, 0x0D, 0x79, 0x4D, 0x15, 0x0D,
-    0x17, 0x47, 0x33, 0x0D, 0x77, 0x47, 0x33, 0x1C, 0x17, 0x0E, 0x15, 0x35, 0x0D, 0x06, 0x45, 0x49,
-    0x1D, 0x7F, 0x33, 0x0D, 0x17, 0x2B, 0x15, 0x1C, 0x71, 0x31, 0x1D, 0x0F, 0x17, 0x0D, 0x14, 0x0A,
-    0x14, 0x0B, 0x71, 0x16, 0x78, 0x7F, 0x61, 0x09, 0x15, 0x29, 0x63, 0x25, 0x53, 0x57, 0x29, 0x0D,
-    0x77, 0x1C, 0x47, 0x0C, 0x33, 0x0D, 0x15, 0x0D, 0x5B, 0x09, 0x31, 0x35, 0x17, 0x0D, 0x29, 0x0D,
-    0x1D, 0x0D, 0x25, 0x21, 0x33, 0x0D, 0x17, 0x0C, 0x15, 0x0A, 0x15, 0x3F, 0x5D, 0x0D, 0x17, 0x0D,
-    0x79, 0x4D, 0x15, 0x0D, 0x25, 0x09, 0x15, 0x0D, 0x51, 0x0B, 0x7A, 0x0D, 0x47, 0x0D, 0x15, 0x0D,
-    0x15, 0x0D, 0x1D, 0x0D, 0x79, 0x03, 0x15, 0x09, 0x15, 0x0D, 0x67, 0x0D, 0x15, 0x71, 0x49, 0x71,
-    0x1F, 0x75, 0x15, 0x16, 0x3D, 0x0D, 0x67, 0x6D, 0x33, 0x1E, 0x76, 0x0D, 0x6E, 0x0E, 0x3E, 0x1E,
-    0x1F, 0x71, 0x19, 0x0E, 0x33, 0x0D, 0x05, 0x09, 0x33, 0x71, 0x5B, 0x13, 0x1C, 0x1F, 0x15, 0x0B,
-    0x15, 0x0E, 0x1F, 0x10, 0x15, 0x28, 0x05, 0x0A, 0x15, 0x2A, 0x1D, 0x71, 0x1F, 0x0C, 0x19, 0x1C,
-    0x15, 0x1B, 0x33, 0x79, 0x17, 0x0B, 0x33, 0x1C, 0x2F, 0x47, 0x31, 0x0A, 0x18, 0x0E, 0x1F, 0x35,
-    0x0D, 0x10, 0x47, 0x49, 0x28, 0x4F, 0x5B, 0x29, 0x15, 0x35, 0x21, 0x10, 0x17, 0x11, 0x17, 0x0C,
-    0x1F, 0x03, 0x21, 0x21, 0x14, 0x17, 0x47, 0x67, 0x16, 0x09, 0x71, 0x6D, 0x15, 0x0A, 0x03, 0x2B,
-    0x15, 0x0D, 0x1D, 0x13, 0x17, 0x2C, 0x15, 0x43, 0x17, 0x0D, 0x15, 0x1F, 0x17, 0x0D, 0x1D, 0x0D,
-    0x06, 0x0E, 0x17, 0x0D, 0x18, 0x29, 0x19, 0x05, 0x61, 0x6D, 0x15, 0x0D, 0x1B, 0x53, 0x7A, 0x0A,
-    0x67, 0x40, 0x1D, 0x0D, 0x17, 0x35, 0x17, 0x0C, 0x03, 0x0E, 0x0D, 0x16, 0x17, 0x33, 0x15, 0x20,
-    0x67, 0x6F, 0x7D, 0x35, 0x71, 0x0A, 0x15, 0x33, 0x7A, 0x0E, 0x15, 0x28, 0x3D, 0x09, 0x16, 0x0D,
-    0x15, 0x0D, 0x67, 0x0D, 0x71, 0x0A, 0x05, 0x0D, 0x15, 0x40, 0x3B, 0x47, 0x71, 0x0A, 0x17, 0x09,
-    0x14, 0x0D, 0x03, 0x03, 0x17, 0x0D, 0x33, 0x0D, 0x79, 0x0D, 0x15, 0x0E, 0x12, 0x0D, 0x6D, 0x3D,
-    0x17, 0x09, 0x77, 0x09, 0x3D, 0x0C, 0x33, 0x6A, 0x17, 0x1D, 0x1D, 0x0B, 0x77, 0x09, 0x2B, 0x0D,
-    0x67, 0x1F, 0x15, 0x0D, 0x1D, 0x44, 0x1F, 0x0D, 0x3D, 0x17, 0x79, 0x0C, 0x15, 0x10, 0x15, 0x09,
-    0x1A, 0x53, 0x77, 0x35, 0x78, 0x7B, 0x1D, 0x04, 0x20, 0x03, 0x43, 0x27, 0x1D, 0x47, 0x31, 0x29,
-];
-
 pub enum GameEvent {
     Connected {
         client_id: usize,
@@ -86,6 +61,8 @@ pub enum GameEvent {
 #[command(author, version, about, long_about = None)]
 struct Cli {
     addr: SocketAddr,
+    #[arg(long)]
+    config: Option<PathBuf>,
 }
 
 #[tokio::main]
@@ -94,27 +71,72 @@ async fn main() {
     env_logger::init();
     dotenvy::dotenv().unwrap();
 
+    let config = match &cli.config {
+        Some(path) => {
+            let config = ServerConfig::load(path).expect("Failed to load server configuration");
+            log::info!("Loaded configuration from {}", path.display());
+            config
+        }
+        None => {
+            log::warn!("No configuration file given, using defaults");
+            ServerConfig::default()
+        }
+    };
+
     let database_url = dotenvy::var("DATABASE_URL").expect("Database URL is mandatory");
 
     let connection = DatabaseService::new(&database_url).await.unwrap();
     let account_repository = connection.account_repository();
-    let mut context =
-        GameServerContext::new(ClientIdManager::with_maximum(750), account_repository);
-    let item_db = match std::fs::read("ItemList.csv") {
+    let guild_repository = connection.guild_repository();
+    let mut context = GameServerContext::new(account_repository, guild_repository, &config);
+    match locale::MessageCatalog::load(&config.data.locales, &config.locale) {
+        Ok(catalog) => {
+            for key in catalog.missing_keys() {
+                log::warn!("Locale {} has no text for {}", config.locale, key.key());
+            }
+            log::info!("Loaded locale {}", config.locale);
+            context.set_message_catalog(catalog);
+        }
+        Err(e) => log::warn!(
+            "Failed to load locale {}: {e}, using {}",
+            config.locale,
+            locale::DEFAULT_LOCALE
+        ),
+    }
+    let item_list = config.data.item_list.display();
+    let item_db = match std::fs::read(&config.data.item_list) {
         Ok(bytes) => {
             let contents: String = bytes.iter().map(|&b| b as char).collect();
-            let db = ItemDatabase::from_csv(&contents).expect("Failed to parse ItemList.csv");
-            log::info!("Loaded ItemList.csv");
+            let db = ItemDatabase::from_csv(&contents)
+                .unwrap_or_else(|e| panic!("Failed to parse {item_list}: {e:?}"));
+            log::info!("Loaded {item_list}");
             db
         }
         Err(e) => {
-            log::warn!("ItemList.csv not found: {e}, using empty item database");
+            log::warn!("{item_list} not found: {e}, using empty item database");
             ItemDatabase::default()
         }
     };
     let mut world = World::new(item_db);
+    let experience = config.data.experience.display();
+    match ExperienceTable::load(&config.data.experience) {
+        Ok(table) => {
+            log::info!("Loaded {experience}");
+            world.set_experience_table(table);
+        }
+        Err(e) => log::warn!("Failed to load {experience}: {e}, players will not level up"),
+    }
+
+    let skills = config.data.skills.display();
+    match skill::SkillTable::load(&config.data.skills) {
+        Ok(table) => {
+            log::info!("Loaded {} skills from {skills}", table.len());
+            world.set_skill_table(table);
+        }
+        Err(e) => log::warn!("Failed to load {skills}: {e}, skills cannot be used"),
+    }
 
-    let mob_templates = match npc::loading::load_mob_templates(Path::new("data/mobs")) {
+    let mob_templates = match npc::loading::load_mob_templates(&config.data.mobs) {
         Ok(t) => {
             log::info!("Loaded {} mob templates", t.len());
             t
@@ -124,28 +146,48 @@ async fn main() {
             std::collections::HashMap::new()
         }
     };
-    let spawn_configs =
-        match npc::loading::load_spawn_groups(Path::new("data/spawns"), &mob_templates) {
-            Ok(c) => {
-                log::info!("Loaded {} spawn groups", c.len());
-                c
-            }
-            Err(e) => {
-                log::warn!("Failed to load spawn groups: {e}, using empty");
-                Vec::new()
-            }
-        };
+    let spawn_configs = match npc::loading::load_spawn_groups(&config.data.spawns, &mob_templates) {
+        Ok(c) => {
+            log::info!("Loaded {} spawn groups", c.len());
+            c
+        }
+        Err(e) => {
+            log::warn!("Failed to load spawn groups: {e}, using empty");
+            Vec::new()
+        }
+    };
     let mut spawn_manager = npc::spawn_manager::SpawnManager::new(spawn_configs);
 
     let (event_tx, mut event_rx) = mpsc::unbounded_channel::<GameEvent>();
     let listener = TcpListener::bind(cli.addr).await.unwrap();
     log::info!("Listening on {}", cli.addr);
 
-    let keytable = Rc::new(KEYTABLE);
+    let (admin_tx, mut admin_rx) = mpsc::unbounded_channel::<admin::AdminRequest>();
+    match config.admin_addr {
+        Some(addr) if addr.ip().is_loopback() => {
+            let admin_tx = admin_tx.clone();
+            tokio::spawn(async move {
+                if let Err(e) = admin::listen(addr, admin_tx).await {
+                    log::error!("Admin channel stopped: {e}");
+                }
+            });
+        }
+        Some(addr) => log::error!("Refusing to expose the admin channel on {}", addr),
+        None => {}
+    }
+
+    let keytable = Rc::new(config.load_keytable().expect("Failed to load keytable"));
     let server_start = Instant::now();
     let mut npc_ticker = npc::tick::NpcTicker::new();
+    let mut regen_ticker = regen::RegenTicker::new(config.tick_interval());
+    let mut affect_ticker = affect::AffectTicker::new(config.tick_interval());
     let pathfinder = npc::pathfinding::GreedyPathfinder;
-    let mut tick_interval = tokio::time::interval(npc::tick::NpcTicker::tick_interval());
+    let mut tick_interval = tokio::time::interval(config.tick_interval());
+    let autosave_period = config.autosave_interval();
+    let mut autosave_interval = tokio::time::interval_at(
+        tokio::time::Instant::now() + autosave_period,
+        autosave_period,
+    );
 
     loop {
         tokio::select! {
@@ -155,6 +197,24 @@ async fn main() {
                     spawn_manager.release_mob_id(id);
                 }
                 spawn_manager.tick(&mut world, &context);
+                if let Err(e) = regen_ticker.tick(&mut world, &context) {
+                    log::warn!("Regeneration failed: {e:?}");
+                }
+                if let Err(e) = affect_ticker.tick(&mut world, &context) {
+                    log::warn!("Affect processing failed: {e:?}");
+                }
+                let decay = config.ground_items.decay();
+                if let Err(e) = decay_ground_items(&mut world, &context, Instant::now(), decay) {
+                    log::warn!("Ground item decay failed: {e:?}");
+                }
+            }
+            _ = autosave_interval.tick() => {
+                let saved = persistence::save_all_players(&world, &context.account_repository).await;
+                log::info!("Autosaved {} players", saved);
+            }
+            Some(request) = admin_rx.recv() => {
+                let reply = admin::execute(request.command, &mut context, &mut world).await;
+                let _ = request.reply.send(reply);
             }
             Ok((stream, addr)) = listener.accept() => {
                 let client_id = match context.allocate_client_id() {
@@ -177,7 +237,7 @@ async fn main() {
                     }
                 });
 
-                tokio::spawn(async move {
+                let reader = tokio::spawn(async move {
                     let mut handshake = HandshakeState::default();
                     let mut buf = [0u8; 4096];
 
@@ -199,6 +259,7 @@ async fn main() {
                     }
                 });
 
+                context.add_connection(client_id, reader.abort_handle());
                 let encdec = EncDecSession::new(client_id as u16, keytable.clone(), server_start);
                 context.add_sender(
                     client_id,
@@ -256,22 +317,18 @@ async fn main() {
                         };
 
                         log::info!("Received packet {:?} from {}", message, client_id);
-                        session.handle(&context, &mut world, message).await;
+                        session
+                            .handle(&context, &mut world, &mut spawn_manager, message)
+                            .await;
+                        if session.is_disconnecting() {
+                            log::info!("Disconnecting ClientId {}", client_id);
+                            let _ = context.disconnect_player(client_id, &mut world).await;
+                            continue;
+                        }
                         context.add_session(client_id, session);
                     }
                     GameEvent::Disconnected { client_id } => {
-                        if let Ok(result) = world.remove_entity(EntityId::Player(client_id)) {
-                            for spectator in &result.spectators {
-                                let _ = context.send_to(
-                                    *spectator,
-                                    odin_networking::messages::server::remove_mob::RemoveMob {
-                                        mob_id: client_id as u16,
-                                        remove_type: 1,
-                                    },
-                                );
-                            }
-                        }
-                        if context.disconnect(client_id).is_err() {
+                        if context.disconnect_player(client_id, &mut world).await.is_err() {
                             log::error!(
                                 "Received a disconnect event from unknown ClientId: {}",
                                 client_id
@@ -0,0 +1,192 @@
+use crate::map::EntityId;
+use odin_models::{item::Item, position::Position};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+pub const GROUND_ITEM_ID_START: usize = 10000;
+pub const MAX_GROUND_ITEMS: usize = 5000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GroundItemId(usize);
+impl GroundItemId {
+    pub fn from_id(id: usize) -> Option<Self> {
+        (GROUND_ITEM_ID_START..GROUND_ITEM_ID_START + MAX_GROUND_ITEMS)
+            .contains(&id)
+            .then_some(GroundItemId(id))
+    }
+
+    pub fn id(&self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GroundItem {
+    pub id: GroundItemId,
+    pub item: Item,
+    pub position: Position,
+    pub owner: Option<EntityId>,
+    pub dropped_at: Instant,
+}
+impl GroundItem {
+    pub fn can_pick_up(&self, entity_id: EntityId, now: Instant, owner_grace: Duration) -> bool {
+        match self.owner {
+            Some(owner) if owner != entity_id => {
+                now.saturating_duration_since(self.dropped_at) >= owner_grace
+            }
+            _ => true,
+        }
+    }
+
+    pub fn is_expired(&self, now: Instant, decay: Duration) -> bool {
+        now.saturating_duration_since(self.dropped_at) >= decay
+    }
+}
+
+#[derive(Default)]
+pub struct GroundItems {
+    items: HashMap<GroundItemId, GroundItem>,
+    cells: HashMap<Position, GroundItemId>,
+    next_index: usize,
+}
+
+impl GroundItems {
+    pub fn insert(
+        &mut self,
+        item: Item,
+        position: Position,
+        owner: Option<EntityId>,
+        now: Instant,
+    ) -> Option<GroundItemId> {
+        if self.cells.contains_key(&position) {
+            return None;
+        }
+        let id = self.allocate_id()?;
+        self.cells.insert(position, id);
+        self.items.insert(
+            id,
+            GroundItem {
+                id,
+                item,
+                position,
+                owner,
+                dropped_at: now,
+            },
+        );
+        Some(id)
+    }
+
+    pub fn remove(&mut self, id: GroundItemId) -> Option<GroundItem> {
+        let item = self.items.remove(&id)?;
+        self.cells.remove(&item.position);
+        Some(item)
+    }
+
+    pub fn get(&self, id: GroundItemId) -> Option<&GroundItem> {
+        self.items.get(&id)
+    }
+
+    pub fn is_occupied(&self, position: Position) -> bool {
+        self.cells.contains_key(&position)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &GroundItem> {
+        self.items.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    fn allocate_id(&mut self) -> Option<GroundItemId> {
+        if self.items.len() >= MAX_GROUND_ITEMS {
+            return None;
+        }
+        for offset in 0..MAX_GROUND_ITEMS {
+            let index = (self.next_index + offset) % MAX_GROUND_ITEMS;
+            let id = GroundItemId(GROUND_ITEM_ID_START + index);
+            if !self.items.contains_key(&id) {
+                self.next_index = (index + 1) % MAX_GROUND_ITEMS;
+                return Some(id);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pos(x: u16, y: u16) -> Position {
+        Position { x, y }
+    }
+
+    #[test]
+    fn insert_allocates_ids_in_the_ground_item_range() {
+        let mut ground = GroundItems::default();
+        let now = Instant::now();
+
+        let first = ground
+            .insert(Item::from(1u16), pos(10, 10), None, now)
+            .unwrap();
+        let second = ground
+            .insert(Item::from(2u16), pos(11, 10), None, now)
+            .unwrap();
+
+        assert_eq!(first.id(), GROUND_ITEM_ID_START);
+        assert_eq!(second.id(), GROUND_ITEM_ID_START + 1);
+        assert!(
+            ground
+                .insert(Item::from(3u16), pos(10, 10), None, now)
+                .is_none()
+        );
+        assert_eq!(ground.len(), 2);
+    }
+
+    #[test]
+    fn remove_frees_the_cell() {
+        let mut ground = GroundItems::default();
+        let now = Instant::now();
+        let id = ground
+            .insert(Item::from(1u16), pos(10, 10), None, now)
+            .unwrap();
+
+        assert_eq!(ground.remove(id).unwrap().item.id, 1);
+        assert!(!ground.is_occupied(pos(10, 10)));
+        assert!(ground.remove(id).is_none());
+    }
+
+    #[test]
+    fn owner_grace_blocks_other_players() {
+        let now = Instant::now();
+        let owner = EntityId::Player(1);
+        let other = EntityId::Player(2);
+        let item = GroundItem {
+            id: GroundItemId(GROUND_ITEM_ID_START),
+            item: Item::from(1u16),
+            position: pos(10, 10),
+            owner: Some(owner),
+            dropped_at: now,
+        };
+        let grace = Duration::from_secs(30);
+
+        assert!(item.can_pick_up(owner, now, grace));
+        assert!(!item.can_pick_up(other, now, grace));
+        assert!(item.can_pick_up(other, now + grace, grace));
+    }
+
+    #[test]
+    fn from_id_rejects_mob_ids() {
+        assert!(GroundItemId::from_id(1000).is_none());
+        assert!(GroundItemId::from_id(GROUND_ITEM_ID_START + MAX_GROUND_ITEMS).is_none());
+        assert_eq!(
+            GroundItemId::from_id(GROUND_ITEM_ID_START).map(|id| id.id()),
+            Some(GROUND_ITEM_ID_START)
+        );
+    }
+}
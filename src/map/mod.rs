@@ -1,8 +1,13 @@
+use crate::map::ground::{GroundItem, GroundItemId, GroundItems};
 use crate::map::spatial_grid::SpatialGrid;
+use crate::npc::mob_id_allocator::MOB_ID_START;
 use odin_models::height_map::HeightMap;
+use odin_models::item::Item;
 use odin_models::position::Position;
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
+pub mod ground;
 mod spatial_grid;
 
 const MAP_SIZE: u16 = 4096;
@@ -14,6 +19,7 @@ pub struct Map {
     spatial: SpatialGrid,
     positions: HashMap<EntityId, Position>,
     height_map: Option<HeightMap>,
+    ground: GroundItems,
 }
 
 impl Map {
@@ -22,6 +28,7 @@ impl Map {
             spatial: SpatialGrid::new(),
             positions: HashMap::new(),
             height_map: None,
+            ground: GroundItems::default(),
         }
     }
 
@@ -30,6 +37,7 @@ impl Map {
             spatial: SpatialGrid::new(),
             positions: HashMap::new(),
             height_map: Some(height_map),
+            ground: GroundItems::default(),
         }
     }
 
@@ -168,13 +176,78 @@ impl Map {
     pub fn get_spectators(&self, center: Position, exclude: EntityId) -> Vec<EntityId> {
         let (min_x, min_y, max_x, max_y) = Self::viewport_bounds(center);
         self.spatial
-            .get_spectators(min_x, min_y, max_x, max_y, exclude)
+            .get_spectators(min_x, min_y, max_x, max_y, Some(exclude))
+    }
+
+    pub fn get_viewers(&self, center: Position) -> Vec<EntityId> {
+        let (min_x, min_y, max_x, max_y) = Self::viewport_bounds(center);
+        self.spatial
+            .get_spectators(min_x, min_y, max_x, max_y, None)
     }
 
     pub fn get_position(&self, id: EntityId) -> Option<Position> {
         self.positions.get(&id).copied()
     }
 
+    pub fn drop_item(
+        &mut self,
+        item: Item,
+        center: Position,
+        owner: Option<EntityId>,
+        now: Instant,
+    ) -> Result<GroundItemId, MapError> {
+        if !Self::is_in_bounds(center) {
+            return Err(MapError::OutOfBounds);
+        }
+        let is_free = |pos| !self.ground.is_occupied(pos) && self.is_walkable(pos);
+        let position = if is_free(center) {
+            center
+        } else {
+            self.find_nearest(center, is_free)
+                .ok_or(MapError::NoFreePosition(center))?
+        };
+        self.ground
+            .insert(item, position, owner, now)
+            .ok_or(MapError::NoFreePosition(center))
+    }
+
+    pub fn ground_item(&self, id: GroundItemId) -> Option<&GroundItem> {
+        self.ground.get(id)
+    }
+
+    pub fn remove_ground_item(&mut self, id: GroundItemId) -> Option<GroundItem> {
+        self.ground.remove(id)
+    }
+
+    pub fn ground_items_in_view(&self, center: Position) -> Vec<&GroundItem> {
+        self.ground
+            .iter()
+            .filter(|item| Self::is_in_view(center, item.position))
+            .collect()
+    }
+
+    pub fn ground_items_entering_view(&self, from: Position, to: Position) -> Vec<&GroundItem> {
+        self.ground
+            .iter()
+            .filter(|item| {
+                Self::is_in_view(to, item.position) && !Self::is_in_view(from, item.position)
+            })
+            .collect()
+    }
+
+    pub fn expired_ground_items(&self, now: Instant, decay: Duration) -> Vec<GroundItemId> {
+        self.ground
+            .iter()
+            .filter(|item| item.is_expired(now, decay))
+            .map(|item| item.id)
+            .collect()
+    }
+
+    pub fn is_in_view(center: Position, pos: Position) -> bool {
+        let (min_x, min_y, max_x, max_y) = Self::viewport_bounds(center);
+        (min_x..=max_x).contains(&pos.x) && (min_y..=max_y).contains(&pos.y)
+    }
+
     fn viewport_bounds(center: Position) -> (u16, u16, u16, u16) {
         let min_x = center.x.saturating_sub(HALF_VIEWPORT_X);
         let min_y = center.y.saturating_sub(HALF_VIEWPORT_Y);
@@ -191,6 +264,16 @@ impl Map {
     }
 
     pub fn find_nearest_free(&self, center: Position) -> Option<Position> {
+        self.find_nearest(center, |pos| {
+            self.spatial.is_occupied(pos.x, pos.y).is_none() && self.is_walkable(pos)
+        })
+    }
+
+    fn find_nearest(
+        &self,
+        center: Position,
+        is_free: impl Fn(Position) -> bool,
+    ) -> Option<Position> {
         for distance in 1..=SEARCH_RANGE {
             for dy in -distance..=distance {
                 for dx in -distance..=distance {
@@ -206,7 +289,7 @@ impl Map {
                         x: nx as u16,
                         y: ny as u16,
                     };
-                    if self.spatial.is_occupied(pos.x, pos.y).is_none() && self.is_walkable(pos) {
+                    if is_free(pos) {
                         return Some(pos);
                     }
                 }
@@ -256,6 +339,14 @@ pub enum EntityId {
     Mob(usize),
 }
 impl EntityId {
+    pub fn from_id(id: usize) -> Self {
+        if id >= MOB_ID_START {
+            EntityId::Mob(id)
+        } else {
+            EntityId::Player(id)
+        }
+    }
+
     pub fn id(&self) -> usize {
         match self {
             EntityId::Player(id) | EntityId::Mob(id) => *id,
@@ -817,4 +908,33 @@ mod tests {
         let dy = (result.position.y as i32 - 100).abs();
         assert!(dx <= SEARCH_RANGE && dy <= SEARCH_RANGE);
     }
+
+    #[test]
+    fn drop_item_stacks_onto_nearest_free_cell() {
+        let mut map = Map::new();
+        let now = Instant::now();
+        let first = map
+            .drop_item(Item::from(1u16), pos(100, 100), None, now)
+            .unwrap();
+        let second = map
+            .drop_item(Item::from(2u16), pos(100, 100), None, now)
+            .unwrap();
+
+        assert_eq!(map.ground_item(first).unwrap().position, pos(100, 100));
+        let second_pos = map.ground_item(second).unwrap().position;
+        assert_ne!(second_pos, pos(100, 100));
+        assert_eq!(second_pos.chebyshev_distance(pos(100, 100)), 1);
+    }
+
+    #[test]
+    fn get_viewers_includes_every_entity_in_view() {
+        let mut map = Map::new();
+        map.insert(player(1), pos(100, 100)).unwrap();
+        map.insert(mob(1000), pos(105, 105)).unwrap();
+        map.insert(player(2), pos(500, 500)).unwrap();
+
+        let mut viewers = map.get_viewers(pos(101, 101));
+        viewers.sort_by_key(|id| id.id());
+        assert_eq!(viewers, vec![player(1), mob(1000)]);
+    }
 }
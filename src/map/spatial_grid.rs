@@ -62,7 +62,7 @@ impl SpatialGrid {
         min_y: u16,
         max_x: u16,
         max_y: u16,
-        exclude: EntityId,
+        exclude: Option<EntityId>,
     ) -> Vec<EntityId> {
         let chunk_min_x = (min_x / CHUNK_SIZE) as usize;
         let chunk_max_x = (max_x / CHUNK_SIZE) as usize;
@@ -74,7 +74,12 @@ impl SpatialGrid {
             let row = cy * CHUNKS_PER_AXIS;
             for cx in chunk_min_x..=chunk_max_x {
                 for &(id, ex, ey) in &self.chunks[row + cx] {
-                    if id != exclude && ex >= min_x && ex <= max_x && ey >= min_y && ey <= max_y {
+                    if Some(id) != exclude
+                        && ex >= min_x
+                        && ex <= max_x
+                        && ey >= min_y
+                        && ey <= max_y
+                    {
                         result.push(id);
                     }
                 }
@@ -166,7 +171,7 @@ mod tests {
         grid.insert(player(2), 110, 110);
         grid.insert(player(3), 200, 200);
 
-        let specs = grid.get_spectators(84, 84, 116, 116, player(99));
+        let specs = grid.get_spectators(84, 84, 116, 116, Some(player(99)));
         assert!(specs.contains(&player(1)));
         assert!(specs.contains(&player(2)));
         assert!(!specs.contains(&player(3)));
@@ -176,7 +181,7 @@ mod tests {
     fn spatial_get_spectators_excludes_self() {
         let mut grid = SpatialGrid::new();
         grid.insert(player(1), 100, 100);
-        let specs = grid.get_spectators(84, 84, 116, 116, player(1));
+        let specs = grid.get_spectators(84, 84, 116, 116, Some(player(1)));
         assert!(!specs.contains(&player(1)));
     }
 
@@ -189,7 +194,7 @@ mod tests {
         let ci_2 = SpatialGrid::chunk_index(34, 34);
         assert_ne!(ci_1, ci_2);
 
-        let specs = grid.get_spectators(14, 14, 50, 50, player(99));
+        let specs = grid.get_spectators(14, 14, 50, 50, None);
         assert!(specs.contains(&player(1)));
         assert!(specs.contains(&player(2)));
     }
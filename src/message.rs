@@ -1,5 +1,20 @@
 use crate::handlers::{
-    gameplay::{action::Action, apply_bonus::ApplyBonus},
+    gameplay::{
+        action::Action,
+        apply_bonus::ApplyBonus,
+        attack::Attack,
+        chat::{Chat, Whisper},
+        ground_item::{DropItem, PickupItem},
+        merchant::{BuyItem, OpenMerchant, SellItem},
+        move_item::MoveItem,
+        party::{AcceptParty, InviteParty, RemoveParty},
+        restart::Restart,
+        skill::SetShortSkill,
+        stall::{BrowseStall, BuyStallItem, OpenStall},
+        storage::StorageCoin,
+        trade::{QuitTrade, Trade},
+        use_item::UseItem,
+    },
     login::{
         authentication::{Authentication, AuthenticationError},
         create_character::CreateCharacter,
@@ -15,9 +30,26 @@ use odin_networking::{
     messages::{
         ClientMessage,
         client::{
-            action::ActionRaw, apply_bonus::ApplyBonusRaw, create_character::CreateCharacterRaw,
-            delete_character::DeleteCharacterRaw, enter_world::EnterWorldRaw,
-            login::LoginMessageRaw, numeric_token::NumericTokenRaw,
+            action::ActionRaw,
+            apply_bonus::ApplyBonusRaw,
+            attack::{AttackMultiRaw, AttackOneRaw, AttackTwoRaw},
+            chat::{ChatRaw, WhisperRaw},
+            create_character::CreateCharacterRaw,
+            delete_character::DeleteCharacterRaw,
+            enter_world::EnterWorldRaw,
+            ground_item::{DropItemRaw, PickupItemRaw},
+            login::LoginMessageRaw,
+            move_item::MoveItemRaw,
+            numeric_token::NumericTokenRaw,
+            open_merchant::OpenMerchantRaw,
+            party::{AcceptPartyRaw, PartyInviteRaw, RemovePartyRaw},
+            restart::RestartRaw,
+            set_short_skill::SetShortSkillRaw,
+            shop::{BuyItemRaw, SellItemRaw},
+            stall::{BrowseStallRaw, BuyStallItemRaw, StallRaw},
+            storage_coin::StorageCoinRaw,
+            trade::{QuitTradeRaw, TradeRaw},
+            use_item::UseItemRaw,
         },
         header::Header,
     },
@@ -44,6 +76,54 @@ pub enum Message {
     Action2(Action),
     #[raw = "ActionRaw"]
     ActionStop(Action),
+    #[raw = "ChatRaw"]
+    Chat(Chat),
+    #[raw = "WhisperRaw"]
+    Whisper(Whisper),
+    #[raw = "AttackMultiRaw"]
+    Attack(Attack),
+    #[raw = "AttackOneRaw"]
+    AttackOne(Attack),
+    #[raw = "AttackTwoRaw"]
+    AttackTwo(Attack),
+    #[raw = "RestartRaw"]
+    Restart(Restart),
+    #[raw = "MoveItemRaw"]
+    MoveItem(MoveItem),
+    #[raw = "OpenMerchantRaw"]
+    OpenMerchant(OpenMerchant),
+    #[raw = "StorageCoinRaw"]
+    DepositCoin(StorageCoin),
+    #[raw = "StorageCoinRaw"]
+    WithdrawCoin(StorageCoin),
+    #[raw = "DropItemRaw"]
+    DropItem(DropItem),
+    #[raw = "PickupItemRaw"]
+    PickupItem(PickupItem),
+    #[raw = "UseItemRaw"]
+    UseItem(UseItem),
+    #[raw = "SetShortSkillRaw"]
+    SetShortSkill(SetShortSkill),
+    #[raw = "BuyItemRaw"]
+    BuyItem(BuyItem),
+    #[raw = "SellItemRaw"]
+    SellItem(SellItem),
+    #[raw = "PartyInviteRaw"]
+    PartyInvite(InviteParty),
+    #[raw = "AcceptPartyRaw"]
+    AcceptParty(AcceptParty),
+    #[raw = "RemovePartyRaw"]
+    RemoveParty(RemoveParty),
+    #[raw = "TradeRaw"]
+    Trade(Trade),
+    #[raw = "QuitTradeRaw"]
+    QuitTrade(QuitTrade),
+    #[raw = "StallRaw"]
+    OpenStall(OpenStall),
+    #[raw = "BrowseStallRaw"]
+    BrowseStall(BrowseStall),
+    #[raw = "BuyStallItemRaw"]
+    BuyStallItem(BuyStallItem),
 }
 
 #[derive(Debug, Error)]
@@ -0,0 +1,435 @@
+use crate::map::EntityId;
+use crate::packets::BroadcastUpdateScore;
+use crate::score::damage::MIN_DAMAGE;
+use crate::session::PacketSender;
+use crate::world::{Mob, World};
+use odin_models::position::Position;
+use odin_models::status::Score;
+use odin_networking::messages::server::attack::{Attack, Damage};
+use rand::Rng;
+
+pub const LEASH_RADIUS: u16 = 20;
+pub const ATTACK_RANGE: u16 = 1;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AggroState {
+    #[default]
+    Idle,
+    Engaged {
+        target: EntityId,
+        anchor: Position,
+    },
+    Leashing {
+        home: Position,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AiDecision {
+    Idle,
+    Chase(Position),
+    Attack(EntityId),
+    Leash(Position),
+    Return(Position),
+}
+
+/// Advances the aggro state of an NPC and tells the ticker what to do with it.
+/// `AiDecision::Idle` hands control back to the movement state machine.
+pub fn decide(world: &mut World, entity_id: EntityId) -> AiDecision {
+    let Some(position) = world.map().get_position(entity_id) else {
+        return AiDecision::Idle;
+    };
+    let Some(npc) = world.get_npc(entity_id) else {
+        return AiDecision::Idle;
+    };
+
+    match npc.aggro {
+        AggroState::Leashing { home } => {
+            if position.chebyshev_distance(home) <= 1 {
+                set_aggro(world, entity_id, AggroState::Idle);
+                AiDecision::Idle
+            } else {
+                AiDecision::Return(home)
+            }
+        }
+        AggroState::Engaged { target, anchor } => match target_position(world, target) {
+            Some(target_pos) if anchor.chebyshev_distance(target_pos) <= LEASH_RADIUS => {
+                approach(position, target, target_pos)
+            }
+            _ => leash(world, entity_id, anchor),
+        },
+        AggroState::Idle => {
+            let radius = npc.template.aggro_radius;
+            if radius == 0 {
+                return AiDecision::Idle;
+            }
+            let Some((target, target_pos)) = find_target(world, entity_id, position, radius) else {
+                return AiDecision::Idle;
+            };
+            engage(world, entity_id, target);
+            approach(position, target, target_pos)
+        }
+    }
+}
+
+/// Makes the NPC and the rest of its spawn group (leader and followers) target
+/// `target`. Members already busy with another target or leashing are left alone.
+pub fn engage(world: &mut World, entity_id: EntityId, target: EntityId) {
+    let Some(npc) = world.get_npc(entity_id) else {
+        return;
+    };
+    let leader = npc.leader.unwrap_or(entity_id);
+
+    let members: Vec<EntityId> = std::iter::once(leader)
+        .chain(world.followers_of(leader).iter().copied())
+        .collect();
+
+    for member in members {
+        let Some(anchor) = world.map().get_position(member) else {
+            continue;
+        };
+        let Some(npc) = world.get_npc_mut(member) else {
+            continue;
+        };
+        if npc.aggro == AggroState::Idle {
+            npc.aggro = AggroState::Engaged { target, anchor };
+        }
+    }
+}
+
+/// Resolves a melee hit from an NPC on a player, broadcasting the attack and the
+/// target's new score. Killing the target sends the NPC back home.
+pub fn attack<P: PacketSender>(
+    world: &mut World,
+    sender: &P,
+    entity_id: EntityId,
+    target: EntityId,
+    rng: &mut impl Rng,
+) {
+    let Some(position) = world.map().get_position(entity_id) else {
+        return;
+    };
+    let Some(target_pos) = world.map().get_position(target) else {
+        return;
+    };
+    let Some(npc) = world.get_npc(entity_id) else {
+        return;
+    };
+    let npc_score = npc.computed.score;
+    let Some(Mob::Player(player)) = world.get_mob_mut(target) else {
+        return;
+    };
+
+    let damage = resolve_hit(&npc_score, &player.computed.score, rng);
+    player.computed.score.hp = player.computed.score.hp.saturating_sub(damage);
+    let killed = player.computed.score.hp == 0;
+
+    let broadcast = || Attack {
+        attacker_id: entity_id.id() as u16,
+        position,
+        target_position: target_pos,
+        skill_index: -1,
+        current_mp: npc_score.mp as i16,
+        motion: 0,
+        skill_parm: 0,
+        double_critical: false,
+        current_exp: 0,
+        damages: vec![Damage {
+            target_id: target.id() as u16,
+            damage: damage as i32,
+        }],
+    };
+    for spectator in world.map().get_spectators(position, entity_id) {
+        let _ = sender.send_to(spectator, broadcast());
+    }
+    let _ = world.broadcast_update_score(target, sender);
+
+    if killed
+        && let Some(Mob::Npc(npc)) = world.get_mob(entity_id)
+        && let AggroState::Engaged { anchor, .. } = npc.aggro
+    {
+        leash(world, entity_id, anchor);
+        let _ = world.broadcast_update_score(entity_id, sender);
+    }
+}
+
+pub fn resolve_hit(attacker: &Score, defender: &Score, rng: &mut impl Rng) -> u32 {
+    let rolled = attacker.damage * rng.gen_range(90..=110) / 100;
+    rolled.saturating_sub(defender.defense / 2).max(MIN_DAMAGE)
+}
+
+fn approach(position: Position, target: EntityId, target_pos: Position) -> AiDecision {
+    if position.chebyshev_distance(target_pos) <= ATTACK_RANGE {
+        AiDecision::Attack(target)
+    } else {
+        AiDecision::Chase(target_pos)
+    }
+}
+
+fn leash(world: &mut World, entity_id: EntityId, anchor: Position) -> AiDecision {
+    let Some(npc) = world.get_npc_mut(entity_id) else {
+        return AiDecision::Idle;
+    };
+    let home = npc.movement.home().unwrap_or(anchor);
+    npc.aggro = AggroState::Leashing { home };
+    npc.computed.score.hp = npc.computed.score.max_hp;
+    AiDecision::Leash(home)
+}
+
+fn set_aggro(world: &mut World, entity_id: EntityId, aggro: AggroState) {
+    if let Some(npc) = world.get_npc_mut(entity_id) {
+        npc.aggro = aggro;
+    }
+}
+
+fn target_position(world: &World, target: EntityId) -> Option<Position> {
+    match world.get_mob(target)? {
+        Mob::Player(player) if player.computed.score.hp > 0 && !player.invisible => {
+            world.map().get_position(target)
+        }
+        _ => None,
+    }
+}
+
+fn find_target(
+    world: &World,
+    entity_id: EntityId,
+    position: Position,
+    radius: u16,
+) -> Option<(EntityId, Position)> {
+    world
+        .map()
+        .get_spectators(position, entity_id)
+        .into_iter()
+        .filter_map(|id| target_position(world, id).map(|pos| (id, pos)))
+        .filter(|(_, pos)| position.chebyshev_distance(*pos) <= radius)
+        .min_by_key(|(id, pos)| (position.chebyshev_distance(*pos), id.id()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::tests::MockPacketSender;
+    use crate::npc::Npc;
+    use crate::npc::movement::{MovementBehavior, MovementState};
+    use crate::world::Player;
+    use odin_models::{character::Character, npc_mob::NpcMob};
+    use odin_networking::messages::ServerMessage;
+    use rand::{SeedableRng, rngs::SmallRng};
+
+    fn pos(x: u16, y: u16) -> Position {
+        Position { x, y }
+    }
+
+    fn new_npc(id: usize, aggro_radius: u16) -> Npc {
+        let entity_id = EntityId::Mob(id);
+        let template = NpcMob {
+            name: format!("Npc{}", id),
+            aggro_radius,
+            score: Score {
+                damage: 50,
+                max_hp: 100,
+                hp: 100,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let movement = MovementState::new(MovementBehavior::Stationary, 3);
+        Npc::new(entity_id, template, movement)
+    }
+
+    fn add_npc(world: &mut World, id: usize, position: Position, aggro_radius: u16) -> EntityId {
+        let entity_id = EntityId::Mob(id);
+        world
+            .add_npc(entity_id, new_npc(id, aggro_radius), position)
+            .unwrap();
+        entity_id
+    }
+
+    fn add_follower(
+        world: &mut World,
+        id: usize,
+        position: Position,
+        aggro_radius: u16,
+        leader: EntityId,
+    ) -> EntityId {
+        let entity_id = EntityId::Mob(id);
+        let mut npc = new_npc(id, aggro_radius);
+        npc.leader = Some(leader);
+        world.add_npc(entity_id, npc, position).unwrap();
+        entity_id
+    }
+
+    fn add_player(world: &mut World, client_id: usize, position: Position) -> EntityId {
+        let entity_id = EntityId::Player(client_id);
+        let mut player = Player::from_character(
+            entity_id,
+            Character {
+                name: format!("Player{}", client_id),
+                ..Default::default()
+            },
+        );
+        player.computed.score.max_hp = 1000;
+        player.computed.score.hp = 1000;
+        world.add_player(entity_id, player, position).unwrap();
+        entity_id
+    }
+
+    fn aggro(world: &World, entity_id: EntityId) -> AggroState {
+        world.get_npc(entity_id).unwrap().aggro
+    }
+
+    #[test]
+    fn passive_npc_ignores_players() {
+        let mut world = World::default();
+        let npc = add_npc(&mut world, 1000, pos(2100, 2100), 0);
+        add_player(&mut world, 1, pos(2102, 2102));
+
+        assert_eq!(decide(&mut world, npc), AiDecision::Idle);
+        assert_eq!(aggro(&world, npc), AggroState::Idle);
+    }
+
+    #[test]
+    fn aggressive_npc_chases_nearest_player_in_radius() {
+        let mut world = World::default();
+        let npc = add_npc(&mut world, 1000, pos(2100, 2100), 5);
+        let near = add_player(&mut world, 1, pos(2104, 2104));
+        add_player(&mut world, 2, pos(2105, 2100));
+        add_player(&mut world, 3, pos(2110, 2110));
+
+        assert_eq!(decide(&mut world, npc), AiDecision::Chase(pos(2104, 2104)));
+        assert_eq!(
+            aggro(&world, npc),
+            AggroState::Engaged {
+                target: near,
+                anchor: pos(2100, 2100)
+            }
+        );
+    }
+
+    #[test]
+    fn attacks_when_adjacent() {
+        let mut world = World::default();
+        let npc = add_npc(&mut world, 1000, pos(2100, 2100), 5);
+        let player = add_player(&mut world, 1, pos(2101, 2100));
+
+        assert_eq!(decide(&mut world, npc), AiDecision::Attack(player));
+
+        let sender = MockPacketSender::default();
+        let mut rng = SmallRng::seed_from_u64(3);
+        attack(&mut world, &sender, npc, player, &mut rng);
+
+        let Some(Mob::Player(target)) = world.get_mob(player) else {
+            panic!("player should exist");
+        };
+        assert!(target.computed.score.hp < 1000);
+        let identifiers: Vec<_> = sender
+            .messages_for(player)
+            .iter()
+            .map(|packet| packet.identifier)
+            .collect();
+        assert_eq!(
+            identifiers,
+            vec![ServerMessage::Attack, ServerMessage::UpdateScore]
+        );
+    }
+
+    #[test]
+    fn leashes_home_and_resets_hp_when_target_escapes() {
+        let mut world = World::default();
+        let npc = add_npc(&mut world, 1000, pos(2100, 2100), 5);
+        let player = add_player(&mut world, 1, pos(2103, 2100));
+        decide(&mut world, npc);
+
+        world.get_npc_mut(npc).unwrap().computed.score.hp = 10;
+        world.force_move_entity(npc, pos(2110, 2100)).unwrap();
+        world
+            .force_move_entity(player, pos(2100 + LEASH_RADIUS + 5, 2100))
+            .unwrap();
+
+        assert_eq!(decide(&mut world, npc), AiDecision::Leash(pos(2100, 2100)));
+        assert_eq!(world.get_npc(npc).unwrap().computed.score.hp, 100);
+        assert_eq!(decide(&mut world, npc), AiDecision::Return(pos(2100, 2100)));
+
+        world.force_move_entity(npc, pos(2100, 2100)).unwrap();
+        assert_eq!(decide(&mut world, npc), AiDecision::Idle);
+        assert_eq!(aggro(&world, npc), AggroState::Idle);
+    }
+
+    #[test]
+    fn leash_returns_to_random_origin() {
+        let mut world = World::default();
+        let npc = add_npc(&mut world, 1000, pos(2100, 2100), 5);
+        world.get_npc_mut(npc).unwrap().movement = MovementState::new(
+            MovementBehavior::Random {
+                origin: pos(2095, 2095),
+                radius: 3,
+                current_target: None,
+            },
+            3,
+        );
+        let player = add_player(&mut world, 1, pos(2103, 2100));
+        decide(&mut world, npc);
+
+        if let Some(Mob::Player(target)) = world.get_mob_mut(player) {
+            target.computed.score.hp = 0;
+        }
+        assert_eq!(decide(&mut world, npc), AiDecision::Leash(pos(2095, 2095)));
+    }
+
+    #[test]
+    fn group_members_share_the_target() {
+        let mut world = World::default();
+        let leader = add_npc(&mut world, 1000, pos(2100, 2100), 0);
+        let follower = add_follower(&mut world, 1001, pos(2101, 2101), 5, leader);
+        let sibling = add_follower(&mut world, 1002, pos(2102, 2100), 0, leader);
+        let other = add_npc(&mut world, 1003, pos(2102, 2102), 0);
+        world.get_npc_mut(leader).unwrap().is_leader = true;
+        let player = add_player(&mut world, 1, pos(2104, 2104));
+
+        decide(&mut world, follower);
+
+        assert!(matches!(
+            aggro(&world, leader),
+            AggroState::Engaged { target, .. } if target == player
+        ));
+        assert!(matches!(
+            aggro(&world, follower),
+            AggroState::Engaged { target, .. } if target == player
+        ));
+        assert!(matches!(
+            aggro(&world, sibling),
+            AggroState::Engaged { target, .. } if target == player
+        ));
+        assert_eq!(aggro(&world, other), AggroState::Idle);
+    }
+
+    #[test]
+    fn followers_stay_grouped_after_the_leader_dies() {
+        let mut world = World::default();
+        let leader = add_npc(&mut world, 1000, pos(2100, 2100), 0);
+        let follower = add_follower(&mut world, 1001, pos(2101, 2101), 5, leader);
+        let sibling = add_follower(&mut world, 1002, pos(2102, 2100), 0, leader);
+        world.remove_entity(leader).unwrap();
+        let player = add_player(&mut world, 1, pos(2104, 2104));
+
+        decide(&mut world, follower);
+
+        assert!(matches!(
+            aggro(&world, sibling),
+            AggroState::Engaged { target, .. } if target == player
+        ));
+    }
+
+    #[test]
+    fn invisible_players_are_not_targeted() {
+        let mut world = World::default();
+        let npc = add_npc(&mut world, 1000, pos(2100, 2100), 5);
+        let player = add_player(&mut world, 1, pos(2102, 2102));
+        if let Some(Mob::Player(target)) = world.get_mob_mut(player) {
+            target.invisible = true;
+        }
+
+        assert_eq!(decide(&mut world, npc), AiDecision::Idle);
+    }
+}
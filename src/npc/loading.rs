@@ -1,7 +1,7 @@
 use crate::npc::spawn_group::{Formation, RouteType, SpawnGroupConfig, SpawnGroupId, SpawnMode, WaypointConfig};
 use odin_models::character::Class;
 use odin_models::item::{Item, ItemBonusEffect};
-use odin_models::npc_mob::NpcMob;
+use odin_models::npc_mob::{LootDrop, NpcMob};
 use odin_models::position::Position;
 use odin_models::status::Score;
 use odin_models::{EquipmentSlot, EquipmentSlots, InventorySlots, MAX_ITEM_EFFECT};
@@ -32,6 +32,8 @@ pub enum LoadError {
     TemplateNotFound(String),
     #[error("Too many item effects (max {max}): got {got}")]
     TooManyEffects { max: usize, got: usize },
+    #[error("Invalid drop chance for inventory slot {slot}: {chance}")]
+    InvalidDropChance { slot: usize, chance: f32 },
 }
 
 #[derive(Deserialize)]
@@ -54,6 +56,8 @@ pub struct MobTemplateToml {
     #[serde(default)]
     pub experience: i64,
     #[serde(default)]
+    pub aggro_radius: u16,
+    #[serde(default)]
     pub score: ScoreToml,
     #[serde(default)]
     pub equipment: Vec<EquipmentEntryToml>,
@@ -99,7 +103,20 @@ impl MobTemplateToml {
         }
 
         let mut inventory = InventorySlots::default();
+        let mut loot = Vec::new();
         for entry in &self.inventory {
+            if !(0.0..=1.0).contains(&entry.drop_chance) {
+                return Err(LoadError::InvalidDropChance {
+                    slot: entry.slot,
+                    chance: entry.drop_chance,
+                });
+            }
+            if entry.drop_chance > 0.0 {
+                loot.push(LootDrop {
+                    slot: entry.slot,
+                    chance: entry.drop_chance,
+                });
+            }
             let effects = convert_item_effects(&entry.effects)?;
             inventory.set(
                 entry.slot,
@@ -121,9 +138,11 @@ impl MobTemplateToml {
             quest_info: self.quest_info,
             coin: self.coin,
             experience: self.experience,
+            aggro_radius: self.aggro_radius,
             score,
             equipments,
             inventory,
+            loot,
         })
     }
 }
@@ -180,6 +199,8 @@ pub struct InventoryEntryToml {
     pub id: u16,
     #[serde(default)]
     pub effects: Vec<ItemEffectToml>,
+    #[serde(default)]
+    pub drop_chance: f32,
 }
 
 #[derive(Deserialize)]
@@ -473,6 +494,7 @@ mod tests {
         assert_eq!(mob.clan, 0);
         assert_eq!(mob.coin, 0);
         assert_eq!(mob.experience, 0);
+        assert_eq!(mob.aggro_radius, 0);
         assert!(mob.equipments.iter().next().is_none());
     }
 
@@ -488,6 +510,7 @@ mod tests {
             quest_info = 7
             coin = 5000
             experience = 50000
+            aggro_radius = 6
 
             [score]
             level = 85
@@ -518,6 +541,11 @@ mod tests {
             slot = 0
             id = 3000
             effects = [{ type = 1, value = 50 }]
+            drop_chance = 0.25
+
+            [[inventory]]
+            slot = 1
+            id = 3001
         "#;
         let template: MobTemplateToml = toml::from_str(toml_str).unwrap();
         let mob = template.into_npc_mob().unwrap();
@@ -530,6 +558,7 @@ mod tests {
         assert_eq!(mob.quest_info, 7);
         assert_eq!(mob.coin, 5000);
         assert_eq!(mob.experience, 50000);
+        assert_eq!(mob.aggro_radius, 6);
 
         assert_eq!(mob.score.level, 85);
         assert_eq!(mob.score.defense, 200);
@@ -580,6 +609,13 @@ mod tests {
                 value: 50
             }
         );
+        assert_eq!(
+            mob.loot,
+            vec![LootDrop {
+                slot: 0,
+                chance: 0.25
+            }]
+        );
     }
 
     #[test]
@@ -1,3 +1,4 @@
+pub mod ai;
 pub mod loading;
 pub mod mob_id_allocator;
 pub mod movement;
@@ -8,13 +9,19 @@ pub mod tick;
 
 use crate::map::EntityId;
 use crate::score::ComputedScore;
+use ai::AggroState;
 use movement::MovementState;
 use odin_models::EquipmentSlots;
+use odin_models::affect::AffectList;
 use odin_models::character::{Class, GuildLevel};
 use odin_models::npc_mob::NpcMob;
 use odin_models::status::Score;
 use spawn_group::SpawnGroupId;
 
+pub const MERCHANT_ITEMS: i16 = 1;
+pub const MERCHANT_STORAGE: i16 = 2;
+pub const MERCHANT_RANGE: u16 = 6;
+
 pub struct Npc {
     pub entity_id: EntityId,
     pub template: NpcMob,
@@ -24,6 +31,8 @@ pub struct Npc {
     pub spawn_group_id: Option<SpawnGroupId>,
     pub is_leader: bool,
     pub leader: Option<EntityId>,
+    pub aggro: AggroState,
+    pub affects: AffectList,
 }
 
 impl Npc {
@@ -41,6 +50,8 @@ impl Npc {
             spawn_group_id: None,
             is_leader: false,
             leader: None,
+            aggro: AggroState::default(),
+            affects: AffectList::default(),
         }
     }
 
@@ -253,6 +253,14 @@ impl MovementState {
         }
     }
 
+    pub fn home(&self) -> Option<Position> {
+        match &self.behavior {
+            MovementBehavior::Stationary => None,
+            MovementBehavior::Random { origin, .. } => Some(*origin),
+            _ => self.current_waypoint_target(),
+        }
+    }
+
     pub fn current_waypoint_target(&self) -> Option<Position> {
         match &self.behavior {
             MovementBehavior::Stationary => None,
@@ -98,6 +98,10 @@ impl SpawnManager {
         }
     }
 
+    pub fn contains(&self, name: &str) -> bool {
+        self.name_index.contains_key(name)
+    }
+
     pub fn is_alive(&self, name: &str) -> bool {
         let Some(indices) = self.name_index.get(name) else {
             return false;
@@ -6,9 +6,10 @@ use odin_networking::messages::server::{
 };
 
 use crate::map::{EntityId, MoveResult};
+use crate::npc::ai::{self, AiDecision};
 use crate::npc::movement::{MovementBehavior, TickAction};
 use crate::npc::pathfinding::{MAX_PATH_STEPS, Pathfinder};
-use crate::packets::ToCreateMob;
+use crate::packets::{BroadcastUpdateScore, ToCreateMob};
 use crate::session::PacketSender;
 use crate::world::{Mob, World};
 use odin_models::{direction::Direction, position::Position};
@@ -66,6 +67,40 @@ impl NpcTicker {
     ) -> Option<usize> {
         let current_pos = world.map().get_position(entity_id)?;
 
+        let speed = world.get_npc(entity_id)?.movement.speed;
+        match ai::decide(world, entity_id) {
+            AiDecision::Idle => {}
+            AiDecision::Attack(target) => {
+                ai::attack(world, sender, entity_id, target, &mut rand::thread_rng());
+                return None;
+            }
+            AiDecision::Leash(home) => {
+                let _ = world.broadcast_update_score(entity_id, sender);
+                Self::walk_toward(
+                    world,
+                    pathfinder,
+                    sender,
+                    entity_id,
+                    current_pos,
+                    home,
+                    speed,
+                );
+                return None;
+            }
+            AiDecision::Chase(destination) | AiDecision::Return(destination) => {
+                Self::walk_toward(
+                    world,
+                    pathfinder,
+                    sender,
+                    entity_id,
+                    current_pos,
+                    destination,
+                    speed,
+                );
+                return None;
+            }
+        }
+
         // Pick random target if needed (must happen before computing at_target)
         {
             let Some(Mob::Npc(npc)) = world.get_mob_mut(entity_id) else {
@@ -146,87 +181,106 @@ impl NpcTicker {
 
             TickAction::Move => {
                 let target_pos = target?;
-                let max_steps = (speed as usize).min(MAX_PATH_STEPS);
-                let map = world.map();
-                let is_passable = |pos: Position| map.is_terrain_passable(pos);
-                let path = pathfinder.find_path(current_pos, target_pos, max_steps, &is_passable);
-
-                if path.is_empty() {
-                    log::trace!(
-                        "[NPC {:?} {}] Move: empty path from {} to {} (already there?)",
-                        entity_id,
-                        npc_name,
-                        current_pos,
-                        target_pos,
-                    );
-                    return None;
-                }
-
-                let intended_dest = path.iter().fold(current_pos, |pos, dir| {
-                    pos.apply_direction(*dir).unwrap_or(pos)
-                });
-
-                // Check occupancy and re-route if needed
-                let (final_path, final_dest) =
-                    if world.map().is_occupied_by_other(intended_dest, entity_id) {
-                        log::trace!(
-                            "[NPC {:?} {}] dest {} occupied, re-routing",
-                            entity_id,
-                            npc_name,
-                            intended_dest,
-                        );
-                        match world.map().find_nearest_free(intended_dest) {
-                            Some(free_pos) => {
-                                let map = world.map();
-                                let is_passable = |pos: Position| map.is_terrain_passable(pos);
-                                let new_path = pathfinder.find_path(
-                                    current_pos,
-                                    free_pos,
-                                    max_steps,
-                                    &is_passable,
-                                );
-                                if new_path.is_empty() {
-                                    return None;
-                                }
-                                let new_dest = new_path.iter().fold(current_pos, |pos, dir| {
-                                    pos.apply_direction(*dir).unwrap_or(pos)
-                                });
-                                (new_path, new_dest)
-                            }
-                            None => return None,
-                        }
-                    } else {
-                        (path, intended_dest)
-                    };
-
-                let route_bytes: Vec<u8> = final_path.iter().map(|d| d.to_route_byte()).collect();
-
-                log::trace!(
-                    "[NPC {:?} {}] Move: pos={} target={} dest={} speed={} steps={} route={:?}",
+                Self::walk_toward(
+                    world,
+                    pathfinder,
+                    sender,
                     entity_id,
-                    npc_name,
                     current_pos,
                     target_pos,
-                    final_dest,
                     speed,
-                    final_path.len(),
-                    route_bytes,
                 );
+                None
+            }
+        }
+    }
+
+    fn walk_toward<P: PacketSender>(
+        world: &mut World,
+        pathfinder: &dyn Pathfinder,
+        sender: &P,
+        entity_id: EntityId,
+        current_pos: Position,
+        target_pos: Position,
+        speed: u8,
+    ) {
+        let npc_name = world
+            .get_npc(entity_id)
+            .map_or(String::new(), |npc| npc.name().to_string());
+        let max_steps = (speed as usize).min(MAX_PATH_STEPS);
+        let map = world.map();
+        let is_passable = |pos: Position| map.is_terrain_passable(pos);
+        let path = pathfinder.find_path(current_pos, target_pos, max_steps, &is_passable);
+
+        if path.is_empty() {
+            log::trace!(
+                "[NPC {:?} {}] Move: empty path from {} to {} (already there?)",
+                entity_id,
+                npc_name,
+                current_pos,
+                target_pos,
+            );
+            return;
+        }
+
+        let intended_dest = path.iter().fold(current_pos, |pos, dir| {
+            pos.apply_direction(*dir).unwrap_or(pos)
+        });
 
-                // Strict move (no deflection)
-                if let Ok(move_result) = world.move_entity(entity_id, final_dest) {
-                    Self::broadcast_npc_walk(
-                        world,
-                        sender,
-                        entity_id,
-                        current_pos,
-                        speed,
-                        &final_path,
-                        &move_result,
-                    );
+        // Check occupancy and re-route if needed
+        let (final_path, final_dest) = if world.map().is_occupied_by_other(intended_dest, entity_id)
+        {
+            log::trace!(
+                "[NPC {:?} {}] dest {} occupied, re-routing",
+                entity_id,
+                npc_name,
+                intended_dest,
+            );
+            match world.map().find_nearest_free(intended_dest) {
+                Some(free_pos) => {
+                    let map = world.map();
+                    let is_passable = |pos: Position| map.is_terrain_passable(pos);
+                    let new_path =
+                        pathfinder.find_path(current_pos, free_pos, max_steps, &is_passable);
+                    if new_path.is_empty() {
+                        return;
+                    }
+                    let new_dest = new_path.iter().fold(current_pos, |pos, dir| {
+                        pos.apply_direction(*dir).unwrap_or(pos)
+                    });
+                    (new_path, new_dest)
                 }
-                None
+                None => return,
             }
+        } else {
+            (path, intended_dest)
+        };
+
+        let route_bytes: Vec<u8> = final_path.iter().map(|d| d.to_route_byte()).collect();
+
+        log::trace!(
+            "[NPC {:?} {}] Move: pos={} target={} dest={} speed={} steps={} route={:?}",
+            entity_id,
+            npc_name,
+            current_pos,
+            target_pos,
+            final_dest,
+            speed,
+            final_path.len(),
+            route_bytes,
+        );
+
+        // Strict move (no deflection)
+        if let Ok(move_result) = world.move_entity(entity_id, final_dest) {
+            Self::broadcast_npc_walk(
+                world,
+                sender,
+                entity_id,
+                current_pos,
+                speed,
+                &final_path,
+                &move_result,
+            );
         }
     }
 
@@ -29,6 +29,8 @@ impl ToCharacterLogin for Player {
             score_bonus: self.score_bonus,
             special_bonus: self.special_bonus,
             skill_bonus: self.skill_bonus,
+            learned_skill: self.learned_skills.to_raw(),
+            skill_bar: self.skill_bar.to_raw(),
             critical: self.computed.critical.raw(),
             save_mana: self.computed.save_mana,
             magic: self.computed.magic,
@@ -1,9 +1,20 @@
+use crate::map::EntityId;
+use crate::session::{PacketSender, SessionError};
 use crate::world::Mob;
-use odin_models::{MAX_AFFECT, position::Position};
-use odin_networking::messages::server::create_mob::CreateMob;
+use odin_models::position::Position;
+use odin_networking::messages::server::{create_mob::CreateMob, stall::CreateMobTrade};
 
 pub trait ToCreateMob {
     fn to_create_mob(&self, position: Position) -> CreateMob;
+
+    /// Sends the mob to `target`, as `CreateMobTrade` while it has a
+    /// personal shop open so its title is shown.
+    fn send_create_mob<P: PacketSender>(
+        &self,
+        target: EntityId,
+        position: Position,
+        sender: &P,
+    ) -> Result<(), SessionError>;
 }
 
 impl ToCreateMob for Mob {
@@ -18,7 +29,7 @@ impl ToCreateMob for Mob {
                 guild: player.guild,
                 guild_level: player.guild_level,
                 create_type: 0,
-                affect: [0; MAX_AFFECT],
+                affect: self.affects().to_bytes(),
             },
             Mob::Npc(npc) => CreateMob {
                 position,
@@ -29,8 +40,30 @@ impl ToCreateMob for Mob {
                 guild: npc.guild(),
                 guild_level: npc.guild_level(),
                 create_type: 0,
-                affect: [0; MAX_AFFECT],
+                affect: self.affects().to_bytes(),
             },
         }
     }
+
+    fn send_create_mob<P: PacketSender>(
+        &self,
+        target: EntityId,
+        position: Position,
+        sender: &P,
+    ) -> Result<(), SessionError> {
+        match self {
+            Mob::Player(player) if player.stall.is_some() => sender.send_to(
+                target,
+                CreateMobTrade {
+                    create_mob: self.to_create_mob(position),
+                    title: player
+                        .stall
+                        .as_ref()
+                        .map(|stall| stall.title.clone())
+                        .unwrap_or_default(),
+                },
+            ),
+            _ => sender.send_to(target, self.to_create_mob(position)),
+        }
+    }
 }
@@ -0,0 +1,24 @@
+use crate::map::ground::GroundItem;
+use odin_networking::messages::server::ground_item::{CreateGroundItem, RemoveGroundItem};
+
+pub trait ToGroundItemPackets {
+    fn to_create_ground_item(&self) -> CreateGroundItem;
+    fn to_remove_ground_item(&self) -> RemoveGroundItem;
+}
+
+impl ToGroundItemPackets for GroundItem {
+    fn to_create_ground_item(&self) -> CreateGroundItem {
+        CreateGroundItem {
+            item_id: self.id.id() as u16,
+            position: self.position,
+            item: self.item,
+            rotation: 0,
+        }
+    }
+
+    fn to_remove_ground_item(&self) -> RemoveGroundItem {
+        RemoveGroundItem {
+            item_id: self.id.id() as u16,
+        }
+    }
+}
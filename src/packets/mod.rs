@@ -1,10 +1,12 @@
 mod character_login;
 mod create_mob;
+mod ground_item;
 mod update_etc;
 mod update_score;
 
 pub use character_login::ToCharacterLogin;
 pub use create_mob::ToCreateMob;
+pub use ground_item::ToGroundItemPackets;
 pub use update_etc::ToUpdateEtc;
 pub use update_score::BroadcastUpdateScore;
 pub use update_score::ToUpdateScore;
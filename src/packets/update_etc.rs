@@ -9,7 +9,7 @@ impl ToUpdateEtc for Player {
     fn to_update_etc(&self) -> UpdateEtc {
         UpdateEtc {
             experience: self.experience,
-            learned_skill: [0; 2],
+            learned_skill: self.learned_skills.to_raw(),
             score_bonus: self.score_bonus,
             special_bonus: self.special_bonus,
             skill_bonus: self.skill_bonus,
@@ -1,7 +1,7 @@
 use crate::map::EntityId;
+use crate::npc::Npc;
 use crate::session::{PacketSender, SessionError};
 use crate::world::{Mob, Player, World};
-use odin_models::MAX_AFFECT;
 use odin_networking::messages::server::update_score::UpdateScore;
 
 pub trait ToUpdateScore {
@@ -17,7 +17,7 @@ impl ToUpdateScore for Player {
             score: self.computed.score,
             critical: self.computed.critical.raw(),
             save_mana: self.computed.save_mana as i8,
-            affect: [0u8; MAX_AFFECT], // TODO: affects system
+            affect: self.affects.to_bytes(),
             guild: self.guild.unwrap_or(0) as u16,
             guild_level,
             resist: [
@@ -35,6 +35,35 @@ impl ToUpdateScore for Player {
     }
 }
 
+impl ToUpdateScore for Npc {
+    fn to_update_score(&self) -> UpdateScore {
+        UpdateScore {
+            mob_id: self.entity_id.id() as u16,
+            score: self.computed.score,
+            critical: self.computed.critical.raw(),
+            save_mana: self.computed.save_mana as i8,
+            affect: self.affects.to_bytes(),
+            guild: self.guild().unwrap_or(0) as u16,
+            guild_level: self.guild_level().map(|g| g.as_raw()).unwrap_or(0),
+            resist: self.computed.resist.map(|resist| resist as i8),
+            req_hp: self.computed.score.hp as i32,
+            req_mp: self.computed.score.mp as i32,
+            magic: self.computed.magic,
+            rsv: 0,
+            learned_skill: 0,
+        }
+    }
+}
+
+impl ToUpdateScore for Mob {
+    fn to_update_score(&self) -> UpdateScore {
+        match self {
+            Mob::Player(player) => player.to_update_score(),
+            Mob::Npc(npc) => npc.to_update_score(),
+        }
+    }
+}
+
 pub trait BroadcastUpdateScore {
     fn broadcast_update_score<P: PacketSender>(
         &self,
@@ -49,7 +78,7 @@ impl BroadcastUpdateScore for World {
         entity_id: EntityId,
         sender: &P,
     ) -> Result<(), SessionError> {
-        let Some(Mob::Player(player)) = self.get_mob(entity_id) else {
+        let Some(mob) = self.get_mob(entity_id) else {
             return Ok(());
         };
 
@@ -58,10 +87,13 @@ impl BroadcastUpdateScore for World {
         };
 
         let spectators = self.map().get_spectators(position, entity_id);
-        sender.send_to(entity_id, player.to_update_score())?;
+        sender.send_to(entity_id, mob.to_update_score())?;
 
         for spectator in &spectators {
-            sender.send_to(*spectator, player.to_update_score())?;
+            sender.send_to(*spectator, mob.to_update_score())?;
+        }
+        for member in self.party_members_out_of_view(entity_id) {
+            sender.send_to(member, mob.to_update_score())?;
         }
 
         Ok(())
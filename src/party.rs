@@ -0,0 +1,289 @@
+use crate::map::EntityId;
+use crate::session::SessionError;
+use std::collections::HashMap;
+
+/// Leader included.
+pub const MAX_PARTY_MEMBERS: usize = 12;
+/// Members farther than this from a kill don't share its experience.
+pub const PARTY_EXPERIENCE_RANGE: u16 = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Party {
+    leader: EntityId,
+    members: Vec<EntityId>,
+}
+
+impl Party {
+    pub fn leader(&self) -> EntityId {
+        self.leader
+    }
+
+    /// Every member, leader first.
+    pub fn members(&self) -> &[EntityId] {
+        &self.members
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.members.len() >= MAX_PARTY_MEMBERS
+    }
+}
+
+/// What a departure did to the party, so callers can notify the members.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartyDeparture {
+    /// The member left and `remaining` stay grouped.
+    Left { remaining: Vec<EntityId> },
+    /// The leader left or a single member was left behind, so every
+    /// remaining member lost its party.
+    Disbanded { remaining: Vec<EntityId> },
+}
+
+/// Parties are keyed by their leader, which never changes: a leaving leader
+/// disbands the party.
+#[derive(Debug, Default)]
+pub struct PartyRegistry {
+    parties: HashMap<EntityId, Party>,
+    membership: HashMap<EntityId, EntityId>,
+    invites: HashMap<EntityId, EntityId>,
+}
+
+impl PartyRegistry {
+    pub fn party_of(&self, entity_id: EntityId) -> Option<&Party> {
+        self.parties.get(self.membership.get(&entity_id)?)
+    }
+
+    /// Every member of `entity_id`'s party, or just `entity_id` when it's
+    /// not in one.
+    pub fn members_of(&self, entity_id: EntityId) -> Vec<EntityId> {
+        self.party_of(entity_id)
+            .map_or_else(|| vec![entity_id], |party| party.members.clone())
+    }
+
+    pub fn pending_invite(&self, target: EntityId) -> Option<EntityId> {
+        self.invites.get(&target).copied()
+    }
+
+    /// Records an invite from `leader` to `target`, replacing any previous
+    /// invite `target` had.
+    pub fn invite(&mut self, leader: EntityId, target: EntityId) -> Result<(), PartyError> {
+        if leader == target {
+            return Err(PartyError::InvalidTarget);
+        }
+        if let Some(party) = self.party_of(leader) {
+            if party.leader != leader {
+                return Err(PartyError::NotLeader);
+            }
+            if party.is_full() {
+                return Err(PartyError::PartyFull);
+            }
+        }
+        if self.membership.contains_key(&target) {
+            return Err(PartyError::AlreadyInParty);
+        }
+
+        self.invites.insert(target, leader);
+        Ok(())
+    }
+
+    /// Joins `member` to the party of `leader`, creating it on the first
+    /// accepted invite.
+    pub fn accept(&mut self, member: EntityId, leader: EntityId) -> Result<&Party, PartyError> {
+        if self.invites.get(&member) != Some(&leader) {
+            return Err(PartyError::NoInvite);
+        }
+        if self.membership.contains_key(&member) {
+            return Err(PartyError::AlreadyInParty);
+        }
+        if self
+            .membership
+            .get(&leader)
+            .is_some_and(|party| *party != leader)
+        {
+            return Err(PartyError::NotLeader);
+        }
+        if self.parties.get(&leader).is_some_and(Party::is_full) {
+            return Err(PartyError::PartyFull);
+        }
+
+        self.invites.remove(&member);
+        let party = self.parties.entry(leader).or_insert_with(|| Party {
+            leader,
+            members: vec![leader],
+        });
+        party.members.push(member);
+        self.membership.insert(leader, leader);
+        self.membership.insert(member, leader);
+        Ok(party)
+    }
+
+    /// Removes `member` from its party, also dropping any invite it sent or
+    /// received.
+    pub fn leave(&mut self, member: EntityId) -> Option<PartyDeparture> {
+        self.invites.remove(&member);
+        self.invites.retain(|_, leader| *leader != member);
+        let leader = self.membership.remove(&member)?;
+        let party = self.parties.get_mut(&leader)?;
+        party.members.retain(|id| *id != member);
+
+        if member != leader && party.members.len() > 1 {
+            return Some(PartyDeparture::Left {
+                remaining: party.members.clone(),
+            });
+        }
+        let party = self.parties.remove(&leader)?;
+        for id in &party.members {
+            self.membership.remove(id);
+        }
+        Some(PartyDeparture::Disbanded {
+            remaining: party.members,
+        })
+    }
+
+    /// Like [`leave`](Self::leave), but only the leader may remove others.
+    pub fn kick(
+        &mut self,
+        leader: EntityId,
+        member: EntityId,
+    ) -> Result<PartyDeparture, PartyError> {
+        let party = self.party_of(member).ok_or(PartyError::NotInParty)?;
+        if party.leader != leader {
+            return Err(PartyError::NotLeader);
+        }
+        self.leave(member).ok_or(PartyError::NotInParty)
+    }
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum PartyError {
+    #[error("Player not found in world")]
+    PlayerNotFound,
+
+    #[error("Invalid party target")]
+    InvalidTarget,
+
+    #[error("Only the party leader can do that")]
+    NotLeader,
+
+    #[error("Party is full")]
+    PartyFull,
+
+    #[error("Target is already in a party")]
+    AlreadyInParty,
+
+    #[error("No pending invite from that leader")]
+    NoInvite,
+
+    #[error("Player is not in a party")]
+    NotInParty,
+
+    #[error(transparent)]
+    Session(#[from] SessionError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(id: usize) -> EntityId {
+        EntityId::Player(id)
+    }
+
+    fn party_with(registry: &mut PartyRegistry, leader: usize, members: &[usize]) {
+        for &member in members {
+            registry.invite(player(leader), player(member)).unwrap();
+            registry.accept(player(member), player(leader)).unwrap();
+        }
+    }
+
+    #[test]
+    fn accepting_an_invite_forms_a_party() {
+        let mut registry = PartyRegistry::default();
+
+        registry.invite(player(1), player(2)).unwrap();
+        assert_eq!(registry.pending_invite(player(2)), Some(player(1)));
+        assert_eq!(
+            registry.accept(player(2), player(3)),
+            Err(PartyError::NoInvite)
+        );
+        registry.accept(player(2), player(1)).unwrap();
+
+        let party = registry.party_of(player(2)).unwrap();
+        assert_eq!(party.leader(), player(1));
+        assert_eq!(party.members(), &[player(1), player(2)]);
+        assert_eq!(registry.members_of(player(1)), vec![player(1), player(2)]);
+        assert_eq!(registry.members_of(player(9)), vec![player(9)]);
+        assert_eq!(registry.pending_invite(player(2)), None);
+    }
+
+    #[test]
+    fn only_the_leader_invites_and_kicks() {
+        let mut registry = PartyRegistry::default();
+        party_with(&mut registry, 1, &[2, 3]);
+
+        assert_eq!(
+            registry.invite(player(2), player(4)),
+            Err(PartyError::NotLeader)
+        );
+        assert_eq!(
+            registry.invite(player(1), player(3)),
+            Err(PartyError::AlreadyInParty)
+        );
+        assert_eq!(
+            registry.kick(player(2), player(3)),
+            Err(PartyError::NotLeader)
+        );
+        assert_eq!(
+            registry.kick(player(1), player(3)),
+            Ok(PartyDeparture::Left {
+                remaining: vec![player(1), player(2)]
+            })
+        );
+        assert!(registry.party_of(player(3)).is_none());
+    }
+
+    #[test]
+    fn party_is_capped() {
+        let mut registry = PartyRegistry::default();
+        let members: Vec<usize> = (2..=MAX_PARTY_MEMBERS).collect();
+        party_with(&mut registry, 1, &members);
+
+        assert_eq!(
+            registry.invite(player(1), player(100)),
+            Err(PartyError::PartyFull)
+        );
+    }
+
+    #[test]
+    fn leader_leaving_disbands_the_party() {
+        let mut registry = PartyRegistry::default();
+        party_with(&mut registry, 1, &[2, 3]);
+
+        assert_eq!(
+            registry.leave(player(1)),
+            Some(PartyDeparture::Disbanded {
+                remaining: vec![player(2), player(3)]
+            })
+        );
+        assert!(registry.party_of(player(2)).is_none());
+        assert!(registry.party_of(player(3)).is_none());
+    }
+
+    #[test]
+    fn last_member_leaving_disbands_the_party() {
+        let mut registry = PartyRegistry::default();
+        party_with(&mut registry, 1, &[2]);
+        registry.invite(player(1), player(3)).unwrap();
+
+        assert_eq!(
+            registry.leave(player(2)),
+            Some(PartyDeparture::Disbanded {
+                remaining: vec![player(1)]
+            })
+        );
+        assert!(registry.party_of(player(1)).is_none());
+        assert_eq!(registry.pending_invite(player(3)), Some(player(1)));
+
+        assert_eq!(registry.leave(player(1)), None);
+        assert_eq!(registry.pending_invite(player(3)), None);
+    }
+}
@@ -0,0 +1,378 @@
+use crate::map::EntityId;
+use crate::world::{Mob, World};
+use odin_models::character::Character;
+use odin_repositories::account_repository::{AccountRepository, AccountRepositoryError};
+
+pub fn character_snapshot(world: &World, entity_id: EntityId) -> Option<Character> {
+    let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+        return None;
+    };
+
+    let mut character = player.to_character();
+    if let Some(position) = world.map().get_position(entity_id) {
+        character.last_pos = position;
+    }
+    Some(character)
+}
+
+pub async fn save_player<A: AccountRepository>(
+    world: &World,
+    entity_id: EntityId,
+    account_repository: &A,
+) -> Result<(), PersistenceError> {
+    let character = character_snapshot(world, entity_id).ok_or(PersistenceError::PlayerNotFound)?;
+    let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+        return Err(PersistenceError::PlayerNotFound);
+    };
+    account_repository
+        .save_player(&character, player.account_id, &player.storage)
+        .await?;
+    Ok(())
+}
+
+pub async fn save_all_players<A: AccountRepository>(
+    world: &World,
+    account_repository: &A,
+) -> usize {
+    let mut saved = 0;
+    for entity_id in world.player_ids() {
+        match save_player(world, entity_id, account_repository).await {
+            Ok(()) => saved += 1,
+            Err(e) => log::error!("Failed to save {:?}: {e}", entity_id),
+        }
+    }
+    saved
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum PersistenceError {
+    #[error("Player not found in world")]
+    PlayerNotFound,
+
+    #[error(transparent)]
+    Repository(#[from] AccountRepositoryError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::login::enter_world::EnterWorld;
+    use crate::handlers::tests::{MockPacketSender, TestAccountRepository};
+    use odin_models::{
+        EquipmentSlot, account_charlist::AccountCharlist, item::Item, position::Position,
+        uuid::Uuid,
+    };
+
+    async fn setup_account(repository: &TestAccountRepository, name: &str) -> Uuid {
+        let account_id = Uuid::new_v4();
+        repository
+            .add_account_with_characters(
+                AccountCharlist {
+                    identifier: account_id,
+                    username: name.to_string(),
+                    password: "pass".to_string(),
+                    ..Default::default()
+                },
+                vec![Character {
+                    identifier: Uuid::new_v4(),
+                    name: name.to_string(),
+                    last_pos: Position { x: 2100, y: 2100 },
+                    score: odin_models::status::Score {
+                        level: 10,
+                        hp: 50,
+                        ..Default::default()
+                    },
+                    inventory: vec![(0, Item::from(400u16))].into(),
+                    ..Default::default()
+                }],
+            )
+            .await
+    }
+
+    async fn enter_world(
+        repository: &TestAccountRepository,
+        account_id: Uuid,
+        client_id: usize,
+        world: &mut World,
+    ) {
+        EnterWorld {
+            slot: 0,
+            force: false,
+            secret_code: String::new(),
+        }
+        .handle(
+            account_id,
+            client_id,
+            repository.account_repository(),
+            &MockPacketSender::default(),
+            world,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn reconnect_sees_state_saved_on_logout() {
+        let repository = TestAccountRepository::new().await;
+        let account_id = setup_account(&repository, "Saver").await;
+        let mut world = World::default();
+        let entity_id = EntityId::Player(1);
+
+        enter_world(&repository, account_id, entity_id.id(), &mut world).await;
+        world
+            .move_entity(entity_id, Position { x: 2110, y: 2105 })
+            .unwrap();
+        {
+            let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) else {
+                panic!("player must be in world");
+            };
+            player.coin = 1234;
+            player.experience = 98765;
+            player.score.strength += 3;
+            player.score.specials[1] = 2;
+            player.inventory = vec![(5, Item::from((401u16, 43u8, 7u8)))].into();
+            player.equipments = vec![(EquipmentSlot::Helmet, Item::from(1101u16))].into();
+        }
+
+        save_player(&world, entity_id, &repository.account_repository())
+            .await
+            .unwrap();
+        world.remove_entity(entity_id).unwrap();
+
+        let entity_id = EntityId::Player(2);
+        enter_world(&repository, account_id, entity_id.id(), &mut world).await;
+
+        let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+            panic!("player must be in world");
+        };
+        assert_eq!(player.coin, 1234);
+        assert_eq!(player.experience, 98765);
+        assert_eq!(player.last_pos, Position { x: 2110, y: 2105 });
+        assert_eq!(player.score.specials[1], 2);
+        assert!(player.inventory.get(0).is_none());
+        assert_eq!(
+            player.inventory.get(5),
+            Some(&Item::from((401u16, 43u8, 7u8)))
+        );
+        assert_eq!(
+            player.equipments.get(EquipmentSlot::Helmet),
+            Some(&Item::from(1101u16))
+        );
+        assert_eq!(
+            world.map().get_position(entity_id),
+            Some(Position { x: 2110, y: 2105 })
+        );
+    }
+
+    #[tokio::test]
+    async fn saves_spent_bonus_points() {
+        let repository = TestAccountRepository::new().await;
+        let account_id = setup_account(&repository, "Bonus").await;
+        let mut world = World::default();
+        let entity_id = EntityId::Player(1);
+
+        enter_world(&repository, account_id, entity_id.id(), &mut world).await;
+        let (strength, score_bonus) = {
+            let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) else {
+                panic!("player must be in world");
+            };
+            player.score.strength += 5;
+            player.calculate_bonus_points();
+            (player.score.strength, player.score_bonus)
+        };
+
+        save_player(&world, entity_id, &repository.account_repository())
+            .await
+            .unwrap();
+        world.remove_entity(entity_id).unwrap();
+        enter_world(&repository, account_id, entity_id.id(), &mut world).await;
+
+        let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+            panic!("player must be in world");
+        };
+        assert_eq!(player.score.strength, strength);
+        assert_eq!(player.score_bonus, score_bonus);
+    }
+
+    #[tokio::test]
+    async fn storage_is_shared_between_characters_of_an_account() {
+        let repository = TestAccountRepository::new().await;
+        let account_id = setup_account(&repository, "Keeper").await;
+        repository
+            .add_character(
+                account_id,
+                Character {
+                    identifier: Uuid::new_v4(),
+                    name: "Alt".to_string(),
+                    slot: 1,
+                    last_pos: Position { x: 2100, y: 2100 },
+                    ..Default::default()
+                },
+            )
+            .await;
+        let mut world = World::default();
+        let entity_id = EntityId::Player(1);
+
+        enter_world(&repository, account_id, entity_id.id(), &mut world).await;
+        {
+            let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) else {
+                panic!("player must be in world");
+            };
+            player
+                .storage
+                .items
+                .set(12, Item::from((402u16, 43u8, 3u8)));
+            player.storage.coin = 4321;
+        }
+        save_player(&world, entity_id, &repository.account_repository())
+            .await
+            .unwrap();
+        world.remove_entity(entity_id).unwrap();
+
+        EnterWorld {
+            slot: 1,
+            force: false,
+            secret_code: String::new(),
+        }
+        .handle(
+            account_id,
+            entity_id.id(),
+            repository.account_repository(),
+            &MockPacketSender::default(),
+            &mut world,
+        )
+        .await
+        .unwrap();
+
+        let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+            panic!("player must be in world");
+        };
+        assert_eq!(player.name, "Alt");
+        assert_eq!(player.storage.coin, 4321);
+        assert_eq!(
+            player.storage.items.get(12),
+            Some(&Item::from((402u16, 43u8, 3u8)))
+        );
+        let account = repository
+            .account_repository()
+            .fetch_account("Keeper")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(account.storage.coin, 4321);
+    }
+
+    #[tokio::test]
+    async fn affects_survive_logout() {
+        use odin_models::affect::{Affect, AffectKind, AffectSource};
+
+        let repository = TestAccountRepository::new().await;
+        let account_id = setup_account(&repository, "Buffed").await;
+        let mut world = World::default();
+        let entity_id = EntityId::Player(1);
+        let haste = Affect {
+            id: AffectKind::Haste as u8,
+            value: 20,
+            remaining_ticks: 90,
+            source: AffectSource::Item(3314),
+        };
+
+        enter_world(&repository, account_id, entity_id.id(), &mut world).await;
+        world
+            .add_affect(entity_id, haste, &MockPacketSender::default())
+            .unwrap();
+        save_player(&world, entity_id, &repository.account_repository())
+            .await
+            .unwrap();
+        world.remove_entity(entity_id).unwrap();
+        enter_world(&repository, account_id, entity_id.id(), &mut world).await;
+
+        let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+            panic!("player must be in world");
+        };
+        assert_eq!(player.affects.get(haste.id), Some(&haste));
+        assert_eq!(player.computed.attack_speed, 20);
+    }
+
+    #[tokio::test]
+    async fn skills_survive_logout() {
+        use odin_models::skill::{EMPTY_SKILL_SLOT, MAX_SKILL_BAR, SkillBar};
+
+        let repository = TestAccountRepository::new().await;
+        let account_id = setup_account(&repository, "Caster").await;
+        let mut world = World::default();
+        let entity_id = EntityId::Player(1);
+        let mut raw_bar = [EMPTY_SKILL_SLOT; MAX_SKILL_BAR];
+        raw_bar[3] = 33;
+        let skill_bar = SkillBar::from_raw(&raw_bar);
+
+        enter_world(&repository, account_id, entity_id.id(), &mut world).await;
+        let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) else {
+            panic!("player must be in world");
+        };
+        player.learned_skills.learn(1);
+        player.learned_skills.learn(33);
+        player.skill_bar = skill_bar;
+        player.skill_bonus = 7;
+        save_player(&world, entity_id, &repository.account_repository())
+            .await
+            .unwrap();
+        world.remove_entity(entity_id).unwrap();
+        enter_world(&repository, account_id, entity_id.id(), &mut world).await;
+
+        let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+            panic!("player must be in world");
+        };
+        assert!(player.learned_skills.knows(1));
+        assert!(player.learned_skills.knows(33));
+        assert!(!player.learned_skills.knows(2));
+        assert_eq!(player.skill_bar, skill_bar);
+        assert_eq!(player.skill_bonus, 7);
+    }
+
+    #[tokio::test]
+    async fn save_all_players_saves_every_player_in_world() {
+        let repository = TestAccountRepository::new().await;
+        let first_account = setup_account(&repository, "First").await;
+        let second_account = setup_account(&repository, "Second").await;
+        let mut world = World::default();
+
+        enter_world(&repository, first_account, 1, &mut world).await;
+        enter_world(&repository, second_account, 2, &mut world).await;
+        for client_id in [1, 2] {
+            let Some(Mob::Player(player)) = world.get_mob_mut(EntityId::Player(client_id)) else {
+                panic!("player must be in world");
+            };
+            player.coin = client_id as i32 * 100;
+        }
+
+        let saved = save_all_players(&world, &repository.account_repository()).await;
+        assert_eq!(saved, 2);
+
+        for (account_id, coin) in [(first_account, 100), (second_account, 200)] {
+            let character = repository
+                .account_repository()
+                .fetch_character(account_id, 0)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(character.coin, coin);
+        }
+    }
+
+    #[tokio::test]
+    async fn save_player_not_in_world_returns_error() {
+        let repository = TestAccountRepository::new().await;
+        let world = World::default();
+
+        assert_eq!(
+            save_player(
+                &world,
+                EntityId::Player(1),
+                &repository.account_repository()
+            )
+            .await,
+            Err(PersistenceError::PlayerNotFound)
+        );
+    }
+}
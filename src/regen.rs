@@ -0,0 +1,184 @@
+use crate::map::EntityId;
+use crate::packets::BroadcastUpdateScore;
+use crate::score::ComputedScore;
+use crate::session::{PacketSender, SessionError};
+use crate::world::{Mob, World};
+use std::time::Duration;
+
+pub const REGEN_INTERVAL: Duration = Duration::from_secs(8);
+/// Share of max HP/MP restored on every pass, before `regen_hp`/`regen_mp`.
+pub const BASE_REGEN_PERCENT: u32 = 2;
+
+/// Restores HP and MP of every living mob on a fixed interval driven by the
+/// main tick, independently of the NPC stride.
+pub struct RegenTicker {
+    ticks_per_regen: u64,
+    tick_counter: u64,
+}
+
+impl RegenTicker {
+    pub fn new(tick_interval: Duration) -> Self {
+        let ticks = REGEN_INTERVAL.as_millis() / tick_interval.as_millis().max(1);
+        Self {
+            ticks_per_regen: (ticks as u64).max(1),
+            tick_counter: 0,
+        }
+    }
+
+    pub fn tick<P: PacketSender>(
+        &mut self,
+        world: &mut World,
+        sender: &P,
+    ) -> Result<(), SessionError> {
+        self.tick_counter += 1;
+        if !self.tick_counter.is_multiple_of(self.ticks_per_regen) {
+            return Ok(());
+        }
+        regenerate(world, sender)
+    }
+}
+
+pub fn regenerate<P: PacketSender>(world: &mut World, sender: &P) -> Result<(), SessionError> {
+    let ids: Vec<EntityId> = world
+        .player_ids()
+        .into_iter()
+        .chain(world.npc_ids())
+        .collect();
+    for entity_id in ids {
+        let computed = match world.get_mob_mut(entity_id) {
+            Some(Mob::Player(player)) => &mut player.computed,
+            Some(Mob::Npc(npc)) => &mut npc.computed,
+            None => continue,
+        };
+        if regenerate_score(computed) {
+            world.broadcast_update_score(entity_id, sender)?;
+        }
+    }
+    Ok(())
+}
+
+fn regenerate_score(computed: &mut ComputedScore) -> bool {
+    let score = &mut computed.score;
+    if score.hp == 0 {
+        return false;
+    }
+    let hp = restore(score.hp, score.max_hp, computed.regen_hp);
+    let mp = restore(score.mp, score.max_mp, computed.regen_mp);
+    let changed = hp != score.hp || mp != score.mp;
+    score.hp = hp;
+    score.mp = mp;
+    changed
+}
+
+fn restore(current: u32, max: u32, regen: i32) -> u32 {
+    if current >= max {
+        return current;
+    }
+    let amount = (max * BASE_REGEN_PERCENT / 100) as i64 + regen as i64;
+    (current as i64 + amount.max(1)).min(max as i64) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::tests::MockPacketSender;
+    use crate::npc::{
+        Npc,
+        movement::{MovementBehavior, MovementState},
+    };
+    use crate::world::Player;
+    use odin_models::{character::Character, npc_mob::NpcMob, position::Position, status::Score};
+    use odin_networking::messages::ServerMessage;
+
+    fn score(hp: u32, mp: u32) -> Score {
+        Score {
+            max_hp: 1000,
+            max_mp: 500,
+            hp,
+            mp,
+            ..Default::default()
+        }
+    }
+
+    fn add_player(world: &mut World, client_id: usize, hp: u32, mp: u32) -> EntityId {
+        let entity_id = EntityId::Player(client_id);
+        let mut player = Player::from_character(entity_id, Character::default());
+        player.computed.score = score(hp, mp);
+        player.computed.regen_hp = 5;
+        let position = Position {
+            x: 2100 + client_id as u16 * 2,
+            y: 2100,
+        };
+        world.add_player(entity_id, player, position).unwrap();
+        entity_id
+    }
+
+    fn current(world: &World, entity_id: EntityId) -> (u32, u32) {
+        let score = match world.get_mob(entity_id).unwrap() {
+            Mob::Player(player) => player.current_score(),
+            Mob::Npc(npc) => npc.current_score(),
+        };
+        (score.hp, score.mp)
+    }
+
+    #[test]
+    fn regenerate_restores_hp_and_mp_toward_max() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let wounded = add_player(&mut world, 1, 100, 495);
+
+        let npc_id = EntityId::Mob(1000);
+        let template = NpcMob {
+            score: score(10, 0),
+            ..Default::default()
+        };
+        let movement = MovementState::new(MovementBehavior::Stationary, 1);
+        world
+            .add_npc(
+                npc_id,
+                Npc::new(npc_id, template, movement),
+                Position { x: 2110, y: 2100 },
+            )
+            .unwrap();
+
+        regenerate(&mut world, &sender).unwrap();
+
+        assert_eq!(current(&world, wounded), (125, 500));
+        assert_eq!(current(&world, npc_id), (30, 10));
+    }
+
+    #[test]
+    fn regenerate_skips_dead_and_unchanged_mobs() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let dead = add_player(&mut world, 1, 0, 0);
+        let healthy = add_player(&mut world, 2, 1000, 500);
+
+        regenerate(&mut world, &sender).unwrap();
+
+        assert_eq!(current(&world, dead), (0, 0));
+        assert!(
+            !sender
+                .messages_for(healthy)
+                .iter()
+                .any(|m| m.identifier == ServerMessage::UpdateScore),
+            "nothing changed, so no UpdateScore should be sent"
+        );
+    }
+
+    #[test]
+    fn ticker_runs_once_per_regen_interval() {
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let wounded = add_player(&mut world, 1, 100, 500);
+        let mut ticker = RegenTicker::new(REGEN_INTERVAL / 4);
+
+        for _ in 0..3 {
+            ticker.tick(&mut world, &sender).unwrap();
+        }
+        assert_eq!(current(&world, wounded), (100, 500));
+
+        ticker.tick(&mut world, &sender).unwrap();
+        assert_eq!(current(&world, wounded), (125, 500));
+    }
+}
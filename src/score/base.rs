@@ -68,6 +68,14 @@ pub fn master_points(level: u16, evolution: Evolution) -> i32 {
     }
 }
 
+pub fn skill_points(level: u16, evolution: Evolution) -> i32 {
+    let level = level as i32;
+    match evolution {
+        Evolution::Mortal => level * 3,
+        _ => 0, // TODO: Arch, Celestial, SubCelestial
+    }
+}
+
 pub(super) fn calculate_base_score(score: &Score, class: Class, evolution: Evolution) -> Score {
     let class_index = i32::from(class) as usize;
     let stats = &CLASS_STATS[class_index];
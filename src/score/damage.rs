@@ -0,0 +1,238 @@
+use super::ComputedScore;
+use rand::Rng;
+
+pub const MIN_DAMAGE: u32 = 1;
+pub const CRITICAL_MULTIPLIER: u32 = 2;
+pub const MAX_EVADE_CHANCE: i32 = 50;
+const VARIANCE: std::ops::RangeInclusive<u32> = 90..=110;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttackKind {
+    Melee,
+    Skill { power: u32, element: Option<usize> },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DamageOutcome {
+    Miss,
+    Hit { damage: u32, critical: bool },
+}
+impl DamageOutcome {
+    pub fn damage(&self) -> u32 {
+        match self {
+            DamageOutcome::Miss => 0,
+            DamageOutcome::Hit { damage, .. } => *damage,
+        }
+    }
+
+    pub fn is_critical(&self) -> bool {
+        matches!(self, DamageOutcome::Hit { critical: true, .. })
+    }
+}
+
+pub struct DamageCalculator<'a> {
+    pub attacker: &'a ComputedScore,
+    pub defender: &'a ComputedScore,
+    pub pvp: bool,
+}
+
+impl DamageCalculator<'_> {
+    pub fn calculate(&self, kind: AttackKind, rng: &mut impl Rng) -> DamageOutcome {
+        if kind == AttackKind::Melee && rng.gen_range(0..100) < self.evade_chance() {
+            return DamageOutcome::Miss;
+        }
+
+        let base = match kind {
+            AttackKind::Melee => {
+                self.attacker.score.damage + self.attacker.weapon_damage.max(0) as u32
+            }
+            AttackKind::Skill { power, .. } => power + self.attacker.magic.max(0) as u32,
+        };
+        let rolled = base * rng.gen_range(VARIANCE) / 100;
+
+        let defense = match kind {
+            AttackKind::Melee => self.defender.score.defense / 2,
+            AttackKind::Skill { .. } => self.defender.score.defense / 4,
+        };
+        let mut damage = rolled.saturating_sub(defense);
+
+        if let AttackKind::Skill {
+            element: Some(element),
+            ..
+        } = kind
+        {
+            let resist = self.resist(element);
+            damage = damage * (100 - resist) / 100;
+        }
+
+        if self.pvp {
+            let modifier = (100 + self.attacker.attack_pvp - self.defender.defense_pvp).max(0);
+            damage = damage * modifier as u32 / 100;
+        }
+
+        let critical = self.attacker.critical.rolls_crit(rng);
+        if critical {
+            damage *= CRITICAL_MULTIPLIER;
+        }
+
+        DamageOutcome::Hit {
+            damage: damage.max(MIN_DAMAGE),
+            critical,
+        }
+    }
+
+    fn evade_chance(&self) -> i32 {
+        (self.defender.parry - self.attacker.hit_rate).clamp(0, MAX_EVADE_CHANCE)
+    }
+
+    fn resist(&self, element: usize) -> u32 {
+        let resist = self.defender.resist.get(element).copied().unwrap_or(0);
+        (resist - self.attacker.ignore_resistance).clamp(0, 100) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use odin_models::status::Score;
+    use rand::{SeedableRng, rngs::SmallRng};
+
+    fn score(damage: u32, defense: u32) -> ComputedScore {
+        ComputedScore {
+            score: Score {
+                damage,
+                defense,
+                max_hp: 1000,
+                hp: 1000,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn rng() -> SmallRng {
+        SmallRng::seed_from_u64(7)
+    }
+
+    fn damages(calculator: &DamageCalculator, kind: AttackKind) -> Vec<u32> {
+        let mut rng = rng();
+        (0..200)
+            .map(|_| calculator.calculate(kind, &mut rng).damage())
+            .collect()
+    }
+
+    #[test]
+    fn melee_damage_is_reduced_by_half_defense() {
+        let attacker = score(100, 0);
+        let defender = score(0, 100);
+        let calculator = DamageCalculator {
+            attacker: &attacker,
+            defender: &defender,
+            pvp: false,
+        };
+
+        for damage in damages(&calculator, AttackKind::Melee) {
+            assert!((40..=60).contains(&damage), "damage {damage}");
+        }
+    }
+
+    #[test]
+    fn damage_never_drops_below_minimum() {
+        let attacker = score(10, 0);
+        let defender = score(0, 1000);
+        let calculator = DamageCalculator {
+            attacker: &attacker,
+            defender: &defender,
+            pvp: false,
+        };
+
+        assert!(
+            damages(&calculator, AttackKind::Melee)
+                .into_iter()
+                .all(|damage| damage == MIN_DAMAGE)
+        );
+    }
+
+    #[test]
+    fn guaranteed_critical_doubles_damage() {
+        let mut attacker = score(100, 0);
+        attacker.critical += 100.0;
+        let defender = score(0, 0);
+        let calculator = DamageCalculator {
+            attacker: &attacker,
+            defender: &defender,
+            pvp: false,
+        };
+
+        let mut rng = rng();
+        for _ in 0..100 {
+            let outcome = calculator.calculate(AttackKind::Melee, &mut rng);
+            assert!(outcome.is_critical());
+            assert!((180..=220).contains(&outcome.damage()));
+        }
+    }
+
+    #[test]
+    fn parry_above_hit_rate_causes_misses() {
+        let attacker = score(100, 0);
+        let mut defender = score(0, 0);
+        defender.parry = 1000;
+        let calculator = DamageCalculator {
+            attacker: &attacker,
+            defender: &defender,
+            pvp: false,
+        };
+
+        let misses = damages(&calculator, AttackKind::Melee)
+            .into_iter()
+            .filter(|damage| *damage == 0)
+            .count();
+        assert!(misses > 50 && misses < 150, "misses {misses}");
+
+        let mut accurate = attacker.clone();
+        accurate.hit_rate = 1000;
+        let calculator = DamageCalculator {
+            attacker: &accurate,
+            defender: &defender,
+            pvp: false,
+        };
+        assert!(!damages(&calculator, AttackKind::Melee).contains(&0));
+    }
+
+    #[test]
+    fn skill_damage_applies_resist() {
+        let mut attacker = score(0, 0);
+        attacker.magic = 100;
+        let mut defender = score(0, 0);
+        defender.resist = [50, 0, 0, 0];
+        let calculator = DamageCalculator {
+            attacker: &attacker,
+            defender: &defender,
+            pvp: false,
+        };
+
+        let kind = AttackKind::Skill {
+            power: 100,
+            element: Some(0),
+        };
+        for damage in damages(&calculator, kind) {
+            assert!((90..=110).contains(&damage), "damage {damage}");
+        }
+    }
+
+    #[test]
+    fn pvp_modifiers_scale_damage() {
+        let mut attacker = score(100, 0);
+        attacker.attack_pvp = 50;
+        let defender = score(0, 0);
+        let calculator = DamageCalculator {
+            attacker: &attacker,
+            defender: &defender,
+            pvp: true,
+        };
+
+        for damage in damages(&calculator, AttackKind::Melee) {
+            assert!((135..=165).contains(&damage), "damage {damage}");
+        }
+    }
+}
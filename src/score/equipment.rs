@@ -1,6 +1,6 @@
 use odin_models::{EquipmentSlots, effect::Effect, item::Item, item_data::ItemDatabase};
 
-pub(super) fn item_ability(item: &Item, effect: Effect, item_db: &ItemDatabase) -> i32 {
+pub fn item_ability(item: &Item, effect: Effect, item_db: &ItemDatabase) -> i32 {
     let mut total: i32 = 0;
     let effect_index = effect as u8;
 
@@ -0,0 +1,128 @@
+use odin_models::character::Evolution;
+use serde::Deserialize;
+use std::path::Path;
+
+/// Cumulative experience required to reach each level, per evolution.
+/// Entry `n` is the experience needed for level `n`, so the last entry is the
+/// level cap.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ExperienceTable {
+    #[serde(default)]
+    mortal: Vec<i64>,
+    #[serde(default)]
+    arch: Vec<i64>,
+    #[serde(default)]
+    celestial: Vec<i64>,
+    #[serde(default)]
+    sub_celestial: Vec<i64>,
+}
+
+impl ExperienceTable {
+    pub fn load(path: &Path) -> Result<Self, ExperienceTableError> {
+        let contents = std::fs::read_to_string(path)?;
+        Self::from_toml(&contents)
+    }
+
+    pub fn from_toml(contents: &str) -> Result<Self, ExperienceTableError> {
+        let table: ExperienceTable = toml::from_str(contents)?;
+        for evolution in [
+            Evolution::Mortal,
+            Evolution::Arch,
+            Evolution::Celestial,
+            Evolution::SubCelestial,
+        ] {
+            let levels = table.levels(evolution);
+            if let Some(level) = levels.windows(2).position(|pair| pair[1] < pair[0]) {
+                return Err(ExperienceTableError::NotAscending {
+                    evolution,
+                    level: level + 1,
+                });
+            }
+        }
+        Ok(table)
+    }
+
+    pub fn max_level(&self, evolution: Evolution) -> Option<u16> {
+        let len = self.levels(evolution).len();
+        (len > 0).then(|| (len - 1).min(u16::MAX as usize) as u16)
+    }
+
+    /// Experience required to reach `level`, or `None` when the level is
+    /// above the cap.
+    pub fn experience_for(&self, evolution: Evolution, level: u16) -> Option<i64> {
+        self.levels(evolution).get(level as usize).copied()
+    }
+
+    /// Highest level reachable with `experience`, or `None` when the
+    /// evolution has no table.
+    pub fn level_for(&self, evolution: Evolution, experience: i64) -> Option<u16> {
+        let reached = self
+            .levels(evolution)
+            .partition_point(|&required| required <= experience);
+        let max_level = self.max_level(evolution)?;
+        Some((reached.saturating_sub(1) as u16).min(max_level))
+    }
+
+    fn levels(&self, evolution: Evolution) -> &[i64] {
+        match evolution {
+            Evolution::Mortal => &self.mortal,
+            Evolution::Arch => &self.arch,
+            Evolution::Celestial => &self.celestial,
+            Evolution::SubCelestial => &self.sub_celestial,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ExperienceTableError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("TOML parse error: {0}")]
+    TomlParse(#[from] toml::de::Error),
+
+    #[error("{evolution:?} experience decreases at level {level}")]
+    NotAscending { evolution: Evolution, level: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TABLE: &str = r#"
+        mortal = [0, 100, 300, 600]
+        arch = [0, 1000]
+    "#;
+
+    #[test]
+    fn level_for_finds_the_highest_reached_level() {
+        let table = ExperienceTable::from_toml(TABLE).unwrap();
+
+        assert_eq!(table.level_for(Evolution::Mortal, 0), Some(0));
+        assert_eq!(table.level_for(Evolution::Mortal, 99), Some(0));
+        assert_eq!(table.level_for(Evolution::Mortal, 100), Some(1));
+        assert_eq!(table.level_for(Evolution::Mortal, 450), Some(2));
+        assert_eq!(table.level_for(Evolution::Arch, 999), Some(0));
+        assert_eq!(table.level_for(Evolution::Celestial, 999), None);
+    }
+
+    #[test]
+    fn level_for_is_capped_at_the_last_entry() {
+        let table = ExperienceTable::from_toml(TABLE).unwrap();
+
+        assert_eq!(table.max_level(Evolution::Mortal), Some(3));
+        assert_eq!(table.level_for(Evolution::Mortal, i64::MAX), Some(3));
+    }
+
+    #[test]
+    fn rejects_decreasing_tables() {
+        let result = ExperienceTable::from_toml("mortal = [0, 100, 50]");
+        assert!(matches!(
+            result,
+            Err(ExperienceTableError::NotAscending {
+                evolution: Evolution::Mortal,
+                level: 2
+            })
+        ));
+    }
+}
@@ -1,10 +1,13 @@
 pub mod base;
 pub mod critical;
-mod equipment;
+pub mod damage;
+pub mod equipment;
+pub mod experience;
 
 use critical::Critical;
 use odin_models::{
     EquipmentSlots,
+    affect::{AffectKind, AffectList},
     character::{Class, Evolution},
     effect::Effect,
     item_data::ItemDatabase,
@@ -166,6 +169,24 @@ impl<'a> StatBuilder<'a> {
         for (i, effect) in SPECIAL_EFFECTS.iter().enumerate() {
             self.specials[i] += equipment::mob_ability(equipments, *effect, self.item_db);
         }
+        self.regen_hp += equipment::mob_ability(equipments, Effect::RegenHp, self.item_db);
+        self.regen_mp += equipment::mob_ability(equipments, Effect::RegenMp, self.item_db);
+        self
+    }
+
+    pub fn apply_affects(mut self, affects: &AffectList) -> Self {
+        self.damage += affects.sum(AffectKind::Damage);
+        self.defense += affects.sum(AffectKind::Defense);
+        self.max_hp += affects.sum(AffectKind::MaxHp);
+        self.max_mp += affects.sum(AffectKind::MaxMp);
+        self.attack_speed += affects.sum(AffectKind::Haste) - affects.sum(AffectKind::Slow);
+        self.regen_hp += affects.sum(AffectKind::RegenHp);
+        self.regen_mp += affects.sum(AffectKind::RegenMp);
+        let resist = affects.sum(AffectKind::Resist);
+        for value in &mut self.resist {
+            *value += resist;
+        }
+        self.exp_bonus += affects.sum(AffectKind::ExpBonus);
         self
     }
 
@@ -461,6 +482,57 @@ mod tests {
         assert_eq!(result.score.damage, 65); // 5 base + 50 template + 10 runtime
     }
 
+    #[test]
+    fn apply_equipment_adds_regen() {
+        let db = ItemDatabase::from_items([
+            make_item_data(100, Effect::RegenHp, 8),
+            make_item_data(200, Effect::RegenMp, 3),
+        ]);
+        let equips = EquipmentSlots::from([
+            (EquipmentSlot::Amulet1, Item::from(100u16)),
+            (EquipmentSlot::Amulet2, Item::from(200u16)),
+        ]);
+        let base = Score::default();
+
+        let result = StatBuilder::from_base(&base, Class::TransKnight, E, &db)
+            .apply_equipment(&equips)
+            .finalize(0, 0);
+
+        assert_eq!(result.regen_hp, 8);
+        assert_eq!(result.regen_mp, 3);
+    }
+
+    #[test]
+    fn apply_affects_adds_buffs_and_debuffs() {
+        use odin_models::affect::{Affect, AffectSource};
+
+        let db = ItemDatabase::default();
+        let affect = |kind: AffectKind, value| Affect {
+            id: kind as u8,
+            value,
+            remaining_ticks: 10,
+            source: AffectSource::System,
+        };
+        let affects = AffectList::from(vec![
+            affect(AffectKind::Damage, 20),
+            affect(AffectKind::MaxHp, 50),
+            affect(AffectKind::Haste, 15),
+            affect(AffectKind::Slow, 5),
+            affect(AffectKind::Resist, 10),
+        ]);
+        // level 0 mortal TK: damage = 5, max_hp = 80
+        let base = Score::default();
+
+        let result = StatBuilder::from_base(&base, Class::TransKnight, E, &db)
+            .apply_affects(&affects)
+            .finalize(0, 0);
+
+        assert_eq!(result.score.damage, 25);
+        assert_eq!(result.score.max_hp, 130);
+        assert_eq!(result.attack_speed, 10);
+        assert_eq!(result.resist, [10; 4]);
+    }
+
     #[test]
     fn full_pipeline_with_equipment() {
         let db = ItemDatabase::from_items([make_item_data(100, Effect::Damage, 50)]);
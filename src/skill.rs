@@ -0,0 +1,247 @@
+use odin_models::character::Class;
+use odin_models::skill::MAX_SKILLS;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SkillTarget {
+    Enemy,
+    #[serde(rename = "self")]
+    Caster,
+    Ally,
+}
+
+/// What a skill does to each of its targets. Damage and heal amounts grow
+/// with the caster's level: `base + per_level * level`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SkillEffect {
+    Damage {
+        base: u32,
+        #[serde(default)]
+        per_level: u32,
+        element: Option<usize>,
+    },
+    Heal {
+        base: u32,
+        #[serde(default)]
+        per_level: u32,
+    },
+    Affect {
+        affect: u8,
+        value: i32,
+        ticks: u32,
+    },
+}
+impl SkillEffect {
+    pub fn power(&self, level: u16) -> u32 {
+        match self {
+            SkillEffect::Damage {
+                base, per_level, ..
+            }
+            | SkillEffect::Heal { base, per_level } => base + per_level * level as u32,
+            SkillEffect::Affect { .. } => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct SkillData {
+    pub id: u8,
+    pub name: String,
+    /// Class allowed to learn the skill, any class when omitted.
+    pub class: Option<i32>,
+    #[serde(default)]
+    pub level: u16,
+    /// Skill points spent to learn it.
+    #[serde(default)]
+    pub points: i16,
+    /// Item that teaches the skill when used.
+    pub book: Option<u16>,
+    #[serde(default)]
+    pub mana: u32,
+    pub range: u16,
+    #[serde(default)]
+    pub cooldown_ms: u64,
+    pub target: SkillTarget,
+    pub effect: SkillEffect,
+}
+impl SkillData {
+    pub fn cooldown(&self) -> Duration {
+        Duration::from_millis(self.cooldown_ms)
+    }
+
+    /// Mana spent on a cast once the caster's `save_mana` percentage is
+    /// discounted.
+    pub fn mana_cost(&self, save_mana: i32) -> u32 {
+        let save_mana = save_mana.clamp(0, 100) as u32;
+        self.mana * (100 - save_mana) / 100
+    }
+
+    pub fn can_learn(&self, class: Class) -> bool {
+        self.class
+            .is_none_or(|required| required == i32::from(class))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SkillFile {
+    #[serde(default)]
+    skills: Vec<SkillData>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SkillTable {
+    skills: HashMap<u8, SkillData>,
+}
+
+impl SkillTable {
+    pub fn load(path: &Path) -> Result<Self, SkillTableError> {
+        let contents = std::fs::read_to_string(path)?;
+        Self::from_toml(&contents)
+    }
+
+    pub fn from_toml(contents: &str) -> Result<Self, SkillTableError> {
+        let file: SkillFile = toml::from_str(contents)?;
+        let mut skills = HashMap::new();
+        for skill in file.skills {
+            if skill.id as usize >= MAX_SKILLS {
+                return Err(SkillTableError::IdOutOfRange(skill.id));
+            }
+            if let Some(class) = skill.class
+                && Class::try_from(class).is_err()
+            {
+                return Err(SkillTableError::InvalidClass {
+                    id: skill.id,
+                    class,
+                });
+            }
+            if let Some(previous) = skills.insert(skill.id, skill) {
+                return Err(SkillTableError::DuplicateId(previous.id));
+            }
+        }
+        Ok(Self { skills })
+    }
+
+    pub fn get(&self, id: u8) -> Option<&SkillData> {
+        self.skills.get(&id)
+    }
+
+    /// Skill taught by the skill book `item_id`, if any.
+    pub fn by_book(&self, item_id: u16) -> Option<&SkillData> {
+        self.skills
+            .values()
+            .find(|skill| skill.book == Some(item_id))
+    }
+
+    pub fn len(&self) -> usize {
+        self.skills.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.skills.is_empty()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SkillTableError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("TOML parse error: {0}")]
+    TomlParse(#[from] toml::de::Error),
+
+    #[error("Skill id {0} is declared twice")]
+    DuplicateId(u8),
+
+    #[error("Skill id {0} is out of range")]
+    IdOutOfRange(u8),
+
+    #[error("Skill {id} has an invalid class {class}")]
+    InvalidClass { id: u8, class: i32 },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TABLE: &str = r#"
+        [[skills]]
+        id = 1
+        name = "Fire Ball"
+        class = 1
+        level = 10
+        points = 2
+        book = 5001
+        mana = 40
+        range = 8
+        cooldown_ms = 1500
+        target = "enemy"
+        effect = { type = "damage", base = 100, per_level = 3, element = 1 }
+
+        [[skills]]
+        id = 2
+        name = "Heal"
+        range = 6
+        target = "ally"
+        effect = { type = "heal", base = 50 }
+
+        [[skills]]
+        id = 3
+        name = "Haste"
+        range = 0
+        target = "self"
+        effect = { type = "affect", affect = 2, value = 20, ticks = 60 }
+    "#;
+
+    #[test]
+    fn parses_every_effect_and_target() {
+        let table = SkillTable::from_toml(TABLE).unwrap();
+
+        assert_eq!(table.len(), 3);
+        let fire_ball = table.get(1).unwrap();
+        assert_eq!(fire_ball.target, SkillTarget::Enemy);
+        assert_eq!(fire_ball.cooldown(), Duration::from_millis(1500));
+        assert_eq!(fire_ball.effect.power(10), 130);
+        assert!(fire_ball.can_learn(Class::Foema));
+        assert!(!fire_ball.can_learn(Class::TransKnight));
+        assert_eq!(table.get(2).unwrap().effect.power(10), 50);
+        assert_eq!(table.get(3).unwrap().target, SkillTarget::Caster);
+        assert_eq!(table.by_book(5001).map(|skill| skill.id), Some(1));
+    }
+
+    #[test]
+    fn mana_cost_discounts_save_mana() {
+        let table = SkillTable::from_toml(TABLE).unwrap();
+        let fire_ball = table.get(1).unwrap();
+
+        assert_eq!(fire_ball.mana_cost(0), 40);
+        assert_eq!(fire_ball.mana_cost(25), 30);
+        assert_eq!(fire_ball.mana_cost(200), 0);
+    }
+
+    #[test]
+    fn rejects_invalid_tables() {
+        let skill = |id: u8, class: i32| {
+            format!(
+                "[[skills]]\nid = {id}\nname = \"S\"\nclass = {class}\nrange = 1\ntarget = \"self\"\neffect = {{ type = \"heal\", base = 1 }}\n"
+            )
+        };
+
+        assert!(matches!(
+            SkillTable::from_toml(&(skill(1, 0) + &skill(1, 0))),
+            Err(SkillTableError::DuplicateId(1))
+        ));
+        assert!(matches!(
+            SkillTable::from_toml(&skill(64, 0)),
+            Err(SkillTableError::IdOutOfRange(64))
+        ));
+        assert!(matches!(
+            SkillTable::from_toml(&skill(1, 9)),
+            Err(SkillTableError::InvalidClass { id: 1, class: 9 })
+        ));
+    }
+}
@@ -0,0 +1,82 @@
+use crate::session::SessionError;
+use odin_models::item::Item;
+use odin_networking::messages::{client::stall::MAX_STALL_ITEMS, server::stall::StallList};
+use odin_repositories::account_repository::AccountRepositoryError;
+
+/// Players farther than this from a personal shop can't browse or buy
+/// from it.
+pub const STALL_RANGE: u16 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StallItem {
+    /// Where the item is in the owner's inventory.
+    pub inventory_slot: usize,
+    /// The item as it was when the shop opened, so a sale can tell if it
+    /// was moved since.
+    pub item: Item,
+    pub price: u32,
+}
+
+/// A player's personal shop. It's open while the owner stays in place, and
+/// isn't saved, so it's gone when the owner logs out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stall {
+    pub title: String,
+    pub items: [Option<StallItem>; MAX_STALL_ITEMS],
+}
+
+impl Stall {
+    pub fn to_stall_list(&self, owner_id: u16) -> StallList {
+        StallList {
+            owner_id,
+            title: self.title.clone(),
+            items: self
+                .items
+                .iter()
+                .enumerate()
+                .filter_map(|(slot, item)| {
+                    item.map(|item| (slot, item.inventory_slot, item.item, item.price))
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum StallError {
+    #[error("Player not found in world")]
+    PlayerNotFound,
+
+    #[error("Invalid personal shop title")]
+    InvalidTitle,
+
+    #[error("Invalid personal shop item")]
+    InvalidItem,
+
+    #[error("Player is trading")]
+    Trading,
+
+    #[error("Player has no personal shop open")]
+    NoStall,
+
+    #[error("Personal shop is too far away")]
+    OutOfRange,
+
+    #[error("Personal shop changed since it was browsed")]
+    StallChanged,
+
+    #[error("Not enough coin")]
+    NotEnoughCoin,
+
+    #[error("Inventory is full")]
+    InventoryFull,
+
+    #[error("Sale would exceed the coin limit")]
+    CoinLimit,
+
+    #[error(transparent)]
+    Repository(#[from] AccountRepositoryError),
+
+    #[error(transparent)]
+    Session(#[from] SessionError),
+}
@@ -0,0 +1,287 @@
+use crate::map::EntityId;
+use crate::session::SessionError;
+use odin_models::item::Item;
+use odin_repositories::account_repository::AccountRepositoryError;
+use std::collections::HashMap;
+
+/// Traders farther apart than this, e.g. after one of them walks away,
+/// have their trade cancelled.
+pub const TRADE_RANGE: u16 = 8;
+
+/// Items and coin one side puts on the table. Items are kept along with the
+/// inventory slot they were offered from, so the swap can tell if they were
+/// moved afterwards.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TradeOffer {
+    pub items: Vec<(usize, Item)>,
+    pub coin: u32,
+}
+
+/// A side first locks its offer and, once both sides are locked, confirms
+/// it. Any offer change sends both sides back to [`TradeStage::Open`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TradeStage {
+    #[default]
+    Open,
+    Locked,
+    Confirmed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TradeSide {
+    pub partner: EntityId,
+    pub offer: TradeOffer,
+    pub stage: TradeStage,
+}
+
+/// Every trade has one side per trader, each pointing to the other.
+#[derive(Debug, Default)]
+pub struct TradeRegistry {
+    requests: HashMap<EntityId, EntityId>,
+    sides: HashMap<EntityId, TradeSide>,
+}
+
+impl TradeRegistry {
+    pub fn side(&self, entity_id: EntityId) -> Option<&TradeSide> {
+        self.sides.get(&entity_id)
+    }
+
+    pub fn partner_of(&self, entity_id: EntityId) -> Option<EntityId> {
+        self.sides.get(&entity_id).map(|side| side.partner)
+    }
+
+    pub fn pending_request(&self, target: EntityId) -> Option<EntityId> {
+        self.requests.get(&target).copied()
+    }
+
+    /// Records a trade request from `requester` to `target`, replacing any
+    /// previous request `target` had.
+    pub fn request(&mut self, requester: EntityId, target: EntityId) -> Result<(), TradeError> {
+        if requester == target {
+            return Err(TradeError::InvalidTarget);
+        }
+        if self.sides.contains_key(&requester) || self.sides.contains_key(&target) {
+            return Err(TradeError::AlreadyTrading);
+        }
+
+        self.requests.insert(target, requester);
+        Ok(())
+    }
+
+    /// Opens the trade between `target` and the `requester` it accepts.
+    pub fn accept(&mut self, target: EntityId, requester: EntityId) -> Result<(), TradeError> {
+        if self.requests.get(&target) != Some(&requester) {
+            return Err(TradeError::NoRequest);
+        }
+        if self.sides.contains_key(&requester) || self.sides.contains_key(&target) {
+            return Err(TradeError::AlreadyTrading);
+        }
+
+        self.requests.remove(&target);
+        self.requests.remove(&requester);
+        for (entity_id, partner) in [(target, requester), (requester, target)] {
+            self.sides.insert(
+                entity_id,
+                TradeSide {
+                    partner,
+                    offer: TradeOffer::default(),
+                    stage: TradeStage::Open,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    /// Replaces the offer of `entity_id`. Both sides have to lock and
+    /// confirm again, as the partner only agreed to the previous offer.
+    pub fn set_offer(&mut self, entity_id: EntityId, offer: TradeOffer) -> Result<(), TradeError> {
+        let side = self
+            .sides
+            .get_mut(&entity_id)
+            .ok_or(TradeError::NotTrading)?;
+        if side.stage != TradeStage::Open {
+            return Err(TradeError::OfferLocked);
+        }
+        side.offer = offer;
+        let partner = side.partner;
+
+        if let Some(partner) = self.sides.get_mut(&partner) {
+            partner.stage = TradeStage::Open;
+        }
+        Ok(())
+    }
+
+    pub fn lock(&mut self, entity_id: EntityId) -> Result<(), TradeError> {
+        let side = self
+            .sides
+            .get_mut(&entity_id)
+            .ok_or(TradeError::NotTrading)?;
+        if side.stage == TradeStage::Open {
+            side.stage = TradeStage::Locked;
+        }
+        Ok(())
+    }
+
+    /// Confirms the offers of both sides, which have to be locked. Returns
+    /// whether the partner had already confirmed, i.e. the trade is ready
+    /// to be carried out.
+    pub fn confirm(&mut self, entity_id: EntityId) -> Result<bool, TradeError> {
+        let side = self.sides.get(&entity_id).ok_or(TradeError::NotTrading)?;
+        let partner_stage = self
+            .sides
+            .get(&side.partner)
+            .ok_or(TradeError::NotTrading)?
+            .stage;
+        if side.stage == TradeStage::Open || partner_stage == TradeStage::Open {
+            return Err(TradeError::NotLocked);
+        }
+
+        if let Some(side) = self.sides.get_mut(&entity_id) {
+            side.stage = TradeStage::Confirmed;
+        }
+        Ok(partner_stage == TradeStage::Confirmed)
+    }
+
+    /// Ends the trade of `entity_id`, also dropping any request it sent or
+    /// received. Returns the partner it was trading with.
+    pub fn close(&mut self, entity_id: EntityId) -> Option<EntityId> {
+        self.requests.remove(&entity_id);
+        self.requests.retain(|_, requester| *requester != entity_id);
+        let side = self.sides.remove(&entity_id)?;
+        self.sides.remove(&side.partner);
+        Some(side.partner)
+    }
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum TradeError {
+    #[error("Player not found in world")]
+    PlayerNotFound,
+
+    #[error("Invalid trade target")]
+    InvalidTarget,
+
+    #[error("Trade partner is too far away")]
+    OutOfRange,
+
+    #[error("Player is already trading")]
+    AlreadyTrading,
+
+    #[error("No pending trade request from that player")]
+    NoRequest,
+
+    #[error("Player is not trading")]
+    NotTrading,
+
+    #[error("Offer is locked")]
+    OfferLocked,
+
+    #[error("Both offers must be locked before confirming")]
+    NotLocked,
+
+    #[error("Invalid trade offer")]
+    InvalidOffer,
+
+    #[error("Not enough coin for the offer")]
+    NotEnoughCoin,
+
+    #[error("Not enough inventory space for the trade")]
+    InventoryFull,
+
+    #[error("Trade would exceed the coin limit")]
+    CoinLimit,
+
+    #[error(transparent)]
+    Repository(#[from] AccountRepositoryError),
+
+    #[error(transparent)]
+    Session(#[from] SessionError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(id: usize) -> EntityId {
+        EntityId::Player(id)
+    }
+
+    fn open_trade(registry: &mut TradeRegistry, requester: usize, target: usize) {
+        registry.request(player(requester), player(target)).unwrap();
+        registry.accept(player(target), player(requester)).unwrap();
+    }
+
+    #[test]
+    fn accepting_a_request_opens_the_trade() {
+        let mut registry = TradeRegistry::default();
+
+        assert_eq!(
+            registry.request(player(1), player(1)),
+            Err(TradeError::InvalidTarget)
+        );
+        registry.request(player(1), player(2)).unwrap();
+        assert_eq!(registry.pending_request(player(2)), Some(player(1)));
+        assert_eq!(
+            registry.accept(player(2), player(3)),
+            Err(TradeError::NoRequest)
+        );
+        registry.accept(player(2), player(1)).unwrap();
+
+        assert_eq!(registry.partner_of(player(1)), Some(player(2)));
+        assert_eq!(registry.partner_of(player(2)), Some(player(1)));
+        assert_eq!(registry.pending_request(player(2)), None);
+        assert_eq!(
+            registry.request(player(3), player(1)),
+            Err(TradeError::AlreadyTrading)
+        );
+    }
+
+    #[test]
+    fn both_sides_lock_before_confirming() {
+        let mut registry = TradeRegistry::default();
+        open_trade(&mut registry, 1, 2);
+
+        registry.lock(player(1)).unwrap();
+        assert_eq!(registry.confirm(player(1)), Err(TradeError::NotLocked));
+
+        registry.lock(player(2)).unwrap();
+        assert_eq!(registry.confirm(player(1)), Ok(false));
+        assert_eq!(registry.confirm(player(2)), Ok(true));
+    }
+
+    #[test]
+    fn changing_an_offer_unlocks_the_partner() {
+        let mut registry = TradeRegistry::default();
+        open_trade(&mut registry, 1, 2);
+        registry.lock(player(2)).unwrap();
+
+        let offer = TradeOffer {
+            items: vec![],
+            coin: 500,
+        };
+        registry.set_offer(player(1), offer.clone()).unwrap();
+
+        assert_eq!(registry.side(player(1)).unwrap().offer, offer);
+        assert_eq!(registry.side(player(2)).unwrap().stage, TradeStage::Open);
+
+        registry.lock(player(1)).unwrap();
+        assert_eq!(
+            registry.set_offer(player(1), TradeOffer::default()),
+            Err(TradeError::OfferLocked)
+        );
+    }
+
+    #[test]
+    fn closing_ends_both_sides() {
+        let mut registry = TradeRegistry::default();
+        open_trade(&mut registry, 1, 2);
+        registry.request(player(3), player(4)).unwrap();
+
+        assert_eq!(registry.close(player(2)), Some(player(1)));
+        assert_eq!(registry.partner_of(player(1)), None);
+        assert_eq!(registry.close(player(1)), None);
+
+        assert_eq!(registry.close(player(3)), None);
+        assert_eq!(registry.pending_request(player(4)), None);
+    }
+}
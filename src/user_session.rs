@@ -1,10 +1,17 @@
 use crate::{
+    commands::{CommandContext, CommandRegistry},
+    configuration::Configuration,
     game_server_context::GameServerContext,
-    handlers::gameplay::action::ActionType,
+    handlers::gameplay::{
+        action::ActionType,
+        guild::{GuildCommand, send_guild_notice},
+        storage::CoinTransfer,
+    },
     map::EntityId,
     message::Message,
+    npc::spawn_manager::SpawnManager,
     session::{SessionError, SessionTrait},
-    world::World,
+    world::{Mob, World},
 };
 use bytes::Bytes;
 use odin_models::account_charlist::AccountCharlist;
@@ -12,7 +19,8 @@ use odin_networking::{
     WritableResource,
     enc_session::{EncDecError, EncDecSession},
 };
-use odin_repositories::account_repository::AccountRepository;
+use odin_repositories::{account_repository::AccountRepository, guild_repository::GuildRepository};
+use std::time::Instant;
 use tokio::sync::mpsc;
 
 #[derive(Default)]
@@ -31,6 +39,7 @@ pub struct UserSession {
     writer: mpsc::UnboundedSender<Bytes>,
     encdec_session: EncDecSession,
     session: Session,
+    disconnecting: bool,
 }
 impl UserSession {
     pub fn new(
@@ -43,13 +52,15 @@ impl UserSession {
             writer,
             encdec_session,
             session: Session::default(),
+            disconnecting: false,
         }
     }
 
-    pub async fn handle<A: AccountRepository>(
+    pub async fn handle<A: AccountRepository, G: GuildRepository>(
         &mut self,
-        context: &GameServerContext<A>,
+        context: &GameServerContext<A, G>,
         world: &mut World,
+        spawn_manager: &mut SpawnManager,
         message: Message,
     ) {
         let sender = self.get_sender();
@@ -80,19 +91,22 @@ impl UserSession {
 
                 match message {
                     Message::Token(msg) => {
-                        match msg.handle(&sender, account_id, *token, repo).await {
+                        match msg.handle(&sender, context, account_id, *token, repo).await {
                             Ok(()) => *token = true,
-                            Err(e) => log::warn!("Token failed: {e:?}"),
+                            Err(e) => {
+                                log::warn!("Token failed: {e:?}");
+                                self.disconnecting = e.should_disconnect();
+                            }
                         }
                     }
                     Message::CreateCharacter(msg) if *token => {
-                        match msg.handle(&sender, account_id, repo).await {
+                        match msg.handle(&sender, context, account_id, repo).await {
                             Ok(new_charlist) => account_charlist.charlist = new_charlist,
                             Err(e) => log::warn!("CreateCharacter failed: {e:?}"),
                         }
                     }
                     Message::DeleteCharacter(msg) if *token => {
-                        match msg.handle(&sender, account_id, repo).await {
+                        match msg.handle(&sender, context, account_id, repo).await {
                             Ok(new_charlist) => account_charlist.charlist = new_charlist,
                             Err(e) => log::warn!("DeleteCharacter failed: {e:?}"),
                         }
@@ -103,6 +117,20 @@ impl UserSession {
                             .await
                         {
                             Ok(()) => {
+                                let entity_id = EntityId::Player(self.client_id);
+                                if let Some(Mob::Player(player)) = world.get_mob_mut(entity_id) {
+                                    player.access = account_charlist.access.clone();
+                                }
+                                if let Err(e) = send_guild_notice(
+                                    entity_id,
+                                    world,
+                                    context,
+                                    &context.guild_repository,
+                                )
+                                .await
+                                {
+                                    log::warn!("Guild notice failed: {e:?}");
+                                }
                                 self.session = Session::World;
                             }
                             Err(e) => log::warn!("EnterWorld failed: {e:?}"),
@@ -136,11 +164,205 @@ impl UserSession {
                         log::warn!("ActionStop failed: {e:?}");
                     }
                 }
+                Message::Chat(msg) if msg.is_command() => {
+                    let entity_id = EntityId::Player(self.client_id);
+                    if let Some(command) = GuildCommand::parse(msg.message()) {
+                        let creation_cost = context.get_guild_config().creation_cost;
+                        if let Err(e) = command
+                            .handle(
+                                entity_id,
+                                world,
+                                context,
+                                &context.guild_repository,
+                                creation_cost,
+                                context.get_message_catalog(),
+                            )
+                            .await
+                        {
+                            log::warn!("Guild command failed: {e:?}");
+                        }
+                    } else {
+                        let mut command_context = CommandContext {
+                            issuer: entity_id,
+                            world,
+                            spawn_manager,
+                            sender: context,
+                            data: context.data_config(),
+                            messages: context.get_message_catalog(),
+                        };
+                        if let Err(e) =
+                            CommandRegistry::default().handle(&mut command_context, msg.message())
+                        {
+                            log::warn!("Command failed: {e:?}");
+                        }
+                    }
+                }
+                Message::Chat(msg) => {
+                    let entity_id = EntityId::Player(self.client_id);
+                    if let Err(e) = msg.handle(entity_id, world, context) {
+                        log::warn!("Chat failed: {e:?}");
+                    }
+                }
+                Message::Attack(msg) | Message::AttackOne(msg) | Message::AttackTwo(msg) => {
+                    let entity_id = EntityId::Player(self.client_id);
+                    match msg.handle(
+                        entity_id,
+                        world,
+                        context,
+                        &mut rand::thread_rng(),
+                        Instant::now(),
+                    ) {
+                        Ok(killed) => {
+                            for npc_id in killed {
+                                spawn_manager.release_mob_id(npc_id.id());
+                            }
+                        }
+                        Err(e) => log::warn!("Attack failed: {e:?}"),
+                    }
+                }
+                Message::Restart(msg) => {
+                    let entity_id = EntityId::Player(self.client_id);
+                    if let Err(e) = msg.handle(entity_id, world, context) {
+                        log::warn!("Restart failed: {e:?}");
+                    }
+                }
+                Message::MoveItem(msg) => {
+                    let entity_id = EntityId::Player(self.client_id);
+                    if let Err(e) = msg.handle(entity_id, world, context) {
+                        log::warn!("MoveItem failed: {e:?}");
+                    }
+                }
+                Message::OpenMerchant(msg) => {
+                    let entity_id = EntityId::Player(self.client_id);
+                    let merchant = context.get_merchant_config();
+                    if let Err(e) = msg.handle(entity_id, world, context, &merchant) {
+                        log::warn!("OpenMerchant failed: {e:?}");
+                    }
+                }
+                Message::BuyItem(msg) => {
+                    let entity_id = EntityId::Player(self.client_id);
+                    let merchant = context.get_merchant_config();
+                    if let Err(e) = msg.handle(entity_id, world, context, &merchant) {
+                        log::warn!("BuyItem failed: {e:?}");
+                    }
+                }
+                Message::SellItem(msg) => {
+                    let entity_id = EntityId::Player(self.client_id);
+                    let sell_ratio = context.get_merchant_config().sell_ratio;
+                    if let Err(e) = msg.handle(entity_id, world, context, sell_ratio) {
+                        log::warn!("SellItem failed: {e:?}");
+                    }
+                }
+                Message::PartyInvite(msg) => {
+                    let entity_id = EntityId::Player(self.client_id);
+                    if let Err(e) = msg.handle(entity_id, world, context) {
+                        log::warn!("PartyInvite failed: {e:?}");
+                    }
+                }
+                Message::AcceptParty(msg) => {
+                    let entity_id = EntityId::Player(self.client_id);
+                    if let Err(e) = msg.handle(entity_id, world, context) {
+                        log::warn!("AcceptParty failed: {e:?}");
+                    }
+                }
+                Message::RemoveParty(msg) => {
+                    let entity_id = EntityId::Player(self.client_id);
+                    if let Err(e) = msg.handle(entity_id, world, context) {
+                        log::warn!("RemoveParty failed: {e:?}");
+                    }
+                }
+                Message::Trade(msg) => {
+                    let entity_id = EntityId::Player(self.client_id);
+                    if let Err(e) = msg
+                        .handle(entity_id, world, context, &context.account_repository)
+                        .await
+                    {
+                        log::warn!("Trade failed: {e:?}");
+                    }
+                }
+                Message::QuitTrade(msg) => {
+                    let entity_id = EntityId::Player(self.client_id);
+                    if let Err(e) = msg.handle(entity_id, world, context) {
+                        log::warn!("QuitTrade failed: {e:?}");
+                    }
+                }
+                Message::OpenStall(msg) => {
+                    let entity_id = EntityId::Player(self.client_id);
+                    if let Err(e) = msg.handle(entity_id, world, context) {
+                        log::warn!("OpenStall failed: {e:?}");
+                    }
+                }
+                Message::BrowseStall(msg) => {
+                    let entity_id = EntityId::Player(self.client_id);
+                    if let Err(e) = msg.handle(entity_id, world, context) {
+                        log::warn!("BrowseStall failed: {e:?}");
+                    }
+                }
+                Message::BuyStallItem(msg) => {
+                    let entity_id = EntityId::Player(self.client_id);
+                    if let Err(e) = msg
+                        .handle(entity_id, world, context, &context.account_repository)
+                        .await
+                    {
+                        log::warn!("BuyStallItem failed: {e:?}");
+                    }
+                }
+                Message::DepositCoin(msg) => {
+                    let entity_id = EntityId::Player(self.client_id);
+                    if let Err(e) = msg.handle(entity_id, world, context, CoinTransfer::Deposit) {
+                        log::warn!("DepositCoin failed: {e:?}");
+                    }
+                }
+                Message::WithdrawCoin(msg) => {
+                    let entity_id = EntityId::Player(self.client_id);
+                    if let Err(e) = msg.handle(entity_id, world, context, CoinTransfer::Withdraw) {
+                        log::warn!("WithdrawCoin failed: {e:?}");
+                    }
+                }
+                Message::DropItem(msg) => {
+                    let entity_id = EntityId::Player(self.client_id);
+                    if let Err(e) = msg.handle(entity_id, world, context, Instant::now()) {
+                        log::warn!("DropItem failed: {e:?}");
+                    }
+                }
+                Message::PickupItem(msg) => {
+                    let entity_id = EntityId::Player(self.client_id);
+                    let owner_grace = context.get_ground_item_config().owner_grace();
+                    if let Err(e) =
+                        msg.handle(entity_id, world, context, owner_grace, Instant::now())
+                    {
+                        log::warn!("PickupItem failed: {e:?}");
+                    }
+                }
+                Message::UseItem(msg) => {
+                    let entity_id = EntityId::Player(self.client_id);
+                    if let Err(e) = msg.handle(entity_id, world, context, Instant::now()) {
+                        log::warn!("UseItem failed: {e:?}");
+                    }
+                }
+                Message::SetShortSkill(msg) => {
+                    let entity_id = EntityId::Player(self.client_id);
+                    if let Err(e) = msg.handle(entity_id, world) {
+                        log::warn!("SetShortSkill failed: {e:?}");
+                    }
+                }
+                Message::Whisper(msg) => {
+                    let entity_id = EntityId::Player(self.client_id);
+                    let messages = context.get_message_catalog();
+                    if let Err(e) = msg.handle(entity_id, world, context, messages, Instant::now())
+                    {
+                        log::warn!("Whisper failed: {e:?}");
+                    }
+                }
                 message => log::error!("Unhandled message in World state: {:?}", message),
             },
         }
     }
 
+    pub fn is_disconnecting(&self) -> bool {
+        self.disconnecting
+    }
+
     pub fn decrypt(&self, data: &mut [u8]) -> Result<(), EncDecError> {
         self.encdec_session.decrypt(data)?;
         Ok(())
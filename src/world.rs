@@ -1,20 +1,42 @@
 use crate::map::{EntityId, InsertResult, Map, MapError, MoveResult, RemoveResult};
-use crate::npc::Npc;
-use crate::score::base::{base_class_stats, master_points, score_points};
+use crate::npc::{MERCHANT_RANGE, Npc};
+use crate::packets::{BroadcastUpdateScore, ToGroundItemPackets, ToUpdateEtc};
+use crate::party::{PARTY_EXPERIENCE_RANGE, PartyRegistry};
+use crate::score::base::{base_class_stats, master_points, score_points, skill_points};
+use crate::score::experience::ExperienceTable;
 use crate::score::{ComputedScore, StatBuilder};
+use crate::session::{PacketSender, SessionError};
+use crate::skill::SkillTable;
+use crate::stall::Stall;
+use crate::trade::TradeRegistry;
+use odin_models::MAX_COIN;
+use odin_models::account::AccessLevel;
+use odin_models::affect::{Affect, AffectList};
 use odin_models::character::Character;
 use odin_models::character::{Class, Evolution, GuildLevel};
+use odin_models::item::Item;
 use odin_models::item_data::ItemDatabase;
 use odin_models::position::Position;
+use odin_models::skill::{LearnedSkills, SkillBar};
 use odin_models::status::Score;
+use odin_models::storage::Storage;
 use odin_models::uuid::Uuid;
 use odin_models::{EquipmentSlots, InventorySlots};
+use odin_networking::messages::server::motion::{MOTION_LEVEL_UP, Motion};
+use rand::Rng;
 use std::collections::HashMap;
+use std::time::Instant;
 
 pub struct World {
     map: Map,
     entities: HashMap<EntityId, Mob>,
+    player_names: HashMap<String, EntityId>,
+    followers: HashMap<EntityId, Vec<EntityId>>,
     item_db: ItemDatabase,
+    experience_table: ExperienceTable,
+    skill_table: SkillTable,
+    parties: PartyRegistry,
+    trades: TradeRegistry,
 }
 
 impl World {
@@ -22,14 +44,178 @@ impl World {
         Self {
             map: Map::new(),
             entities: HashMap::new(),
+            player_names: HashMap::new(),
+            followers: HashMap::new(),
             item_db,
+            experience_table: ExperienceTable::default(),
+            skill_table: SkillTable::default(),
+            parties: PartyRegistry::default(),
+            trades: TradeRegistry::default(),
         }
     }
 
+    pub fn experience_table(&self) -> &ExperienceTable {
+        &self.experience_table
+    }
+
     pub fn item_db(&self) -> &ItemDatabase {
         &self.item_db
     }
 
+    pub fn set_experience_table(&mut self, experience_table: ExperienceTable) {
+        self.experience_table = experience_table;
+    }
+
+    pub fn skills(&self) -> &SkillTable {
+        &self.skill_table
+    }
+
+    pub fn set_skill_table(&mut self, skill_table: SkillTable) {
+        self.skill_table = skill_table;
+    }
+
+    pub fn parties(&self) -> &PartyRegistry {
+        &self.parties
+    }
+
+    pub fn parties_mut(&mut self) -> &mut PartyRegistry {
+        &mut self.parties
+    }
+
+    pub fn trades(&self) -> &TradeRegistry {
+        &self.trades
+    }
+
+    pub fn trades_mut(&mut self) -> &mut TradeRegistry {
+        &mut self.trades
+    }
+
+    /// Party members, `entity_id` included, standing within `range` of it.
+    /// Just `entity_id` when it's not grouped.
+    pub fn party_members_in_range(&self, entity_id: EntityId, range: u16) -> Vec<EntityId> {
+        let Some(position) = self.map.get_position(entity_id) else {
+            return vec![];
+        };
+        self.parties
+            .members_of(entity_id)
+            .into_iter()
+            .filter(|member| {
+                self.map
+                    .get_position(*member)
+                    .is_some_and(|other| other.chebyshev_distance(position) <= range)
+            })
+            .collect()
+    }
+
+    /// Party members that can't see `entity_id` on the map, and so need
+    /// its HP and position sent to them directly.
+    pub fn party_members_out_of_view(&self, entity_id: EntityId) -> Vec<EntityId> {
+        let Some(party) = self.parties.party_of(entity_id) else {
+            return vec![];
+        };
+        let spectators = self
+            .map
+            .get_position(entity_id)
+            .map(|position| self.map.get_spectators(position, entity_id))
+            .unwrap_or_default();
+        party
+            .members()
+            .iter()
+            .copied()
+            .filter(|member| *member != entity_id && !spectators.contains(member))
+            .collect()
+    }
+
+    /// Adds experience to a player and applies every level-up it earns,
+    /// up to the table's level cap. Returns the number of levels gained.
+    pub fn gain_experience<P: PacketSender>(
+        &mut self,
+        entity_id: EntityId,
+        experience: i64,
+        sender: &P,
+    ) -> Result<u16, SessionError> {
+        let Some(Mob::Player(player)) = self.entities.get_mut(&entity_id) else {
+            return Ok(0);
+        };
+        player.experience = player.experience.saturating_add(experience.max(0));
+
+        let old_level = player.score.level;
+        let new_level = self
+            .experience_table
+            .level_for(player.evolution, player.experience)
+            .map_or(old_level, |level| level.max(old_level));
+        if new_level == old_level {
+            sender.send_to(entity_id, player.to_update_etc())?;
+            return Ok(0);
+        }
+
+        let new_level = self.set_level(entity_id, new_level, sender)?;
+        Ok(new_level - old_level)
+    }
+
+    /// Sets a player's level, capped by the experience table, and matches
+    /// its experience and skill points to it. HP and MP are refilled, and a
+    /// higher level plays the level-up motion to the player and its
+    /// spectators. Returns the level set, which stays the same when the
+    /// player's evolution has no table.
+    pub fn set_level<P: PacketSender>(
+        &mut self,
+        entity_id: EntityId,
+        level: u16,
+        sender: &P,
+    ) -> Result<u16, SessionError> {
+        let Some(Mob::Player(player)) = self.entities.get_mut(&entity_id) else {
+            return Ok(level);
+        };
+        let old_level = player.score.level;
+        let Some(max_level) = self.experience_table.max_level(player.evolution) else {
+            return Ok(old_level);
+        };
+        let new_level = level.min(max_level);
+
+        if self
+            .experience_table
+            .level_for(player.evolution, player.experience)
+            != Some(new_level)
+            && let Some(experience) = self
+                .experience_table
+                .experience_for(player.evolution, new_level)
+        {
+            player.experience = experience;
+        }
+        player.score.level = new_level;
+        let skill_bonus = player.skill_bonus as i32 + skill_points(new_level, player.evolution)
+            - skill_points(old_level, player.evolution);
+        player.skill_bonus = skill_bonus.clamp(0, i16::MAX as i32) as i16;
+        player.calculate_bonus_points();
+        sender.send_to(entity_id, player.to_update_etc())?;
+        if new_level == old_level {
+            return Ok(new_level);
+        }
+
+        self.recalculate_score(entity_id);
+        if let Some(Mob::Player(player)) = self.entities.get_mut(&entity_id) {
+            player.computed.score.hp = player.computed.score.max_hp;
+            player.computed.score.mp = player.computed.score.max_mp;
+        }
+        self.broadcast_update_score(entity_id, sender)?;
+
+        if new_level > old_level
+            && let Some(position) = self.map.get_position(entity_id)
+        {
+            let level_up = || Motion {
+                mob_id: entity_id.id() as u16,
+                motion: MOTION_LEVEL_UP,
+                parm: 3,
+            };
+            sender.send_to(entity_id, level_up())?;
+            for spectator in self.map.get_spectators(position, entity_id) {
+                sender.send_to(spectator, level_up())?;
+            }
+        }
+        Ok(new_level)
+    }
+
     pub fn recalculate_score(&mut self, entity_id: EntityId) -> bool {
         let Some(mob) = self.entities.get_mut(&entity_id) else {
             return false;
@@ -45,6 +231,7 @@ impl World {
                     &self.item_db,
                 )
                 .apply_equipment(&player.equipments)
+                .apply_affects(&player.affects)
                 .finalize(hp, mp);
                 let changed = player.computed != new_computed;
                 player.computed = new_computed;
@@ -54,6 +241,50 @@ impl World {
         }
     }
 
+    /// Puts an affect on a mob, replacing one with the same id, and
+    /// broadcasts the new affect bytes and score. Returns false when the mob
+    /// is gone or already carries `MAX_AFFECT` affects.
+    pub fn add_affect<P: PacketSender>(
+        &mut self,
+        entity_id: EntityId,
+        affect: Affect,
+        sender: &P,
+    ) -> Result<bool, SessionError> {
+        let Some(affects) = self.affects_mut(entity_id) else {
+            return Ok(false);
+        };
+        if !affects.insert(affect) {
+            return Ok(false);
+        }
+        self.recalculate_score(entity_id);
+        self.broadcast_update_score(entity_id, sender)?;
+        Ok(true)
+    }
+
+    pub fn remove_affect<P: PacketSender>(
+        &mut self,
+        entity_id: EntityId,
+        id: u8,
+        sender: &P,
+    ) -> Result<Option<Affect>, SessionError> {
+        let Some(removed) = self
+            .affects_mut(entity_id)
+            .and_then(|affects| affects.remove(id))
+        else {
+            return Ok(None);
+        };
+        self.recalculate_score(entity_id);
+        self.broadcast_update_score(entity_id, sender)?;
+        Ok(Some(removed))
+    }
+
+    pub fn affects_mut(&mut self, entity_id: EntityId) -> Option<&mut AffectList> {
+        match self.entities.get_mut(&entity_id)? {
+            Mob::Player(player) => Some(&mut player.affects),
+            Mob::Npc(npc) => Some(&mut npc.affects),
+        }
+    }
+
     pub fn add_player(
         &mut self,
         entity_id: EntityId,
@@ -61,16 +292,37 @@ impl World {
         position: Position,
     ) -> Result<InsertResult, MapError> {
         let result = self.map.force_insert(entity_id, position)?;
+        self.player_names
+            .insert(player.name.to_lowercase(), entity_id);
         self.entities.insert(entity_id, Mob::Player(player));
         Ok(result)
     }
 
     pub fn remove_entity(&mut self, id: EntityId) -> Result<RemoveResult, MapError> {
         let result = self.map.remove(id)?;
-        self.entities.remove(&id);
+        match self.entities.remove(&id) {
+            Some(Mob::Player(player)) => {
+                self.player_names.remove(&player.name.to_lowercase());
+            }
+            Some(Mob::Npc(npc)) => {
+                if let Some(leader) = npc.leader
+                    && let Some(followers) = self.followers.get_mut(&leader)
+                {
+                    followers.retain(|follower| *follower != id);
+                    if followers.is_empty() {
+                        self.followers.remove(&leader);
+                    }
+                }
+            }
+            None => {}
+        }
         Ok(result)
     }
 
+    pub fn find_player_by_name(&self, name: &str) -> Option<EntityId> {
+        self.player_names.get(&name.to_lowercase()).copied()
+    }
+
     pub fn move_entity(
         &mut self,
         id: EntityId,
@@ -94,10 +346,22 @@ impl World {
         position: Position,
     ) -> Result<InsertResult, MapError> {
         let result = self.map.force_insert(entity_id, position)?;
+        if let Some(leader) = npc.leader {
+            self.followers.entry(leader).or_default().push(entity_id);
+        }
         self.entities.insert(entity_id, Mob::Npc(npc));
         Ok(result)
     }
 
+    /// Followers spawned under `leader` that are still in the world. The list
+    /// outlives the leader, so a leaderless group still acts together.
+    pub fn followers_of(&self, leader: EntityId) -> &[EntityId] {
+        self.followers
+            .get(&leader)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
     pub fn get_mob(&self, id: EntityId) -> Option<&Mob> {
         self.entities.get(&id)
     }
@@ -130,6 +394,87 @@ impl World {
             .collect()
     }
 
+    pub fn player_ids(&self) -> Vec<EntityId> {
+        self.entities
+            .iter()
+            .filter_map(|(id, mob)| match mob {
+                Mob::Player(_) => Some(*id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn merchant_in_reach(&self, entity_id: EntityId, merchant: i16) -> bool {
+        let Some(position) = self.map.get_position(entity_id) else {
+            return false;
+        };
+        self.map
+            .get_spectators(position, entity_id)
+            .into_iter()
+            .any(|id| {
+                self.get_npc(id)
+                    .is_some_and(|npc| npc.template.merchant == merchant)
+                    && self.map.get_position(id).is_some_and(|npc_position| {
+                        npc_position.chebyshev_distance(position) <= MERCHANT_RANGE
+                    })
+            })
+    }
+
+    /// Hands out the rewards of a dead NPC: coin goes to the killer, experience
+    /// is split among its party members in range and template inventory items
+    /// roll their loot chance to drop.
+    /// Must be called before the NPC is removed from the world.
+    pub fn on_npc_killed<P: PacketSender>(
+        &mut self,
+        killer: EntityId,
+        npc_id: EntityId,
+        sender: &P,
+        rng: &mut impl Rng,
+        now: Instant,
+    ) -> Result<(), SessionError> {
+        let (Some(npc), Some(position)) = (self.get_npc(npc_id), self.map.get_position(npc_id))
+        else {
+            return Ok(());
+        };
+        let experience = npc.template.experience;
+        let coin = npc.template.coin;
+        let drops: Vec<Item> = npc
+            .template
+            .loot
+            .iter()
+            .filter(|drop| rng.gen_range(0.0..1.0) < drop.chance)
+            .filter_map(|drop| npc.template.inventory.get(drop.slot).copied())
+            .collect();
+
+        let owner = match self.get_mob_mut(killer) {
+            Some(Mob::Player(player)) => {
+                player.coin = (player.coin as i64 + coin.max(0) as i64).min(MAX_COIN as i64) as i32;
+                let members = self.party_members_in_range(killer, PARTY_EXPERIENCE_RANGE);
+                let share = experience / members.len().max(1) as i64;
+                for member in members {
+                    self.gain_experience(member, share, sender)?;
+                }
+                Some(killer)
+            }
+            _ => None,
+        };
+
+        for item in drops {
+            let Ok(id) = self.map.drop_item(item, position, owner, now) else {
+                log::debug!("No room to drop loot {} of {npc_id:?}", item.id);
+                continue;
+            };
+            let ground_item = self
+                .map
+                .ground_item(id)
+                .expect("dropped loot is on the ground");
+            for viewer in self.map.get_viewers(ground_item.position) {
+                sender.send_to(viewer, ground_item.to_create_ground_item())?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn entity_exists(&self, id: EntityId) -> bool {
         self.entities.contains_key(&id)
     }
@@ -167,10 +512,22 @@ impl Mob {
             Mob::Npc(_) => false,
         }
     }
+
+    pub fn affects(&self) -> &AffectList {
+        match self {
+            Mob::Player(player) => &player.affects,
+            Mob::Npc(npc) => &npc.affects,
+        }
+    }
+
+    pub fn is_invisible(&self) -> bool {
+        matches!(self, Mob::Player(player) if player.invisible)
+    }
 }
 
 pub struct Player {
     pub entity_id: EntityId,
+    pub account_id: Uuid,
     pub identifier: Uuid,
     pub name: String,
     pub slot: i32,
@@ -187,10 +544,22 @@ pub struct Player {
     pub last_pos: Position,
     pub inventory: InventorySlots,
     pub equipments: EquipmentSlots,
+    pub affects: AffectList,
+    pub learned_skills: LearnedSkills,
+    pub skill_bar: SkillBar,
+    pub storage: Box<Storage>,
     pub computed: ComputedScore,
     pub score_bonus: i16,
     pub special_bonus: i16,
     pub skill_bonus: i16,
+    pub last_shout: Option<Instant>,
+    /// Guild the player was last invited to, until it accepts.
+    pub guild_invite: Option<i16>,
+    pub stall: Option<Box<Stall>>,
+    pub item_cooldowns: HashMap<u16, Instant>,
+    pub skill_cooldowns: HashMap<u8, Instant>,
+    pub access: Option<AccessLevel>,
+    pub invisible: bool,
 }
 
 impl Player {
@@ -199,6 +568,7 @@ impl Player {
         let mp = character.score.mp;
         Self {
             entity_id,
+            account_id: Uuid::default(),
             identifier: character.identifier,
             name: character.name,
             slot: character.slot,
@@ -215,6 +585,10 @@ impl Player {
             last_pos: character.last_pos,
             inventory: character.inventory,
             equipments: character.equipments,
+            affects: character.affects,
+            learned_skills: character.learned_skills,
+            skill_bar: character.skill_bar,
+            storage: Box::default(),
             computed: ComputedScore {
                 score: Score {
                     hp,
@@ -225,7 +599,14 @@ impl Player {
             },
             score_bonus: 0,
             special_bonus: 0,
-            skill_bonus: 0,
+            skill_bonus: character.skill_bonus,
+            last_shout: None,
+            guild_invite: None,
+            stall: None,
+            item_cooldowns: HashMap::new(),
+            skill_cooldowns: HashMap::new(),
+            access: None,
+            invisible: false,
         }
     }
 
@@ -233,6 +614,35 @@ impl Player {
         self.entity_id
     }
 
+    pub fn to_character(&self) -> Character {
+        Character {
+            identifier: self.identifier,
+            name: self.name.clone(),
+            slot: self.slot,
+            score: Score {
+                hp: self.computed.score.hp,
+                mp: self.computed.score.mp,
+                ..self.score
+            },
+            evolution: self.evolution,
+            merchant: self.merchant,
+            guild: self.guild,
+            guild_level: self.guild_level,
+            class: self.class,
+            affect_info: self.affect_info,
+            quest_info: self.quest_info,
+            coin: self.coin,
+            experience: self.experience,
+            last_pos: self.last_pos,
+            inventory: self.inventory.clone(),
+            equipments: self.equipments.clone(),
+            affects: self.affects.clone(),
+            learned_skills: self.learned_skills,
+            skill_bar: self.skill_bar,
+            skill_bonus: self.skill_bonus,
+        }
+    }
+
     pub fn revive(&mut self) -> bool {
         if self.computed.score.hp > 0 {
             return false;
@@ -268,7 +678,7 @@ mod tests {
     use super::*;
     use crate::npc::Npc;
     use crate::npc::movement::{MovementBehavior, MovementState};
-    use odin_models::npc_mob::NpcMob;
+    use odin_models::npc_mob::{LootDrop, NpcMob};
 
     fn make_npc(id: usize) -> (EntityId, Npc) {
         let entity_id = EntityId::Mob(id);
@@ -370,6 +780,26 @@ mod tests {
         assert!(result.spectators.contains(&npc_id));
     }
 
+    #[test]
+    fn find_player_by_name_ignores_case() {
+        let mut world = World::default();
+        let player_id = EntityId::Player(1);
+        let player = Player::from_character(
+            player_id,
+            Character {
+                name: "Test".to_string(),
+                ..Default::default()
+            },
+        );
+        world
+            .add_player(player_id, player, pos(2100, 2100))
+            .unwrap();
+
+        assert_eq!(world.find_player_by_name("tEST"), Some(player_id));
+        world.remove_entity(player_id).unwrap();
+        assert_eq!(world.find_player_by_name("Test"), None);
+    }
+
     #[test]
     fn recalculate_score_noop_for_npc() {
         let mut world = World::default();
@@ -377,4 +807,187 @@ mod tests {
         world.add_npc(id, npc, pos(2100, 2100)).unwrap();
         assert!(!world.recalculate_score(id));
     }
+
+    #[test]
+    fn on_npc_killed_rewards_killer_and_drops_loot() {
+        use crate::handlers::tests::MockPacketSender;
+        use odin_networking::messages::ServerMessage;
+        use rand::{SeedableRng, rngs::SmallRng};
+
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let killer = EntityId::Player(1);
+        let player = Player::from_character(
+            killer,
+            Character {
+                coin: 50,
+                ..Default::default()
+            },
+        );
+        world.add_player(killer, player, pos(2100, 2100)).unwrap();
+
+        let npc_id = EntityId::Mob(1000);
+        let mut template = NpcMob {
+            coin: 100,
+            experience: 500,
+            loot: vec![
+                LootDrop {
+                    slot: 0,
+                    chance: 1.0,
+                },
+                LootDrop {
+                    slot: 1,
+                    chance: 0.0,
+                },
+            ],
+            ..Default::default()
+        };
+        template.inventory.set(0, Item::from(1101u16));
+        template.inventory.set(1, Item::from(1102u16));
+        let movement = MovementState::new(MovementBehavior::Stationary, 1);
+        world
+            .add_npc(
+                npc_id,
+                Npc::new(npc_id, template, movement),
+                pos(2102, 2100),
+            )
+            .unwrap();
+
+        let now = Instant::now();
+        world
+            .on_npc_killed(
+                killer,
+                npc_id,
+                &sender,
+                &mut SmallRng::seed_from_u64(1),
+                now,
+            )
+            .unwrap();
+
+        let Some(Mob::Player(player)) = world.get_mob(killer) else {
+            panic!("expected Player");
+        };
+        assert_eq!(player.experience, 500);
+        assert_eq!(player.coin, 150);
+
+        let drops = world.map().ground_items_in_view(pos(2102, 2100));
+        assert_eq!(drops.len(), 1);
+        assert_eq!(drops[0].item.id, 1101);
+        assert_eq!(drops[0].owner, Some(killer));
+
+        let identifiers: Vec<_> = sender
+            .messages_for(killer)
+            .iter()
+            .map(|packet| packet.identifier)
+            .collect();
+        assert_eq!(
+            identifiers,
+            vec![ServerMessage::UpdateEtc, ServerMessage::CreateGroundItem]
+        );
+    }
+
+    #[test]
+    fn on_npc_killed_splits_experience_with_party_in_range() {
+        use crate::handlers::tests::MockPacketSender;
+        use rand::{SeedableRng, rngs::SmallRng};
+
+        let mut world = World::default();
+        let sender = MockPacketSender::default();
+        let [killer, near, far] = [1, 2, 3].map(EntityId::Player);
+        for (entity_id, position) in [
+            (killer, pos(2100, 2100)),
+            (near, pos(2120, 2120)),
+            (far, pos(2500, 2500)),
+        ] {
+            let player = Player::from_character(entity_id, Character::default());
+            world.add_player(entity_id, player, position).unwrap();
+        }
+        for member in [near, far] {
+            world.parties_mut().invite(killer, member).unwrap();
+            world.parties_mut().accept(member, killer).unwrap();
+        }
+
+        let npc_id = EntityId::Mob(1000);
+        let template = NpcMob {
+            coin: 100,
+            experience: 500,
+            ..Default::default()
+        };
+        let movement = MovementState::new(MovementBehavior::Stationary, 1);
+        world
+            .add_npc(
+                npc_id,
+                Npc::new(npc_id, template, movement),
+                pos(2102, 2100),
+            )
+            .unwrap();
+        world
+            .on_npc_killed(
+                killer,
+                npc_id,
+                &sender,
+                &mut SmallRng::seed_from_u64(1),
+                Instant::now(),
+            )
+            .unwrap();
+
+        let player = |entity_id| match world.get_mob(entity_id) {
+            Some(Mob::Player(player)) => (player.experience, player.coin),
+            _ => panic!("expected Player"),
+        };
+        assert_eq!(player(killer), (250, 100));
+        assert_eq!(player(near), (250, 0));
+        assert_eq!(player(far), (0, 0));
+    }
+
+    #[test]
+    fn gain_experience_applies_multi_level_jumps_up_to_the_cap() {
+        use crate::handlers::tests::MockPacketSender;
+        use odin_networking::messages::ServerMessage;
+
+        let mut world = World::default();
+        world.set_experience_table(
+            ExperienceTable::from_toml("mortal = [0, 100, 300, 600]").unwrap(),
+        );
+        let sender = MockPacketSender::default();
+        let entity_id = EntityId::Player(1);
+        let watcher = EntityId::Player(2);
+        for (id, position) in [(entity_id, pos(2100, 2100)), (watcher, pos(2102, 2100))] {
+            let player = Player::from_character(id, Character::default());
+            world.add_player(id, player, position).unwrap();
+        }
+        world.recalculate_score(entity_id);
+
+        assert_eq!(world.gain_experience(entity_id, 50, &sender), Ok(0));
+        assert_eq!(world.gain_experience(entity_id, 300, &sender), Ok(2));
+        let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+            panic!("expected Player");
+        };
+        assert_eq!(player.score.level, 2);
+        assert_eq!(player.skill_bonus, 6);
+        assert_eq!(player.computed.score.hp, player.computed.score.max_hp);
+
+        assert_eq!(world.gain_experience(entity_id, 10_000, &sender), Ok(1));
+        assert_eq!(world.gain_experience(entity_id, 10_000, &sender), Ok(0));
+        let Some(Mob::Player(player)) = world.get_mob(entity_id) else {
+            panic!("expected Player");
+        };
+        assert_eq!(player.score.level, 3);
+        assert_eq!(player.experience, 20_350);
+
+        let watcher_packets: Vec<_> = sender
+            .messages_for(watcher)
+            .iter()
+            .map(|packet| packet.identifier)
+            .collect();
+        assert_eq!(
+            watcher_packets,
+            vec![
+                ServerMessage::UpdateScore,
+                ServerMessage::Motion,
+                ServerMessage::UpdateScore,
+                ServerMessage::Motion
+            ]
+        );
+    }
 }